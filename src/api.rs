@@ -1,3 +1,13 @@
+
 pub mod auth;
+pub mod client;
+pub mod error;
 pub mod namespace;
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod config_zip;
+pub mod service;
+pub mod instance;
+pub mod watch;
+
+#[cfg(test)]
+pub mod test_server;
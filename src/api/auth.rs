@@ -1,50 +1,51 @@
+use reqwest::StatusCode;
+
+use crate::api::error::NacosError;
 use crate::resp::auth_login_resp::AuthLoginResp;
-use crate::config::{load_config, AppConfig};
 
-pub async fn login(url: &str, username: &str, password: &str) -> Result<AuthLoginResp, String> {
-    let client = reqwest::Client::new();
-    let resp = client.post(format!("{}/nacos/v1/auth/login", url))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(format!("username={}&password={}", username, password))
+pub async fn login(
+    http: &reqwest::Client,
+    url: &str,
+    username: &str,
+    password: &str,
+) -> Result<AuthLoginResp, NacosError> {
+    let response = http
+        .post(format!("{}/nacos/v1/auth/login", url))
+        .form(&[("username", username), ("password", password)])
         .send()
-        .await;
+        .await?;
 
-        match resp {
-            Ok(response) => {
-                if response.status().is_success() {
-                    let json_resp = response.json::<AuthLoginResp>().await.map_err(|e| e.to_string())?;
-                    Ok(json_resp)
-                } else {
-                    let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                    Err(format!("Request failed: {}", text_resp))
-                }
-            }
-            Err(e) => Err(format!("Network error: {}", e)),
+    let status = response.status();
+    let text = response.text().await?;
+    match status {
+        _ if status.is_success() => {
+            serde_json::from_str::<AuthLoginResp>(&text).map_err(|e| NacosError::decode(e, &text))
+        }
+        // 账号密码错误时 Nacos 返回 403 "unknown user!"
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(NacosError::Unauthorized(text.trim().to_string()))
         }
+        _ => Err(NacosError::from_status(status, &text)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::load_config;
-    use crate::config::AppConfig;
+    use crate::api::test_server::FakeNacos;
 
     #[tokio::test]
     async fn test_login() {
-        let app_config: AppConfig = load_config();
-        let resp = login(
-            &app_config.nacos.url, 
-            &app_config.nacos.username, 
-            &app_config.nacos.password).await;
+        let (url, _fake) = FakeNacos::spawn().await;
+        let resp = login(&reqwest::Client::new(), &url, "nacos", "nacos").await;
         assert!(resp.is_ok(), "Login failed: {:?}", resp.err());
     }
 
     #[tokio::test]
     async fn test_login_with_invalid_credentials() {
-        let app_config: AppConfig = load_config();
-        let resp = login(
-            &app_config.nacos.url, "wrong_user", "wrong_pass").await;
+        let (url, _fake) = FakeNacos::spawn().await;
+        let resp = login(&reqwest::Client::new(), &url, "wrong_user", "wrong_pass").await;
         println!("Login response: {:?}", resp);
-        assert!(resp.is_err());
+        assert!(matches!(resp, Err(NacosError::Unauthorized(_))));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+use crate::api::auth::login;
+use crate::api::error::NacosError;
+use crate::config::NacosConfig;
+
+// token 在到期前提前刷新的余量，避免请求途中过期
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct Token {
+    access_token: String,
+    expires_at: Instant,
+}
+
+// Nacos 客户端：持有服务地址、账号和复用连接的 http client，
+// 首次请求时登录，token 过期或被服务端拒绝时自动重新登录并重试
+#[derive(Clone)]
+pub struct NacosClient {
+    url: String,
+    username: String,
+    password: String,
+    http: reqwest::Client,
+    token: Arc<Mutex<Option<Token>>>,
+}
+
+impl NacosClient {
+    pub fn new(url: &str, username: &str, password: &str) -> NacosClient {
+        NacosClient {
+            url: url.trim_end_matches('/').to_string(),
+            username: username.to_string(),
+            password: password.to_string(),
+            http: reqwest::Client::builder()
+                .connect_timeout(CONNECT_TIMEOUT)
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .expect("failed to build http client"),
+            token: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn from_config(config: &NacosConfig) -> NacosClient {
+        NacosClient::new(&config.url, &config.username, &config.password)
+    }
+
+    // 返回可用的 access_token，没有或即将过期时重新登录
+    async fn access_token(&self) -> Result<String, NacosError> {
+        let mut token = self.token.lock().await;
+        if let Some(token) = token.as_ref()
+            && Instant::now() + TOKEN_REFRESH_MARGIN < token.expires_at
+        {
+            return Ok(token.access_token.clone());
+        }
+
+        let resp = login(&self.http, &self.url, &self.username, &self.password).await?;
+        *token = Some(Token {
+            access_token: resp.accessToken.clone(),
+            expires_at: Instant::now() + Duration::from_secs(resp.tokenTtl.max(0) as u64),
+        });
+        Ok(resp.accessToken)
+    }
+
+    // 只丢弃被拒绝的那个 token，避免并发请求把刚刷新的 token 也清掉
+    async fn invalidate_token(&self, rejected: &str) {
+        let mut token = self.token.lock().await;
+        if token.as_ref().is_some_and(|t| t.access_token == rejected) {
+            *token = None;
+        }
+    }
+
+    // 发送带 accessToken 的请求，成功时返回响应体文本。
+    // build 用来补充查询参数/表单，token 失效重试时会被再次调用
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<String, NacosError> {
+        let body = self.send_bytes(method, path, build).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    // 同 send，返回原始响应体，用于导出 zip 等二进制内容
+    pub(crate) async fn send_bytes(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Vec<u8>, NacosError> {
+        let mut retried = false;
+        loop {
+            let access_token = self.access_token().await?;
+            let request = self
+                .http
+                .request(method.clone(), format!("{}{}", self.url, path))
+                .query(&[("accessToken", &access_token)]);
+            let response = build(request).send().await?;
+
+            let status = response.status();
+            let body = response.bytes().await?.to_vec();
+            if status.is_success() {
+                return Ok(body);
+            }
+            let text = String::from_utf8_lossy(&body);
+            if !retried && is_token_rejected(status, &text) {
+                self.invalidate_token(&access_token).await;
+                retried = true;
+                continue;
+            }
+            return Err(NacosError::from_status(status, &text));
+        }
+    }
+}
+
+pub(crate) fn parse_json<T: DeserializeOwned>(text: &str) -> Result<T, NacosError> {
+    serde_json::from_str::<T>(text).map_err(|e| NacosError::decode(e, text))
+}
+
+// Nacos 对过期/无效 token 返回 403（部分版本为 401），响应体形如 "token expired!"；
+// 其他 403 是没有权限，重新登录也没用
+fn is_token_rejected(status: StatusCode, body: &str) -> bool {
+    status == StatusCode::UNAUTHORIZED || body.contains("token expired") || body.contains("token invalid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_server::FakeNacos;
+
+    #[tokio::test]
+    async fn test_login_lazily_and_reuse_token() {
+        let (url, fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "nacos", "nacos");
+        assert_eq!(fake.lock().unwrap().login_count, 0);
+
+        client.list_namespaces().await.unwrap();
+        client.list_namespaces().await.unwrap();
+        assert_eq!(fake.lock().unwrap().login_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_relogin_when_token_expired() {
+        let (url, fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "nacos", "nacos");
+        client.list_namespaces().await.unwrap();
+
+        fake.lock().unwrap().expire_tokens();
+        let resp = client.list_namespaces().await;
+        assert!(resp.is_ok(), "Request after token expiry failed: {:?}", resp.err());
+        assert_eq!(fake.lock().unwrap().login_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_relogin_when_forbidden() {
+        let (url, fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "nacos", "nacos");
+        fake.lock().unwrap().forbidden_paths.push("/nacos/v1/console/namespaces");
+
+        let resp = client.list_namespaces().await;
+        assert!(matches!(resp, Err(NacosError::Forbidden(_))), "{:?}", resp);
+        assert_eq!(fake.lock().unwrap().login_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_relogin_when_ttl_elapsed() {
+        let (url, fake) = FakeNacos::spawn().await;
+        // ttl 小于刷新余量，每次请求前都会重新登录
+        fake.lock().unwrap().token_ttl = 1;
+        let client = NacosClient::new(&url, "nacos", "nacos");
+
+        client.list_namespaces().await.unwrap();
+        client.list_namespaces().await.unwrap();
+        assert_eq!(fake.lock().unwrap().login_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_credentials() {
+        let (url, _fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "wrong_user", "wrong_pass");
+        let resp = client.list_namespaces().await;
+        assert!(matches!(resp, Err(err) if err.needs_login()));
+    }
+}
@@ -1,209 +1,573 @@
-use crate::resp::config_list_resp::ConfigListResp;
-
-pub async fn list(
-    url: &str,
-    access_token: &str,
-    ns_id: &str,
-) -> Result<ConfigListResp, String> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .get(&format!(
-            "{url}/nacos/v2/cs/history/configs?accessToken={access_token}&namespaceId={ns_id}"
-        ))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                let json_resp = response.json::<ConfigListResp>().await.map_err(|e| e.to_string())?;
-                Ok(json_resp)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))
-            }
+use reqwest::Method;
+
+use crate::api::client::{parse_json, NacosClient};
+use crate::api::error::NacosError;
+use crate::resp::config_history_resp::{ConfigHistoryPageResp, ConfigHistoryResp};
+use crate::resp::config_import_resp::ConfigImportResult;
+use crate::resp::config_listener_resp::ListenerStatusResp;
+use crate::resp::config_page_resp::{ConfigInfo, ConfigPageResp};
+use crate::resp::rest_result::RestResult;
+
+// 新建配置时附带的信息，对应 Nacos 的 appName/config_tags/desc
+#[derive(Clone, Default)]
+pub struct ConfigMeta {
+    pub app_name: String,
+    pub tags: String, // 逗号分隔
+    pub desc: String,
+}
+
+// 复制或导入时目标中已有同名配置的处理方式
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConflictPolicy {
+    Skip,
+    Overwrite,
+    Abort,
+}
+
+impl ConflictPolicy {
+    const ALL: [ConflictPolicy; 3] = [ConflictPolicy::Skip, ConflictPolicy::Overwrite, ConflictPolicy::Abort];
+
+    // 导入接口的 policy 参数
+    pub fn param(self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "SKIP",
+            ConflictPolicy::Overwrite => "OVERWRITE",
+            ConflictPolicy::Abort => "ABORT",
         }
-        Err(e) => Err(format!("Network error: {}", e)),
     }
-}
 
-//ns_id 命名空间id，不传表示查询public空间
-pub async fn get(
-    url: &str,
-    access_token: &str,
-    ns_id: Option<String>,
-    data_id: &str,
-    group: &str,
-) -> Result<String, String> {
-    let params = match ns_id {
-        Some(ns_id) => format!("tenant={ns_id}&dataId={data_id}&group={group}"),
-        None => format!("dataId={data_id}&group={group}"),
-    };
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .get(&format!(
-            "{url}/nacos/v1/cs/configs?accessToken={access_token}&{params}"
-        ))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                let json_resp = response.text().await.map_err(|e| e.to_string())?;
-                Ok(json_resp)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))
-            }
+    pub fn name(self) -> &'static str {
+        match self {
+            ConflictPolicy::Skip => "skip",
+            ConflictPolicy::Overwrite => "overwrite",
+            ConflictPolicy::Abort => "abort",
         }
-        Err(e) => Err(format!("Network error: {}", e)),
+    }
+
+    pub fn next(self) -> ConflictPolicy {
+        let i = ConflictPolicy::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        ConflictPolicy::ALL[(i + 1) % ConflictPolicy::ALL.len()]
     }
 }
 
-//发布配置/更新配置
-pub async fn publish(
-    url: &str,
-    access_token: &str,
-    ns_id: Option<String>,
-    data_id: &str,
-    group: &str,
-    content: &str,
-    type_: &str,
-) -> Result<bool, String> {
-    let params = match ns_id {
-        Some(ns_id) => format!("tenant={ns_id}&dataId={data_id}&group={group}&content={content}&type={type_}"),
-        None => format!("dataId={data_id}&group={group}&content={content}&type={type_}"),
-    };
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(&format!("{url}/nacos/v1/cs/configs?accessToken={access_token}&{params}"))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                let json_resp = response.json::<bool>().await.map_err(|e| e.to_string())?;
-                Ok(json_resp)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))
-            }
-        }
-        Err(e) => Err(format!("Network error: {}", e)),
+// 内容搜索一次取多少条配置
+const FETCH_PAGE_SIZE: u32 = 100;
+
+// 与 Nacos 一致的配置内容 md5，用于 cas 发布
+pub fn config_md5(content: &str) -> String {
+    format!("{:x}", md5::compute(content))
+}
+
+// 正在监听配置的客户端，md5 为客户端当前持有的内容的 md5
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigListener {
+    pub ip: String,
+    pub data_id: String,
+    pub group: String,
+    pub tenant: String,
+    pub md5: String,
+}
+
+// 解析 groupKey，返回 (dataId, group, tenant)
+pub fn parse_group_key(key: &str) -> Option<(String, String, String)> {
+    let unescape = |s: &str| s.replace("%2B", "+").replace("%25", "%");
+    let parts: Vec<&str> = key.split('+').collect();
+    match parts[..] {
+        [data_id, group] => Some((unescape(data_id), unescape(group), String::new())),
+        [data_id, group, tenant] => Some((unescape(data_id), unescape(group), unescape(tenant))),
+        _ => None,
     }
 }
 
-pub async fn delete(
-    url: &str,
-    access_token: &str,
-    ns_id: Option<String>,
-    data_id: &str,
-    group: &str,
-) -> Result<bool, String> {
-    let params = match ns_id {
-        Some(ns_id) => format!("tenant={ns_id}&dataId={data_id}&group={group}"),
-        None => format!("dataId={data_id}&group={group}"),
-    };
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .delete(&format!("{url}/nacos/v1/cs/configs?accessToken={access_token}&{params}"))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                let json_resp = response.json::<bool>().await.map_err(|e| e.to_string())?;
-                Ok(json_resp)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))
+impl NacosClient {
+    // 分页查询命名空间下的配置，data_id/group 为空表示不过滤，支持 * 通配符
+    pub async fn search_configs(
+        &self,
+        ns_id: &str,
+        data_id: &str,
+        group: &str,
+        page_no: u32,
+        page_size: u32,
+    ) -> Result<ConfigPageResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/cs/configs", |req| {
+                req.query(&[
+                    ("search", "blur"),
+                    ("tenant", ns_id),
+                    ("dataId", data_id),
+                    ("group", group),
+                ])
+                .query(&[("pageNo", page_no), ("pageSize", page_size)])
+            })
+            .await?;
+        parse_json::<ConfigPageResp>(&text)
+    }
+
+    // 分页取出命名空间下所有配置及内容，用于内容搜索
+    pub async fn fetch_all_configs(&self, ns_id: &str) -> Result<Vec<ConfigInfo>, NacosError> {
+        let mut configs: Vec<ConfigInfo> = vec![];
+        for page_no in 1.. {
+            let page = self.search_configs(ns_id, "", "", page_no, FETCH_PAGE_SIZE).await?;
+            let last = page.pageItems.is_empty();
+            configs.extend(page.pageItems);
+            if last || configs.len() >= page.totalCount.max(0) as usize {
+                break;
             }
         }
-        Err(e) => Err(format!("Network error: {}", e)),
+        // 分页结果里没有内容时逐个获取
+        let tenant = Some(ns_id).filter(|id| !id.is_empty());
+        for config in configs.iter_mut().filter(|c| c.content.is_none()) {
+            config.content = Some(self.get_config(tenant, &config.dataId, &config.group).await?);
+        }
+        Ok(configs)
+    }
+
+    //ns_id 命名空间id，不传表示查询public空间
+    pub async fn get_config(
+        &self,
+        ns_id: Option<&str>,
+        data_id: &str,
+        group: &str,
+    ) -> Result<String, NacosError> {
+        self.send(Method::GET, "/nacos/v1/cs/configs", |req| {
+            req.query(&[("tenant", ns_id.unwrap_or_default()), ("dataId", data_id), ("group", group)])
+        })
+        .await
+    }
+
+    //发布配置/更新配置
+    //cas_md5 为开始编辑时的内容 md5，服务端内容已变化时返回 NacosError::Conflict，不传则直接覆盖
+    pub async fn publish_config(
+        &self,
+        ns_id: Option<&str>,
+        data_id: &str,
+        group: &str,
+        content: &str,
+        type_: &str,
+        cas_md5: Option<&str>,
+    ) -> Result<bool, NacosError> {
+        let text = self
+            .send(Method::POST, "/nacos/v1/cs/configs", |req| {
+                let mut form = vec![
+                    ("tenant", ns_id.unwrap_or_default()),
+                    ("dataId", data_id),
+                    ("group", group),
+                    ("content", content),
+                    ("type", type_),
+                ];
+                // 不同版本的 Nacos 分别从请求头和表单读取 casMd5，两处都带上
+                match cas_md5 {
+                    Some(md5) => {
+                        form.push(("casMd5", md5));
+                        req.header("casMd5", md5).form(&form)
+                    }
+                    None => req.form(&form),
+                }
+            })
+            .await?;
+        parse_json::<bool>(&text)
+    }
+
+    //新建配置，同时写入 appName、标签和描述
+    //Nacos 没有单独的新建接口，同名配置会被直接覆盖，调用前需要先确认配置不存在
+    pub async fn create_config(
+        &self,
+        ns_id: Option<&str>,
+        data_id: &str,
+        group: &str,
+        content: &str,
+        type_: &str,
+        meta: &ConfigMeta,
+    ) -> Result<bool, NacosError> {
+        let text = self
+            .send(Method::POST, "/nacos/v1/cs/configs", |req| {
+                req.form(&[
+                    ("tenant", ns_id.unwrap_or_default()),
+                    ("dataId", data_id),
+                    ("group", group),
+                    ("content", content),
+                    ("type", type_),
+                    ("appName", &meta.app_name),
+                    ("config_tags", &meta.tags),
+                    ("desc", &meta.desc),
+                ])
+            })
+            .await?;
+        parse_json::<bool>(&text)
+    }
+
+    // 配置的历史版本，按时间倒序分页，列表中不含内容
+    pub async fn list_config_history(
+        &self,
+        ns_id: &str,
+        data_id: &str,
+        group: &str,
+        page_no: u32,
+        page_size: u32,
+    ) -> Result<ConfigHistoryPageResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v2/cs/history/list", |req| {
+                req.query(&[("namespaceId", ns_id), ("dataId", data_id), ("group", group)])
+                    .query(&[("pageNo", page_no), ("pageSize", page_size)])
+            })
+            .await?;
+        parse_json::<ConfigHistoryPageResp>(&text)
+    }
+
+    // 某个历史版本的详情，包含当时的内容
+    pub async fn get_config_history(
+        &self,
+        ns_id: &str,
+        data_id: &str,
+        group: &str,
+        nid: &str,
+    ) -> Result<ConfigHistoryResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v2/cs/history", |req| {
+                req.query(&[("namespaceId", ns_id), ("dataId", data_id), ("group", group), ("nid", nid)])
+            })
+            .await?;
+        parse_json::<ConfigHistoryResp>(&text)
+    }
+
+    pub async fn delete_config(
+        &self,
+        ns_id: Option<&str>,
+        data_id: &str,
+        group: &str,
+    ) -> Result<bool, NacosError> {
+        let text = self
+            .send(Method::DELETE, "/nacos/v1/cs/configs", |req| {
+                req.query(&[("tenant", ns_id.unwrap_or_default()), ("dataId", data_id), ("group", group)])
+            })
+            .await?;
+        parse_json::<bool>(&text)
+    }
+
+    // 按配置 id（分页查询结果里的 id）批量删除，一次请求删除多个配置
+    pub async fn delete_configs(&self, ids: &[String]) -> Result<bool, NacosError> {
+        let ids = ids.join(",");
+        let text = self
+            .send(Method::DELETE, "/nacos/v1/cs/configs", |req| {
+                req.query(&[("delType", "ids"), ("ids", ids.as_str())])
+            })
+            .await?;
+        Ok(parse_json::<RestResult<bool>>(&text)?.data)
+    }
+
+    // 正在监听某个配置的客户端 ip 和各自的 md5；客户端只在长轮询时上报，刚启动的客户端可能还没出现
+    pub async fn config_listeners(
+        &self,
+        ns_id: Option<&str>,
+        data_id: &str,
+        group: &str,
+    ) -> Result<Vec<ConfigListener>, NacosError> {
+        let tenant = ns_id.unwrap_or_default();
+        let text = self
+            .send(Method::GET, "/nacos/v1/cs/configs/listener", |req| {
+                req.query(&[("tenant", tenant), ("dataId", data_id), ("group", group), ("sampleTime", "1")])
+            })
+            .await?;
+        let resp = parse_json::<ListenerStatusResp>(&text)?;
+        let mut listeners: Vec<ConfigListener> = resp
+            .lisentersGroupkeyStatus
+            .into_iter()
+            .map(|(ip, md5)| ConfigListener {
+                ip,
+                data_id: data_id.to_string(),
+                group: group.to_string(),
+                tenant: tenant.to_string(),
+                md5,
+            })
+            .collect();
+        listeners.sort_by(|a, b| a.ip.cmp(&b.ip));
+        Ok(listeners)
+    }
+
+    // 某个客户端 ip 在所有命名空间下监听的配置
+    pub async fn listening_configs(&self, ip: &str) -> Result<Vec<ConfigListener>, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/cs/listener", |req| {
+                req.query(&[("ip", ip), ("all", "true"), ("sampleTime", "1")])
+            })
+            .await?;
+        let resp = parse_json::<ListenerStatusResp>(&text)?;
+        let mut listeners: Vec<ConfigListener> = resp
+            .lisentersGroupkeyStatus
+            .into_iter()
+            .filter_map(|(key, md5)| {
+                let (data_id, group, tenant) = parse_group_key(&key)?;
+                Some(ConfigListener { ip: ip.to_string(), data_id, group, tenant, md5 })
+            })
+            .collect();
+        listeners.sort_by(|a, b| (&a.tenant, &a.group, &a.data_id).cmp(&(&b.tenant, &b.group, &b.data_id)));
+        Ok(listeners)
+    }
+
+    // 导出命名空间下的配置为 Nacos 格式的 zip，ids 为空时导出全部
+    pub async fn export_configs(&self, ns_id: &str, ids: &[String]) -> Result<Vec<u8>, NacosError> {
+        let ids = ids.join(",");
+        self.send_bytes(Method::GET, "/nacos/v1/cs/configs", |req| {
+            req.query(&[
+                ("exportV2", "true"),
+                ("tenant", ns_id),
+                ("dataId", ""),
+                ("group", ""),
+                ("ids", ids.as_str()),
+            ])
+        })
+        .await
+    }
+
+    // 导入 Nacos 格式的 zip，policy 决定已存在的配置如何处理
+    pub async fn import_configs(
+        &self,
+        ns_id: &str,
+        zip: &[u8],
+        policy: ConflictPolicy,
+    ) -> Result<ConfigImportResult, NacosError> {
+        let text = self
+            .send(Method::POST, "/nacos/v1/cs/configs", |req| {
+                let file = reqwest::multipart::Part::bytes(zip.to_vec()).file_name("import.zip");
+                req.query(&[("import", "true"), ("namespace", ns_id), ("policy", policy.param())])
+                    .multipart(reqwest::multipart::Form::new().part("file", file))
+            })
+            .await?;
+        // 文件无法识别等错误时 http 状态仍是 200，错误码在响应体里
+        let result = parse_json::<RestResult<Option<ConfigImportResult>>>(&text)?;
+        match result.data {
+            Some(data) if result.code == 200 => Ok(data),
+            _ => Err(NacosError::Server {
+                status: 200,
+                code: Some(result.code),
+                message: result.message.unwrap_or(text),
+            }),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use crate::api;
-    use crate::config::AppConfig;
-    use crate::config::load_config;
-    use std::sync::OnceLock;
-
-    // 全局缓存 access_token
-    static ACCESS_TOKEN_CACHE: OnceLock<String> = OnceLock::new();
-    static APP_CONFIG_CACHE: OnceLock<AppConfig> = OnceLock::new();
-
-    // 辅助函数：加载并缓存 AppConfig
-    fn get_app_config() -> &'static AppConfig {
-        APP_CONFIG_CACHE.get_or_init(|| {
-            load_config()
-        })
+    use crate::api::client::NacosClient;
+    use crate::api::config::{config_md5, parse_group_key, ConfigMeta, ConflictPolicy};
+    use crate::api::config_zip::read_zip;
+    use crate::api::error::NacosError;
+    use crate::api::test_server::{group_key, FakeNacos};
+
+    const NS_ID: &str = "8fa56574-e685-495c-833e-42b525b35c1a";
+
+    async fn setup() -> (NacosClient, std::sync::Arc<std::sync::Mutex<FakeNacos>>) {
+        let (url, fake) = FakeNacos::spawn().await;
+        fake.lock().unwrap().put_config(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", "yaml", "server:\n  port: 8080\n");
+        (NacosClient::new(&url, "nacos", "nacos"), fake)
     }
 
-    // 辅助函数：登录并获取 access_token，支持缓存
-    async fn get_access_token() -> Result<String, String> {
-        if let Some(token) = ACCESS_TOKEN_CACHE.get() {
-            return Ok(token.clone());
+    #[tokio::test]
+    async fn test_search_paged() {
+        let (client, fake) = setup().await;
+        for i in 0..25 {
+            fake.lock().unwrap().put_config(NS_ID, &format!("app-{i:02}.yml"), "DEFAULT_GROUP", "yaml", "");
         }
 
-        let app_config = get_app_config();
+        let page = client.search_configs(NS_ID, "", "", 2, 10).await.unwrap();
+        assert_eq!(page.totalCount, 26);
+        assert_eq!(page.pageItems[0].dataId, "app-09.yml");
+        assert_eq!(page.pageItems.len(), 10);
 
-        let auth_login_resp = api::auth::login(
-            &app_config.nacos.url,
-            &app_config.nacos.username,
-            &app_config.nacos.password,
-        )
-        .await;
+        let page = client.search_configs(NS_ID, "app-1*", "", 1, 10).await.unwrap();
+        assert_eq!(page.totalCount, 10);
 
-        match auth_login_resp {
-            Ok(resp) => {
-                ACCESS_TOKEN_CACHE.set(resp.accessToken.clone()).unwrap();
-                Ok(resp.accessToken)
-            }
-            Err(e) => Err(format!("Login failed: {}", e)),
-        }
+        fake.lock().unwrap().put_config(NS_ID, "app-99.yml", "ORDER_GROUP", "yaml", "");
+        let page = client.search_configs(NS_ID, "app-*", "*ORDER*", 1, 10).await.unwrap();
+        assert_eq!(page.totalCount, 1);
+        assert_eq!(page.pageItems[0].dataId, "app-99.yml");
+
+        let page = client.search_configs("", "", "", 1, 10).await.unwrap();
+        assert_eq!(page.totalCount, 0);
     }
+
     #[tokio::test]
-    async fn test_list() {
-        let app_config = get_app_config();
-        let access_token = match get_access_token().await {
-            Ok(token) => token,
-            Err(e) => {
-                println!("{}", e);
-                return;
-            }
-        };
+    async fn test_fetch_all() {
+        let (client, fake) = setup().await;
+        for i in 0..120 {
+            fake.lock().unwrap().put_config(NS_ID, &format!("app-{i:03}.yml"), "DEFAULT_GROUP", "yaml", &format!("id: {i}"));
+        }
 
-        let resp = list(
-            &app_config.nacos.url,
-            &access_token,
-            &"".to_string()).await;
-        assert!(resp.is_ok(), "Failed to list configs: {:?}", resp.err());
+        let configs = client.fetch_all_configs(NS_ID).await.unwrap();
+        assert_eq!(configs.len(), 121);
+        let config = configs.iter().find(|c| c.dataId == "app-042.yml").unwrap();
+        assert_eq!(config.content.as_deref(), Some("id: 42"));
     }
 
     #[tokio::test]
     async fn test_get() {
-        let app_config = get_app_config();
-        let access_token = match get_access_token().await {
-            Ok(token) => token,
-            Err(e) => {
-                println!("{}", e);
-                return;
-            }
+        let (client, _fake) = setup().await;
+        let resp = client.get_config(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP").await;
+        assert!(resp.is_ok(), "Failed to get config: {:?}", resp.err());
+        assert_eq!(resp.unwrap(), "server:\n  port: 8080\n");
+    }
+
+    #[tokio::test]
+    async fn test_publish_and_delete() {
+        let (client, _fake) = setup().await;
+        // 内容包含 & = 等特殊字符，确认会被正确编码
+        let content = "a=1&b=2\nurl: http://host/?x=y";
+        let resp = client.publish_config(None, "new.properties", "DEFAULT_GROUP", content, "properties", None).await;
+        assert!(resp.unwrap(), "Config publish should return true");
+        assert_eq!(client.get_config(None, "new.properties", "DEFAULT_GROUP").await.unwrap(), content);
+
+        let resp = client.delete_config(None, "new.properties", "DEFAULT_GROUP").await;
+        assert!(resp.unwrap(), "Config deletion should return true");
+        let resp = client.get_config(None, "new.properties", "DEFAULT_GROUP").await;
+        assert!(matches!(resp, Err(NacosError::NotFound(_))), "Deleted config should be gone: {:?}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_create_with_meta() {
+        let (client, fake) = setup().await;
+        let meta = ConfigMeta {
+            app_name: "order".to_string(),
+            tags: "a,b".to_string(),
+            desc: "order service".to_string(),
         };
+        let resp = client.create_config(Some(NS_ID), "order.yaml", "ORDER", "a: 1\n", "yaml", &meta).await;
+        assert!(resp.unwrap(), "Config create should return true");
 
-        let resp = get(
-            &app_config.nacos.url,
-            &access_token,
-            Some("8fa56574-e685-495c-833e-42b525b35c1a".to_string()),
-            "lute-iot-admin.yml",
-            "DEFAULT_GROUP",
-        )
-        .await;
-        assert!(resp.is_ok(), "Failed to get config: {:?}", resp.err());
+        let fake = fake.lock().unwrap();
+        let config = fake.configs.iter().find(|c| c.data_id == "order.yaml").unwrap();
+        assert_eq!((config.group.as_str(), config.type_.as_str()), ("ORDER", "yaml"));
+        assert_eq!(config.app_name, "order");
+        assert_eq!(config.tags, "a,b");
+        assert_eq!(config.desc, "order service");
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let (client, fake) = setup().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_config(NS_ID, "log.properties", "LOG", "properties", "level=INFO\n");
+            let log = fake.configs.iter_mut().find(|c| c.data_id == "log.properties").unwrap();
+            log.app_name = "logger".to_string();
+            log.desc = "log levels".to_string();
+        }
+
+        let zip = client.export_configs(NS_ID, &[]).await.unwrap();
+        let exported = read_zip(&zip).unwrap();
+        assert_eq!(exported.len(), 2);
+        let log = exported.iter().find(|c| c.data_id == "log.properties").unwrap();
+        assert_eq!((log.group.as_str(), log.type_.as_str()), ("LOG", "properties"));
+        assert_eq!((log.app_name.as_str(), log.desc.as_str()), ("logger", "log levels"));
+
+        // 按 id 只导出选中的配置
+        let id = fake.lock().unwrap().configs[0].id.to_string();
+        let zip_one = client.export_configs(NS_ID, &[id]).await.unwrap();
+        assert_eq!(read_zip(&zip_one).unwrap().len(), 1);
+
+        // 导入到 public 后内容和元数据与源命名空间一致
+        let result = client.import_configs("", &zip, ConflictPolicy::Abort).await.unwrap();
+        assert_eq!(result.succCount, 2);
+        assert_eq!(client.get_config(None, "log.properties", "LOG").await.unwrap(), "level=INFO\n");
+        assert_eq!(read_zip(&client.export_configs("", &[]).await.unwrap()).unwrap(), exported);
+
+        // 已存在的配置按策略处理
+        client.publish_config(None, "log.properties", "LOG", "level=DEBUG\n", "properties", None).await.unwrap();
+        let result = client.import_configs("", &zip, ConflictPolicy::Skip).await.unwrap();
+        assert_eq!((result.succCount, result.skipCount), (0, 2));
+        let result = client.import_configs("", &zip, ConflictPolicy::Abort).await.unwrap();
+        assert_eq!((result.succCount, result.failData.unwrap().len()), (0, 2));
+        assert_eq!(client.get_config(None, "log.properties", "LOG").await.unwrap(), "level=DEBUG\n");
+        let result = client.import_configs("", &zip, ConflictPolicy::Overwrite).await.unwrap();
+        assert_eq!(result.succCount, 2);
+        assert_eq!(client.get_config(None, "log.properties", "LOG").await.unwrap(), "level=INFO\n");
+
+        let err = client.import_configs("", b"not a zip", ConflictPolicy::Skip).await;
+        assert!(matches!(err, Err(NacosError::Server { code: Some(100005), .. })), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_publish_cas() {
+        let (client, fake) = setup().await;
+        let base = client.get_config(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP").await.unwrap();
+        let base_md5 = config_md5(&base);
+
+        // 别人先改了配置，基于旧版本的发布应当冲突
+        fake.lock().unwrap().put_config(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", "yaml", "server:\n  port: 9090\n");
+        let resp = client
+            .publish_config(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP", "mine", "yaml", Some(&base_md5))
+            .await;
+        assert!(matches!(resp, Err(NacosError::Conflict(_))), "Stale casMd5 should conflict: {:?}", resp);
+
+        let server = client.get_config(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP").await.unwrap();
+        assert_eq!(server, "server:\n  port: 9090\n");
+        let resp = client
+            .publish_config(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP", "mine", "yaml", Some(&config_md5(&server)))
+            .await;
+        assert!(resp.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_history() {
+        let (client, fake) = setup().await;
+        fake.lock().unwrap().put_config(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", "yaml", "server:\n  port: 9090\n");
+        client.delete_config(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP").await.unwrap();
+
+        // 最新的在前：删除、更新、创建
+        let page = client.list_config_history(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", 1, 10).await.unwrap();
+        assert_eq!(page.data.totalCount, 3);
+        let ops: Vec<_> = page.data.pageItems.iter().map(|h| h.opType.trim()).collect();
+        assert_eq!(ops, vec!["D", "U", "I"]);
+        assert!(page.data.pageItems.iter().all(|h| h.content.is_none()));
+
+        // 更新记录保存的是修改前的内容
+        let nid = &page.data.pageItems[1].id;
+        let detail = client.get_config_history(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", nid).await.unwrap();
+        assert_eq!(detail.data.content.as_deref(), Some("server:\n  port: 8080\n"));
+
+        let resp = client.get_config_history(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", "999").await;
+        assert!(matches!(resp, Err(NacosError::NotFound(_))), "Unknown nid should be not found: {:?}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_delete_by_ids() {
+        let (client, fake) = setup().await;
+        for i in 0..3 {
+            fake.lock().unwrap().put_config(NS_ID, &format!("app-{i}.yml"), "DEFAULT_GROUP", "yaml", "");
+        }
+        let page = client.search_configs(NS_ID, "app-*", "", 1, 10).await.unwrap();
+        let ids: Vec<String> = page.pageItems.iter().take(2).map(|c| c.id.clone()).collect();
+        assert!(client.delete_configs(&ids).await.unwrap());
+
+        let page = client.search_configs(NS_ID, "app-*", "", 1, 10).await.unwrap();
+        let left: Vec<_> = page.pageItems.iter().map(|c| c.dataId.as_str()).collect();
+        assert_eq!(left, vec!["app-2.yml"]);
+    }
+
+    #[test]
+    fn test_group_key() {
+        let key = group_key("a+b.yml", "DEFAULT_GROUP", "dev");
+        assert_eq!(key, "a%2Bb.yml+DEFAULT_GROUP+dev");
+        assert_eq!(parse_group_key(&key), Some(("a+b.yml".to_string(), "DEFAULT_GROUP".to_string(), "dev".to_string())));
+        assert_eq!(parse_group_key("app.yml+G"), Some(("app.yml".to_string(), "G".to_string(), String::new())));
+        assert_eq!(parse_group_key("app.yml"), None);
+    }
+
+    #[tokio::test]
+    async fn test_config_listeners() {
+        let (client, fake) = setup().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_listener(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", "10.0.0.2", "old");
+            fake.put_listener(NS_ID, "lute-iot-admin.yml", "DEFAULT_GROUP", "10.0.0.1", "new");
+            fake.put_listener("", "common.yml", "DEFAULT_GROUP", "10.0.0.1", "abc");
+        }
+        let listeners = client.config_listeners(Some(NS_ID), "lute-iot-admin.yml", "DEFAULT_GROUP").await.unwrap();
+        let ips: Vec<_> = listeners.iter().map(|l| (l.ip.as_str(), l.md5.as_str())).collect();
+        assert_eq!(ips, vec![("10.0.0.1", "new"), ("10.0.0.2", "old")]);
+
+        let configs = client.listening_configs("10.0.0.1").await.unwrap();
+        let keys: Vec<_> = configs.iter().map(|c| (c.tenant.as_str(), c.data_id.as_str())).collect();
+        assert_eq!(keys, vec![("", "common.yml"), (NS_ID, "lute-iot-admin.yml")]);
+        assert!(client.listening_configs("10.9.9.9").await.unwrap().is_empty());
     }
 }
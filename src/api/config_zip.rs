@@ -0,0 +1,160 @@
+// Nacos 控制台导出/导入配置使用的 zip 格式：
+// 每个配置是一个 {group}/{dataId} 文件，类型、appName、描述写在根目录的 .metadata.yml 里；
+// 旧版本导出的是 .meta，只记录 appName，格式为 {group}.{dataId 中的 . 换成 ~}.app=appName
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use serde::Deserialize;
+use zip::ZipArchive;
+
+const METADATA_FILE: &str = ".metadata.yml";
+const LEGACY_META_FILE: &str = ".meta";
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ZipConfig {
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub app_name: String,
+    pub desc: String,
+    pub content: String,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    metadata: Vec<MetadataItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataItem {
+    group: String,
+    data_id: String,
+    #[serde(default)]
+    desc: Option<String>,
+    #[serde(rename = "type", default)]
+    type_: Option<String>,
+    #[serde(default)]
+    app_name: Option<String>,
+}
+
+// 按 zip 中的顺序返回配置；不是 {group}/{dataId} 形式的文件忽略
+pub fn read_zip(bytes: &[u8]) -> Result<Vec<ZipConfig>, String> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("not a zip archive: {e}"))?;
+    let mut configs = vec![];
+    let mut metadata: Option<Metadata> = None;
+    let mut legacy_meta = String::new();
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i).map_err(|e| e.to_string())?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|e| format!("{name}: {e}"))?;
+        match name.as_str() {
+            METADATA_FILE => {
+                metadata = Some(serde_yaml::from_str(&content).map_err(|e| format!("{METADATA_FILE}: {e}"))?);
+            }
+            LEGACY_META_FILE => legacy_meta = content,
+            _ => {
+                if let Some((group, data_id)) = name.split_once('/')
+                    && !group.is_empty()
+                    && !data_id.is_empty()
+                    && !data_id.contains('/')
+                {
+                    configs.push(ZipConfig {
+                        data_id: data_id.to_string(),
+                        group: group.to_string(),
+                        content,
+                        ..ZipConfig::default()
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(metadata) = metadata {
+        for item in metadata.metadata {
+            if let Some(config) = configs.iter_mut().find(|c| c.group == item.group && c.data_id == item.data_id) {
+                config.type_ = item.type_.unwrap_or_default();
+                config.app_name = item.app_name.unwrap_or_default();
+                config.desc = item.desc.unwrap_or_default();
+            }
+        }
+    } else {
+        // 与 Nacos 导入时一样，用配置的 group 和 dataId 拼出 key 去查，
+        // 不去拆 key，group 里的 . 和 dataId 里原有的 ~ 都不会拆错
+        let app_names: HashMap<&str, &str> = legacy_meta
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, app_name)| (key.trim(), app_name.trim()))
+            .collect();
+        for config in configs.iter_mut() {
+            let key = format!("{}.{}.app", config.group, config.data_id.replace('.', "~"));
+            if let Some(app_name) = app_names.get(key.as_str()) {
+                config.app_name = app_name.to_string();
+            }
+        }
+    }
+    Ok(configs)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::*;
+    use crate::api::test_server::write_zip;
+
+    #[test]
+    fn test_zip_round_trip() {
+        let configs = vec![
+            ZipConfig {
+                data_id: "app.yaml".to_string(),
+                group: "DEFAULT_GROUP".to_string(),
+                type_: "yaml".to_string(),
+                app_name: "app".to_string(),
+                desc: "main config".to_string(),
+                content: "server:\n  port: 8080\n".to_string(),
+            },
+            ZipConfig {
+                data_id: "log.properties".to_string(),
+                group: "LOG".to_string(),
+                type_: "properties".to_string(),
+                content: "level=INFO".to_string(),
+                ..ZipConfig::default()
+            },
+        ];
+        let bytes = write_zip(&configs).unwrap();
+        assert_eq!(read_zip(&bytes).unwrap(), configs);
+    }
+
+    #[test]
+    fn test_read_legacy_meta() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        zip.start_file("DEFAULT_GROUP/app.yaml", options).unwrap();
+        zip.write_all(b"a: 1").unwrap();
+        // group 里有 .、dataId 里本来就有 ~ 的配置
+        zip.start_file("com.example/v1~app.yaml", options).unwrap();
+        zip.write_all(b"b: 1").unwrap();
+        zip.start_file(".meta", options).unwrap();
+        zip.write_all(b"DEFAULT_GROUP.app~yaml.app=order\ncom.example.v1~app~yaml.app=billing\n").unwrap();
+        let bytes = zip.finish().unwrap().into_inner();
+
+        let configs = read_zip(&bytes).unwrap();
+        assert_eq!(configs.len(), 2);
+        assert_eq!((configs[0].data_id.as_str(), configs[0].app_name.as_str()), ("app.yaml", "order"));
+        assert_eq!(
+            (configs[1].group.as_str(), configs[1].data_id.as_str(), configs[1].app_name.as_str()),
+            ("com.example", "v1~app.yaml", "billing")
+        );
+
+        assert!(read_zip(b"not a zip").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+// api 层统一的错误类型，界面据此区分认证失败、资源不存在、超时等情况
+#[derive(Debug)]
+pub enum NacosError {
+    // 连接失败、连接被重置等
+    Network(String),
+    Timeout,
+    // 账号或密码错误，登录失败
+    Unauthorized(String),
+    // 已登录但没有权限
+    Forbidden(String),
+    NotFound(String),
+    // casMd5 与服务端当前版本不一致，配置已被别人修改
+    Conflict(String),
+    // 其他非 2xx 响应，code/message 取自 Nacos 的 json 响应体
+    Server {
+        status: u16,
+        code: Option<i32>,
+        message: String,
+    },
+    // 响应体无法解析，body 为原始响应
+    Decode { error: String, body: String },
+}
+
+// Nacos v2 接口出错时的响应体
+#[derive(Deserialize)]
+struct ErrorBody {
+    code: Option<i32>,
+    message: Option<String>,
+}
+
+impl NacosError {
+    pub fn from_status(status: StatusCode, body: &str) -> NacosError {
+        let (code, message) = match serde_json::from_str::<ErrorBody>(body) {
+            Ok(err) => (err.code, err.message.unwrap_or_else(|| body.to_string())),
+            Err(_) => (None, body.trim().to_string()),
+        };
+        match status {
+            StatusCode::UNAUTHORIZED => NacosError::Unauthorized(message),
+            StatusCode::FORBIDDEN => NacosError::Forbidden(message),
+            StatusCode::NOT_FOUND => NacosError::NotFound(message),
+            // Nacos cas 发布失败时返回 500 和 "Cas publish fail, server md5 may have changed"
+            StatusCode::CONFLICT => NacosError::Conflict(message),
+            _ if message.to_ascii_lowercase().contains("cas publish fail") => NacosError::Conflict(message),
+            _ => NacosError::Server {
+                status: status.as_u16(),
+                code,
+                message,
+            },
+        }
+    }
+
+    pub fn decode(error: impl fmt::Display, body: &str) -> NacosError {
+        NacosError::Decode {
+            error: error.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    // 只有认证失败时重新登录才有意义
+    pub fn needs_login(&self) -> bool {
+        matches!(self, NacosError::Unauthorized(_))
+    }
+}
+
+impl From<reqwest::Error> for NacosError {
+    fn from(err: reqwest::Error) -> NacosError {
+        if err.is_timeout() {
+            NacosError::Timeout
+        } else {
+            NacosError::Network(err.to_string())
+        }
+    }
+}
+
+impl fmt::Display for NacosError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NacosError::Network(msg) => write!(f, "Network error: {}", msg),
+            NacosError::Timeout => write!(f, "Request timed out"),
+            NacosError::Unauthorized(msg) => write!(f, "Login failed: {}", msg),
+            NacosError::Forbidden(msg) => write!(f, "Permission denied: {}", msg),
+            NacosError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            NacosError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            NacosError::Server {
+                status,
+                code: Some(code),
+                message,
+            } => write!(f, "Server error {} (code {}): {}", status, code, message),
+            NacosError::Server { status, message, .. } => {
+                write!(f, "Server error {}: {}", status, message)
+            }
+            NacosError::Decode { error, body } => {
+                write!(f, "Unexpected response: {} (body: {})", error, body)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NacosError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status() {
+        let err = NacosError::from_status(StatusCode::NOT_FOUND, "config data not exist");
+        assert!(matches!(err, NacosError::NotFound(msg) if msg == "config data not exist"));
+
+        let err = NacosError::from_status(
+            StatusCode::BAD_REQUEST,
+            r#"{"code":20004,"message":"namespace not exist","data":null}"#,
+        );
+        assert!(matches!(
+            err,
+            NacosError::Server { status: 400, code: Some(20004), message } if message == "namespace not exist"
+        ));
+
+        let err = NacosError::from_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "caused: Cas publish fail, server md5 may have changed.;",
+        );
+        assert!(matches!(err, NacosError::Conflict(_)));
+
+        let err = NacosError::from_status(StatusCode::FORBIDDEN, "authorization failed!");
+        assert!(matches!(err, NacosError::Forbidden(_)));
+        assert!(!err.needs_login());
+    }
+}
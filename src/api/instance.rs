@@ -0,0 +1,215 @@
+use reqwest::Method;
+
+use crate::api::client::{parse_json, NacosClient};
+use crate::api::error::NacosError;
+use crate::resp::instance_list_resp::{Instance, InstanceListResp};
+
+// 逐页获取一个集群所有实例时的每页条数
+const FETCH_PAGE_SIZE: u32 = 100;
+
+impl NacosClient {
+    // 分页查询服务在某个集群下的实例，包含健康检查不通过和已下线的实例
+    pub async fn list_instances(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+        cluster: &str,
+        page_no: u32,
+        page_size: u32,
+    ) -> Result<InstanceListResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/ns/catalog/instances", |req| {
+                req.query(&[
+                    ("namespaceId", ns_id),
+                    ("serviceName", service_name),
+                    ("groupName", group_name),
+                    ("clusterName", cluster),
+                ])
+                .query(&[("pageNo", page_no), ("pageSize", page_size)])
+            })
+            .await?;
+        parse_json::<InstanceListResp>(&text)
+    }
+
+    // 服务所有集群的全部实例，集群列表取自服务定义
+    pub async fn fetch_all_instances(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+    ) -> Result<Vec<Instance>, NacosError> {
+        let service = self.get_service(ns_id, service_name, group_name).await?;
+        let mut instances: Vec<Instance> = vec![];
+        for cluster in &service.clusters {
+            let mut fetched = 0;
+            for page_no in 1.. {
+                let page = self
+                    .list_instances(ns_id, service_name, group_name, &cluster.name, page_no, FETCH_PAGE_SIZE)
+                    .await?;
+                fetched += page.list.len();
+                let last = page.list.is_empty();
+                instances.extend(page.list);
+                if last || fetched >= page.count.max(0) as usize {
+                    break;
+                }
+            }
+        }
+        Ok(instances)
+    }
+
+    // 手动注册实例；服务不存在时 Nacos 会自动创建。临时实例没有心跳会在几十秒后被剔除
+    pub async fn register_instance(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+        instance: &Instance,
+    ) -> Result<(), NacosError> {
+        let form = instance_form(ns_id, service_name, group_name, instance);
+        self.send(Method::POST, "/nacos/v1/ns/instance", |req| req.form(&form)).await?;
+        Ok(())
+    }
+
+    // 更新实例的权重、上下线状态和元数据；接口会整体覆盖，所以每次都带上全部字段
+    pub async fn update_instance(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+        instance: &Instance,
+    ) -> Result<(), NacosError> {
+        let form = instance_form(ns_id, service_name, group_name, instance);
+        self.send(Method::PUT, "/nacos/v1/ns/instance", |req| req.form(&form)).await?;
+        Ok(())
+    }
+
+    // 注销实例，用于清理进程已经不在的残留实例
+    pub async fn deregister_instance(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+        instance: &Instance,
+    ) -> Result<(), NacosError> {
+        let port = instance.port.to_string();
+        self.send(Method::DELETE, "/nacos/v1/ns/instance", |req| {
+            req.query(&[
+                ("namespaceId", ns_id),
+                ("serviceName", service_name),
+                ("groupName", group_name),
+                ("clusterName", instance.clusterName.as_str()),
+                ("ip", instance.ip.as_str()),
+                ("port", port.as_str()),
+                ("ephemeral", if instance.ephemeral { "true" } else { "false" }),
+            ])
+        })
+        .await?;
+        Ok(())
+    }
+}
+
+fn instance_form(ns_id: &str, service_name: &str, group_name: &str, instance: &Instance) -> Vec<(&'static str, String)> {
+    let bool_str = |b: bool| if b { "true" } else { "false" }.to_string();
+    vec![
+        ("namespaceId", ns_id.to_string()),
+        ("serviceName", service_name.to_string()),
+        ("groupName", group_name.to_string()),
+        ("clusterName", instance.clusterName.clone()),
+        ("ip", instance.ip.clone()),
+        ("port", instance.port.to_string()),
+        ("weight", instance.weight.to_string()),
+        ("enabled", bool_str(instance.enabled)),
+        ("healthy", bool_str(instance.healthy)),
+        ("ephemeral", bool_str(instance.ephemeral)),
+        ("metadata", serde_json::to_string(&instance.metadata).unwrap_or_else(|_| "{}".to_string())),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::api::client::NacosClient;
+    use crate::api::test_server::FakeNacos;
+    use crate::resp::instance_list_resp::Instance;
+
+    const NS_ID: &str = "dev";
+
+    #[tokio::test]
+    async fn test_fetch_all_instances() {
+        let (url, fake) = FakeNacos::spawn().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_service(NS_ID, "order-service", "DEFAULT_GROUP", 0.5);
+            fake.put_instance(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.0.1", 8080, "DEFAULT", true);
+            fake.put_instance(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.0.2", 8080, "DEFAULT", false);
+            fake.put_instance(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.0.3", 8080, "BACKUP", true);
+        }
+        let client = NacosClient::new(&url, "nacos", "nacos");
+
+        let page = client
+            .list_instances(NS_ID, "order-service", "DEFAULT_GROUP", "DEFAULT", 1, 1)
+            .await
+            .unwrap();
+        assert_eq!((page.count, page.list.len()), (2, 1));
+
+        let instances = client.fetch_all_instances(NS_ID, "order-service", "DEFAULT_GROUP").await.unwrap();
+        let ips: Vec<&str> = instances.iter().map(|i| i.ip.as_str()).collect();
+        assert_eq!(ips, vec!["10.0.0.3", "10.0.0.1", "10.0.0.2"]);
+        assert!(!instances[2].healthy);
+        assert_eq!(instances[0].clusterName, "BACKUP");
+    }
+
+    #[tokio::test]
+    async fn test_update_instance() {
+        let (url, fake) = FakeNacos::spawn().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_service(NS_ID, "order-service", "DEFAULT_GROUP", 0.0);
+            fake.put_instance(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.0.1", 8080, "DEFAULT", true);
+        }
+        let client = NacosClient::new(&url, "nacos", "nacos");
+
+        let mut instance = client.fetch_all_instances(NS_ID, "order-service", "DEFAULT_GROUP").await.unwrap().remove(0);
+        instance.enabled = false;
+        instance.weight = 0.5;
+        instance.metadata.insert("version".to_string(), "2.0".to_string());
+        client.update_instance(NS_ID, "order-service", "DEFAULT_GROUP", &instance).await.unwrap();
+
+        let updated = client.fetch_all_instances(NS_ID, "order-service", "DEFAULT_GROUP").await.unwrap().remove(0);
+        assert!(!updated.enabled);
+        assert_eq!(updated.weight, 0.5);
+        assert_eq!(updated.metadata.get("version").map(String::as_str), Some("2.0"));
+
+        instance.port = 9090;
+        let resp = client.update_instance(NS_ID, "order-service", "DEFAULT_GROUP", &instance).await;
+        assert!(resp.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_register_deregister() {
+        let (url, _fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "nacos", "nacos");
+        let instance = Instance {
+            ip: "10.0.0.5".to_string(),
+            port: 9000,
+            weight: 2.0,
+            healthy: true,
+            enabled: true,
+            ephemeral: false,
+            clusterName: "DEFAULT".to_string(),
+            metadata: HashMap::from([("smoke".to_string(), "true".to_string())]),
+        };
+        // 服务不存在时随注册一起创建
+        client.register_instance(NS_ID, "smoke-service", "TEST_GROUP", &instance).await.unwrap();
+        let instances = client.fetch_all_instances(NS_ID, "smoke-service", "TEST_GROUP").await.unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!((instances[0].weight, instances[0].ephemeral), (2.0, false));
+        assert_eq!(instances[0].metadata.get("smoke").map(String::as_str), Some("true"));
+
+        client.deregister_instance(NS_ID, "smoke-service", "TEST_GROUP", &instance).await.unwrap();
+        let instances = client.fetch_all_instances(NS_ID, "smoke-service", "TEST_GROUP").await.unwrap();
+        assert!(instances.is_empty());
+    }
+}
@@ -1,177 +1,95 @@
+use reqwest::Method;
 
+use crate::api::client::{parse_json, NacosClient};
+use crate::api::error::NacosError;
 use crate::resp::namespace_list_resp::NamespaceListResp;
 
-pub async fn list(url: &str) -> Result<NamespaceListResp, String> {
-
-    let client = reqwest::Client::new();
-    let resp = client
-        .get(&format!("{url}/nacos/v1/console/namespaces"))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                let json_resp = response.json::<NamespaceListResp>().await.map_err(|e| e.to_string())?;
-                Ok(json_resp)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))
-            }
+impl NacosClient {
+    pub async fn list_namespaces(&self) -> Result<NamespaceListResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/console/namespaces", |req| req)
+            .await?;
+        let resp = parse_json::<NamespaceListResp>(&text)?;
+        // 控制台接口出错时 http 状态仍可能是 200，以响应体里的 code 为准
+        if resp.code != 200 {
+            return Err(NacosError::Server {
+                status: 200,
+                code: Some(resp.code),
+                message: resp.message.unwrap_or_default(),
+            });
         }
-        Err(e) => Err(format!("Network error: {}", e)),
-    } 
-}
+        Ok(resp)
+    }
 
-// 创建成功返回true， 创建失败返回false
-pub async fn create(url: &str, access_token: &str, ns_id: &str, ns_name: &str, ns_desc: &str) -> Result<bool, String> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .post(&format!("{url}/nacos/v1/console/namespaces?accessToken={access_token}"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(format!("customNamespaceId{}=&namespaceName={}&namespaceDesc={}",ns_id, ns_name, ns_desc))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                Ok(true)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))   
-            }
-        }
-        Err(e) => Err(format!("Network error: {}", e)),
+    // 创建成功返回true， 创建失败返回false
+    pub async fn create_namespace(&self, ns_id: &str, ns_name: &str, ns_desc: &str) -> Result<bool, NacosError> {
+        let text = self
+            .send(Method::POST, "/nacos/v1/console/namespaces", |req| {
+                req.form(&[
+                    ("customNamespaceId", ns_id),
+                    ("namespaceName", ns_name),
+                    ("namespaceDesc", ns_desc),
+                ])
+            })
+            .await?;
+        parse_json::<bool>(&text)
     }
-}
 
-pub async fn update(url: &str, access_token: &str, ns_id: &str, ns_name: &str, ns_desc: &str) -> Result<bool, String> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .put(&format!("{url}/nacos/v1/console/namespaces?accessToken={access_token}"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(format!("namespace={ns_id}&namespaceShowName={ns_name}&namespaceDesc={ns_desc}"))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                Ok(true)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))   
-            }
-        }
-        Err(e) => Err(format!("Network error: {}", e)),
+    pub async fn update_namespace(&self, ns_id: &str, ns_name: &str, ns_desc: &str) -> Result<bool, NacosError> {
+        let text = self
+            .send(Method::PUT, "/nacos/v1/console/namespaces", |req| {
+                req.form(&[
+                    ("namespace", ns_id),
+                    ("namespaceShowName", ns_name),
+                    ("namespaceDesc", ns_desc),
+                ])
+            })
+            .await?;
+        parse_json::<bool>(&text)
     }
-}
 
-pub async fn delete(url: &str, access_token: &str, ns_id: &str) -> Result<bool, String> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .delete(&format!("{url}/nacos/v1/console/namespaces?accessToken={access_token}"))
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .body(format!("namespaceId={ns_id}"))
-        .send()
-        .await;
-    match resp {
-        Ok(response) => {
-            if response.status().is_success() {
-                Ok(true)
-            } else {
-                let text_resp = response.text().await.map_err(|e| e.to_string())?;
-                Err(format!("Request failed: {}", text_resp))   
-            }
-        }
-        Err(e) => Err(format!("Network error: {}", e)),
+    pub async fn delete_namespace(&self, ns_id: &str) -> Result<bool, NacosError> {
+        let text = self
+            .send(Method::DELETE, "/nacos/v1/console/namespaces", |req| {
+                req.form(&[("namespaceId", ns_id)])
+            })
+            .await?;
+        parse_json::<bool>(&text)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use crate::api;
-    use crate::config::AppConfig;
-    use crate::config::load_config;
-    use std::sync::OnceLock;
-
-    // 全局缓存 access_token
-    static ACCESS_TOKEN_CACHE: OnceLock<String> = OnceLock::new();
-    static APP_CONFIG_CACHE: OnceLock<AppConfig> = OnceLock::new();
-
-    // 辅助函数：加载并缓存 AppConfig
-    fn get_app_config() -> &'static AppConfig {
-        APP_CONFIG_CACHE.get_or_init(|| {
-            load_config()
-        })
-    }
-
-    // 辅助函数：登录并获取 access_token，支持缓存
-    async fn get_access_token() -> Result<String, String> {
-        if let Some(token) = ACCESS_TOKEN_CACHE.get() {
-            return Ok(token.clone());
-        }
-
-        let app_config = get_app_config();
-
-        let auth_login_resp = api::auth::login(
-            &app_config.nacos.url,
-            &app_config.nacos.username,
-            &app_config.nacos.password,
-        )
-        .await;
-
-        match auth_login_resp {
-            Ok(resp) => {
-                ACCESS_TOKEN_CACHE.set(resp.accessToken.clone()).unwrap();
-                Ok(resp.accessToken)
-            }
-            Err(e) => Err(format!("Login failed: {}", e)),
-        }
-    }
+    use crate::api::client::NacosClient;
+    use crate::api::test_server::FakeNacos;
 
     #[tokio::test]
     async fn test_list() {
-        let app_config = get_app_config();
-        let resp = list(&app_config.nacos.url).await;
+        let (url, _fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "nacos", "nacos");
+        let resp = client.list_namespaces().await;
         assert!(resp.is_ok(), "Failed to list namespaces: {:?}", resp.err());
+        assert_eq!(resp.unwrap().data[0].namespaceShowName, "public");
     }
 
     #[tokio::test]
-    async fn test_create() {
-        let app_config = get_app_config();
-        let access_token = get_access_token().await.unwrap();
+    async fn test_create_update_delete() {
+        let (url, fake) = FakeNacos::spawn().await;
+        let client = NacosClient::new(&url, "nacos", "nacos");
 
         let ns_id = "123";
-        let ns_name = "Test-Namespace";
-        let ns_desc = "This is a test namespace";
-
-        let resp = create(&app_config.nacos.url, &access_token, ns_id, ns_name, ns_desc).await;
+        let resp = client.create_namespace(ns_id, "Test-Namespace", "This is a test namespace").await;
         assert!(resp.is_ok(), "Failed to create namespace: {:?}", resp.err());
-        assert_eq!(resp.unwrap(), true, "Namespace creation should return true");
-    }
-
-    #[tokio::test]
-    async fn test_update() {
-        let app_config = get_app_config();
-        let access_token = get_access_token().await.unwrap();
-
-        let ns_id = "123";
-        let ns_name = "Updated-Namespace";
-        let ns_desc = "This is an updated test namespace";
+        assert!(resp.unwrap(), "Namespace creation should return true");
 
-        let resp = update(&app_config.nacos.url, &access_token, ns_id, ns_name, ns_desc).await;
+        let resp = client.update_namespace(ns_id, "Updated-Namespace", "This is an updated test namespace").await;
         assert!(resp.is_ok(), "Failed to update namespace: {:?}", resp.err());
-        assert_eq!(resp.unwrap(), true, "Namespace update should return true");
-    }
+        assert!(resp.unwrap(), "Namespace update should return true");
+        assert_eq!(fake.lock().unwrap().namespaces[1].name, "Updated-Namespace");
 
-    #[tokio::test]
-    async fn test_delete() {
-        let app_config = get_app_config();
-        let access_token = get_access_token().await.unwrap();
-
-        let ns_id = "123"; // 确保这个ID是存在的
-        let resp = delete(&app_config.nacos.url, &access_token, ns_id).await;
+        let resp = client.delete_namespace(ns_id).await;
         assert!(resp.is_ok(), "Failed to delete namespace: {:?}", resp.err());
-        assert_eq!(resp.unwrap(), true, "Namespace deletion should return true");
+        assert!(resp.unwrap(), "Namespace deletion should return true");
+        assert_eq!(fake.lock().unwrap().namespaces.len(), 1);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,227 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
+use serde_json::json;
+
+use crate::api::client::{parse_json, NacosClient};
+use crate::api::error::NacosError;
+use crate::resp::service_detail_resp::ServiceDetail;
+use crate::resp::service_list_resp::ServiceListResp;
+use crate::resp::subscriber_list_resp::SubscriberListResp;
+
+// 新建或修改服务时提交的服务定义
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ServiceSpec {
+    pub name: String,
+    pub group: String,
+    pub protect_threshold: f32,
+    pub metadata: HashMap<String, String>,
+    pub selector: Option<String>, // label 选择器的表达式，None 表示不使用选择器
+}
+
+impl ServiceSpec {
+    fn form(&self, ns_id: &str) -> Vec<(&'static str, String)> {
+        let selector = match &self.selector {
+            Some(expression) => json!({"type": "label", "expression": expression}),
+            None => json!({"type": "none"}),
+        };
+        vec![
+            ("namespaceId", ns_id.to_string()),
+            ("serviceName", self.name.clone()),
+            ("groupName", self.group.clone()),
+            ("protectThreshold", self.protect_threshold.to_string()),
+            ("metadata", serde_json::to_string(&self.metadata).unwrap_or_else(|_| "{}".to_string())),
+            ("selector", selector.to_string()),
+        ]
+    }
+}
+
+impl From<ServiceDetail> for ServiceSpec {
+    fn from(detail: ServiceDetail) -> ServiceSpec {
+        ServiceSpec {
+            name: detail.name,
+            group: detail.groupName,
+            protect_threshold: detail.protectThreshold,
+            metadata: detail.metadata,
+            selector: detail
+                .selector
+                .filter(|s| s.type_ == "label")
+                .map(|s| s.expression.unwrap_or_default()),
+        }
+    }
+}
+
+impl NacosClient {
+    // 分页查询命名空间下的服务，带实例数和健康实例数；service_name/group_name 为空表示不过滤
+    pub async fn list_services(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+        page_no: u32,
+        page_size: u32,
+    ) -> Result<ServiceListResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/ns/catalog/services", |req| {
+                req.query(&[
+                    ("hasIpCount", "true"),
+                    ("withInstances", "false"),
+                    ("namespaceId", ns_id),
+                    ("serviceNameParam", service_name),
+                    ("groupNameParam", group_name),
+                ])
+                .query(&[("pageNo", page_no), ("pageSize", page_size)])
+            })
+            .await?;
+        parse_json::<ServiceListResp>(&text)
+    }
+
+    // 服务定义：保护阈值、元数据、选择器和集群
+    pub async fn get_service(&self, ns_id: &str, service_name: &str, group_name: &str) -> Result<ServiceDetail, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/ns/service", |req| {
+                req.query(&[("namespaceId", ns_id), ("serviceName", service_name), ("groupName", group_name)])
+            })
+            .await?;
+        parse_json::<ServiceDetail>(&text)
+    }
+
+    pub async fn create_service(&self, ns_id: &str, spec: &ServiceSpec) -> Result<(), NacosError> {
+        let form = spec.form(ns_id);
+        self.send(Method::POST, "/nacos/v1/ns/service", |req| req.form(&form)).await?;
+        Ok(())
+    }
+
+    pub async fn update_service(&self, ns_id: &str, spec: &ServiceSpec) -> Result<(), NacosError> {
+        let form = spec.form(ns_id);
+        self.send(Method::PUT, "/nacos/v1/ns/service", |req| req.form(&form)).await?;
+        Ok(())
+    }
+
+    // 服务下还有实例时 Nacos 会拒绝删除
+    pub async fn delete_service(&self, ns_id: &str, service_name: &str, group_name: &str) -> Result<(), NacosError> {
+        self.send(Method::DELETE, "/nacos/v1/ns/service", |req| {
+            req.query(&[("namespaceId", ns_id), ("serviceName", service_name), ("groupName", group_name)])
+        })
+        .await?;
+        Ok(())
+    }
+
+    // 分页查询订阅了服务的客户端，默认汇总集群所有节点上的订阅者
+    pub async fn list_subscribers(
+        &self,
+        ns_id: &str,
+        service_name: &str,
+        group_name: &str,
+        page_no: u32,
+        page_size: u32,
+    ) -> Result<SubscriberListResp, NacosError> {
+        let text = self
+            .send(Method::GET, "/nacos/v1/ns/service/subscribers", |req| {
+                req.query(&[("namespaceId", ns_id), ("serviceName", service_name), ("groupName", group_name)])
+                    .query(&[("pageNo", page_no), ("pageSize", page_size)])
+            })
+            .await?;
+        parse_json::<SubscriberListResp>(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::ServiceSpec;
+    use crate::api::client::NacosClient;
+    use crate::api::error::NacosError;
+    use crate::api::test_server::FakeNacos;
+
+    const NS_ID: &str = "dev";
+
+    async fn setup() -> (NacosClient, std::sync::Arc<std::sync::Mutex<FakeNacos>>) {
+        let (url, fake) = FakeNacos::spawn().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_service(NS_ID, "order-service", "DEFAULT_GROUP", 0.5);
+            fake.put_instance(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.0.1", 8080, "DEFAULT", true);
+            fake.put_instance(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.0.2", 8080, "BACKUP", false);
+            fake.put_service(NS_ID, "user-service", "USER_GROUP", 0.0);
+            fake.put_service("", "public-service", "DEFAULT_GROUP", 0.0);
+        }
+        (NacosClient::new(&url, "nacos", "nacos"), fake)
+    }
+
+    #[tokio::test]
+    async fn test_list_services() {
+        let (client, _fake) = setup().await;
+        let resp = client.list_services(NS_ID, "", "", 1, 10).await.unwrap();
+        assert_eq!(resp.count, 2);
+        let order = &resp.serviceList[0];
+        assert_eq!((order.name.as_str(), order.groupName.as_str()), ("order-service", "DEFAULT_GROUP"));
+        assert_eq!((order.ipCount, order.healthyInstanceCount, order.clusterCount), (2, 1, 2));
+
+        let resp = client.list_services(NS_ID, "user", "", 1, 10).await.unwrap();
+        assert_eq!(resp.count, 1);
+        let resp = client.list_services(NS_ID, "", "", 2, 1).await.unwrap();
+        assert_eq!(resp.serviceList[0].name, "user-service");
+    }
+
+    #[tokio::test]
+    async fn test_get_service() {
+        let (client, _fake) = setup().await;
+        let service = client.get_service(NS_ID, "order-service", "DEFAULT_GROUP").await.unwrap();
+        assert_eq!(service.protectThreshold, 0.5);
+        assert_eq!(service.clusters.len(), 2);
+
+        let resp = client.get_service(NS_ID, "missing", "DEFAULT_GROUP").await;
+        assert!(matches!(resp, Err(NacosError::NotFound(_))), "{:?}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_service_crud() {
+        let (client, _fake) = setup().await;
+        let mut spec = ServiceSpec {
+            name: "pay-service".to_string(),
+            group: "PAY_GROUP".to_string(),
+            protect_threshold: 0.3,
+            metadata: HashMap::from([("owner".to_string(), "pay".to_string())]),
+            selector: None,
+        };
+        client.create_service(NS_ID, &spec).await.unwrap();
+        let created = ServiceSpec::from(client.get_service(NS_ID, "pay-service", "PAY_GROUP").await.unwrap());
+        assert_eq!(created, spec);
+
+        spec.protect_threshold = 0.8;
+        spec.metadata.clear();
+        spec.selector = Some("CONSUMER.label.zone = PROVIDER.label.zone".to_string());
+        client.update_service(NS_ID, &spec).await.unwrap();
+        let updated = ServiceSpec::from(client.get_service(NS_ID, "pay-service", "PAY_GROUP").await.unwrap());
+        assert_eq!(updated, spec);
+
+        // 有实例的服务不能删除
+        let resp = client.delete_service(NS_ID, "order-service", "DEFAULT_GROUP").await;
+        assert!(resp.is_err());
+        client.delete_service(NS_ID, "pay-service", "PAY_GROUP").await.unwrap();
+        let resp = client.get_service(NS_ID, "pay-service", "PAY_GROUP").await;
+        assert!(matches!(resp, Err(NacosError::NotFound(_))), "{:?}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_list_subscribers() {
+        let (client, fake) = setup().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_subscriber(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.1.1", 51000, "Nacos-Java-Client:v2.2.0", "cart");
+            fake.put_subscriber(NS_ID, "order-service", "DEFAULT_GROUP", "10.0.1.2", 51001, "Nacos-Go-Client:v2.1.0", "web");
+        }
+        let resp = client.list_subscribers(NS_ID, "order-service", "DEFAULT_GROUP", 1, 1).await.unwrap();
+        assert_eq!(resp.count, 2);
+        let subscriber = &resp.subscribers[0];
+        assert_eq!((subscriber.addrStr.as_str(), subscriber.app.as_str()), ("10.0.1.1:51000", "cart"));
+        assert_eq!(subscriber.namespaceId, NS_ID);
+
+        let resp = client.list_subscribers(NS_ID, "order-service", "DEFAULT_GROUP", 2, 1).await.unwrap();
+        assert_eq!(resp.subscribers[0].agent, "Nacos-Go-Client:v2.1.0");
+        let resp = client.list_subscribers(NS_ID, "user-service", "USER_GROUP", 1, 10).await.unwrap();
+        assert!(resp.subscribers.is_empty());
+    }
+}
@@ -0,0 +1,1040 @@
+// 测试用的本地 Nacos 替身：一个极简的 HTTP/1.1 服务加上内存中的 Nacos 状态，
+// 只实现客户端用到的接口，让 api 层的测试不依赖真实的 Nacos 服务
+use std::collections::HashMap;
+use std::io::{Cursor, Write};
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::api::config_zip::{read_zip, ZipConfig};
+
+pub const USERNAME: &str = "nacos";
+pub const PASSWORD: &str = "nacos";
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    // 查询参数和表单参数合并在一起，Nacos 两种传参方式都接受
+    pub params: HashMap<String, String>,
+    pub headers: HashMap<String, String>, // 名称统一小写
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn param(&self, name: &str) -> &str {
+        self.params.get(name).map(|s| s.as_str()).unwrap_or_default()
+    }
+
+    // multipart/form-data 请求中名为 name 的部分
+    pub fn multipart_part(&self, name: &str) -> Option<&[u8]> {
+        let content_type = self.headers.get("content-type")?;
+        let boundary = format!("--{}", content_type.split_once("boundary=")?.1.trim_matches('"'));
+        let disposition = format!("name=\"{name}\"");
+        split_bytes(&self.body, boundary.as_bytes()).into_iter().find_map(|part| {
+            let header_end = part.windows(4).position(|w| w == b"\r\n\r\n")?;
+            let head = String::from_utf8_lossy(&part[..header_end]);
+            head.contains(&disposition)
+                .then(|| part[header_end + 4..].strip_suffix(b"\r\n").unwrap_or(&part[header_end + 4..]))
+        })
+    }
+}
+
+fn split_bytes<'a>(data: &'a [u8], separator: &[u8]) -> Vec<&'a [u8]> {
+    let mut parts = vec![];
+    let mut rest = data;
+    while let Some(pos) = rest.windows(separator.len()).position(|w| w == separator) {
+        parts.push(&rest[..pos]);
+        rest = &rest[pos + separator.len()..];
+    }
+    parts.push(rest);
+    parts
+}
+
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn text(status: u16, body: &str) -> Response {
+        Response {
+            status,
+            content_type: "text/plain;charset=UTF-8",
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    pub fn json(status: u16, body: serde_json::Value) -> Response {
+        Response {
+            status,
+            content_type: "application/json",
+            body: body.to_string().into_bytes(),
+        }
+    }
+}
+
+// 启动替身服务，返回 base url
+pub async fn spawn<F>(handler: F) -> String
+where
+    F: Fn(Request) -> Response + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handler = Arc::new(handler);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move {
+                let _ = serve(stream, handler.as_ref()).await;
+            });
+        }
+    });
+    url
+}
+
+async fn serve<F>(mut stream: TcpStream, handler: &F) -> std::io::Result<()>
+where
+    F: Fn(Request) -> Response,
+{
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default().to_string();
+
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let content_length = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let is_form = headers
+        .get("content-type")
+        .is_some_and(|v| v.contains("application/x-www-form-urlencoded"));
+
+    let mut body = buf[header_end..].to_vec();
+    while body.len() < content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let mut params = parse_urlencoded(query);
+    if is_form {
+        params.extend(parse_urlencoded(&String::from_utf8_lossy(&body)));
+    }
+
+    let response = handler(Request {
+        method,
+        path: path.to_string(),
+        params,
+        headers,
+        body,
+    });
+
+    let head = format!(
+        "HTTP/1.1 {} Stand-in\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await
+}
+
+fn parse_urlencoded(s: &str) -> HashMap<String, String> {
+    s.split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(k), percent_decode(v))
+        })
+        .collect()
+}
+
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(b) => {
+                        out.push(b);
+                        i += 2;
+                    }
+                    Err(_) => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+pub struct FakeNamespace {
+    pub id: String,
+    pub name: String,
+    pub desc: String,
+}
+
+pub struct FakeConfig {
+    pub id: u64,
+    pub tenant: String,
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub content: String,
+    pub app_name: String,
+    pub tags: String,
+    pub desc: String,
+    pub last_modified: i64,
+}
+
+// 监听配置的客户端上报的 md5
+pub struct FakeListener {
+    pub ip: String,
+    pub tenant: String,
+    pub data_id: String,
+    pub group: String,
+    pub md5: String,
+}
+
+// 与 Nacos 一致：新增记录新内容，修改和删除记录变更前的内容
+pub struct FakeHistory {
+    pub id: u64,
+    pub tenant: String,
+    pub data_id: String,
+    pub group: String,
+    pub content: String,
+    pub op_type: &'static str,
+    pub modified: i64,
+}
+
+pub struct FakeInstance {
+    pub ip: String,
+    pub port: u16,
+    pub cluster: String,
+    pub weight: f64,
+    pub healthy: bool,
+    pub enabled: bool,
+    pub ephemeral: bool,
+    pub metadata: HashMap<String, String>,
+}
+
+pub struct FakeSubscriber {
+    pub ip: String,
+    pub port: u16,
+    pub agent: String,
+    pub app: String,
+}
+
+pub struct FakeService {
+    pub namespace: String,
+    pub name: String,
+    pub group: String,
+    pub protect_threshold: f64,
+    pub metadata: HashMap<String, String>,
+    pub selector: serde_json::Value,
+    pub instances: Vec<FakeInstance>,
+    pub subscribers: Vec<FakeSubscriber>,
+}
+
+impl FakeService {
+    fn clusters(&self) -> Vec<&str> {
+        let mut clusters: Vec<&str> = self.instances.iter().map(|i| i.cluster.as_str()).collect();
+        clusters.sort_unstable();
+        clusters.dedup();
+        clusters
+    }
+}
+
+// 内存中的 Nacos 状态
+pub struct FakeNacos {
+    pub namespaces: Vec<FakeNamespace>,
+    pub configs: Vec<FakeConfig>,
+    pub history: Vec<FakeHistory>,
+    pub services: Vec<FakeService>,
+    pub listeners: Vec<FakeListener>,
+    next_config_id: u64,
+    pub tokens: Vec<String>,
+    pub token_ttl: i64,
+    pub login_count: usize,
+    pub forbidden_paths: Vec<&'static str>, // 这些接口返回没有权限的 403
+    clock: i64,
+}
+
+impl FakeNacos {
+    pub fn new() -> FakeNacos {
+        FakeNacos {
+            namespaces: vec![FakeNamespace {
+                id: "".to_string(),
+                name: "public".to_string(),
+                desc: "Public Namespace".to_string(),
+            }],
+            configs: vec![],
+            history: vec![],
+            services: vec![],
+            listeners: vec![],
+            next_config_id: 0,
+            tokens: vec![],
+            token_ttl: 18000,
+            login_count: 0,
+            forbidden_paths: vec![],
+            clock: 1_700_000_000_000,
+        }
+    }
+
+    // 启动一个带默认数据的替身服务
+    pub async fn spawn() -> (String, Arc<Mutex<FakeNacos>>) {
+        let fake = Arc::new(Mutex::new(FakeNacos::new()));
+        let state = fake.clone();
+        let url = spawn(move |req| state.lock().unwrap().handle(req)).await;
+        (url, fake)
+    }
+
+    // 让已发放的 token 全部失效，模拟 token 过期
+    pub fn expire_tokens(&mut self) {
+        self.tokens.clear();
+    }
+
+    pub fn put_config(&mut self, tenant: &str, data_id: &str, group: &str, type_: &str, content: &str) {
+        self.clock += 1000;
+        let last_modified = self.clock;
+        match self.find_config(tenant, data_id, group) {
+            Some(index) => {
+                let old = self.configs[index].content.clone();
+                self.record_history(tenant, data_id, group, &old, "U");
+                let config = &mut self.configs[index];
+                config.type_ = type_.to_string();
+                config.content = content.to_string();
+                config.last_modified = last_modified;
+            }
+            None => {
+                self.record_history(tenant, data_id, group, content, "I");
+                self.next_config_id += 1;
+                self.configs.push(FakeConfig {
+                    id: self.next_config_id,
+                    tenant: tenant.to_string(),
+                    data_id: data_id.to_string(),
+                    group: group.to_string(),
+                    type_: type_.to_string(),
+                    content: content.to_string(),
+                    app_name: "".to_string(),
+                    tags: "".to_string(),
+                    desc: "".to_string(),
+                    last_modified,
+                });
+            }
+        }
+    }
+
+    pub fn put_listener(&mut self, tenant: &str, data_id: &str, group: &str, ip: &str, md5: &str) {
+        self.listeners.push(FakeListener {
+            ip: ip.to_string(),
+            tenant: tenant.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            md5: md5.to_string(),
+        });
+    }
+
+    pub fn put_service(&mut self, namespace: &str, name: &str, group: &str, protect_threshold: f64) {
+        self.services.push(FakeService {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            group: group.to_string(),
+            protect_threshold,
+            metadata: HashMap::new(),
+            selector: json!({"type": "none", "contextType": "NONE"}),
+            instances: vec![],
+            subscribers: vec![],
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn put_instance(&mut self, namespace: &str, service: &str, group: &str, ip: &str, port: u16, cluster: &str, healthy: bool) {
+        if let Some(service) = self.find_service_mut(namespace, service, group) {
+            service.instances.push(FakeInstance {
+                ip: ip.to_string(),
+                port,
+                cluster: cluster.to_string(),
+                weight: 1.0,
+                healthy,
+                enabled: true,
+                ephemeral: true,
+                metadata: HashMap::new(),
+            });
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn put_subscriber(&mut self, namespace: &str, service: &str, group: &str, ip: &str, port: u16, agent: &str, app: &str) {
+        if let Some(service) = self.find_service_mut(namespace, service, group) {
+            service.subscribers.push(FakeSubscriber {
+                ip: ip.to_string(),
+                port,
+                agent: agent.to_string(),
+                app: app.to_string(),
+            });
+        }
+    }
+
+    fn find_service_mut(&mut self, namespace: &str, name: &str, group: &str) -> Option<&mut FakeService> {
+        self.services
+            .iter_mut()
+            .find(|s| s.namespace == namespace && s.name == name && s.group == group)
+    }
+
+    fn record_history(&mut self, tenant: &str, data_id: &str, group: &str, content: &str, op_type: &'static str) {
+        self.history.push(FakeHistory {
+            id: self.history.len() as u64 + 1,
+            tenant: tenant.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            content: content.to_string(),
+            op_type,
+            modified: self.clock,
+        });
+    }
+
+    fn find_config(&self, tenant: &str, data_id: &str, group: &str) -> Option<usize> {
+        self.configs
+            .iter()
+            .position(|c| c.tenant == tenant && c.data_id == data_id && c.group == group)
+    }
+
+    fn handle(&mut self, req: Request) -> Response {
+        if req.path == "/nacos/v1/auth/login" {
+            return self.login(&req);
+        }
+        if !self.tokens.iter().any(|t| t == req.param("accessToken")) {
+            return Response::text(403, "token expired!");
+        }
+        if self.forbidden_paths.contains(&req.path.as_str()) {
+            return Response::text(403, "authorization failed!");
+        }
+
+        match (req.method.as_str(), req.path.as_str()) {
+            ("GET", "/nacos/v1/console/namespaces") => self.list_namespaces(),
+            ("POST", "/nacos/v1/console/namespaces") => {
+                let id = match req.param("customNamespaceId") {
+                    "" => format!("generated-{}", self.namespaces.len()),
+                    id => id.to_string(),
+                };
+                self.namespaces.push(FakeNamespace {
+                    id,
+                    name: req.param("namespaceName").to_string(),
+                    desc: req.param("namespaceDesc").to_string(),
+                });
+                Response::text(200, "true")
+            }
+            ("PUT", "/nacos/v1/console/namespaces") => {
+                match self.namespaces.iter_mut().find(|ns| ns.id == req.param("namespace")) {
+                    Some(ns) => {
+                        ns.name = req.param("namespaceShowName").to_string();
+                        ns.desc = req.param("namespaceDesc").to_string();
+                        Response::text(200, "true")
+                    }
+                    None => Response::text(404, "namespace not exist"),
+                }
+            }
+            ("DELETE", "/nacos/v1/console/namespaces") => {
+                self.namespaces.retain(|ns| ns.id != req.param("namespaceId"));
+                Response::text(200, "true")
+            }
+            ("GET", "/nacos/v2/cs/history/list") => self.list_history(&req),
+            ("GET", "/nacos/v2/cs/history") => {
+                let found = self.history.iter().find(|h| {
+                    h.id.to_string() == req.param("nid")
+                        && h.tenant == req.param("namespaceId")
+                        && h.data_id == req.param("dataId")
+                        && h.group == req.param("group")
+                });
+                match found {
+                    Some(history) => Response::json(200, json!({"code": 0, "message": "success", "data": history_json(history, true)})),
+                    None => Response::json(404, json!({"code": 20004, "message": "certain config history not exist", "data": null})),
+                }
+            }
+            ("GET", "/nacos/v1/cs/configs") if req.params.contains_key("search") => self.search_configs(&req),
+            ("GET", "/nacos/v1/cs/configs") if req.param("exportV2") == "true" => self.export_configs(&req),
+            ("POST", "/nacos/v1/cs/configs") if req.param("import") == "true" => self.import_configs(&req),
+            ("GET", "/nacos/v1/cs/configs/listener") => {
+                let status: serde_json::Map<String, serde_json::Value> = self
+                    .listeners
+                    .iter()
+                    .filter(|l| {
+                        l.tenant == req.param("tenant") && l.data_id == req.param("dataId") && l.group == req.param("group")
+                    })
+                    .map(|l| (l.ip.clone(), json!(l.md5)))
+                    .collect();
+                Response::json(200, json!({"collectionStatus": 200, "lisentersGroupkeyStatus": status}))
+            }
+            // 长轮询：立即比较 md5 返回，不挂起请求
+            ("POST", "/nacos/v1/cs/configs/listener") => {
+                let mut changed = String::new();
+                for line in req.param("Listening-Configs").split('\u{1}').filter(|l| !l.is_empty()) {
+                    let words: Vec<&str> = line.split('\u{2}').collect();
+                    let (data_id, group, md5) = (words[0], words[1], words[2]);
+                    let tenant = words.get(3).copied().unwrap_or_default();
+                    let current = self
+                        .find_config(tenant, data_id, group)
+                        .map(|index| format!("{:x}", md5::compute(&self.configs[index].content)))
+                        .unwrap_or_default();
+                    if current != md5 {
+                        changed.push_str(&words[..2].join("\u{2}"));
+                        if !tenant.is_empty() {
+                            changed.push('\u{2}');
+                            changed.push_str(tenant);
+                        }
+                        changed.push('\u{1}');
+                    }
+                }
+                Response::text(200, &percent_encode(&changed))
+            }
+            ("GET", "/nacos/v1/cs/listener") => {
+                let status: serde_json::Map<String, serde_json::Value> = self
+                    .listeners
+                    .iter()
+                    .filter(|l| l.ip == req.param("ip"))
+                    .map(|l| (group_key(&l.data_id, &l.group, &l.tenant), json!(l.md5)))
+                    .collect();
+                Response::json(200, json!({"collectionStatus": 200, "lisentersGroupkeyStatus": status}))
+            }
+            ("GET", "/nacos/v1/cs/configs") => {
+                match self.find_config(req.param("tenant"), req.param("dataId"), req.param("group")) {
+                    Some(index) => Response::text(200, &self.configs[index].content),
+                    None => Response::text(404, "config data not exist"),
+                }
+            }
+            ("POST", "/nacos/v1/cs/configs") => {
+                // 带 casMd5 时只有与当前内容一致才允许更新
+                let current = self.find_config(req.param("tenant"), req.param("dataId"), req.param("group"));
+                let cas_md5 = req.param("casMd5");
+                if !cas_md5.is_empty()
+                    && current.is_none_or(|index| format!("{:x}", md5::compute(&self.configs[index].content)) != cas_md5)
+                {
+                    return Response::text(500, "caused: Cas publish fail, server md5 may have changed.;");
+                }
+                self.put_config(
+                    req.param("tenant"),
+                    req.param("dataId"),
+                    req.param("group"),
+                    req.param("type"),
+                    req.param("content"),
+                );
+                // 和 Nacos 一样，没带的字段会被清空
+                if let Some(index) = self.find_config(req.param("tenant"), req.param("dataId"), req.param("group")) {
+                    let config = &mut self.configs[index];
+                    config.app_name = req.param("appName").to_string();
+                    config.tags = req.param("config_tags").to_string();
+                    config.desc = req.param("desc").to_string();
+                }
+                Response::text(200, "true")
+            }
+            ("DELETE", "/nacos/v1/cs/configs") if req.param("delType") == "ids" => {
+                let ids: Vec<&str> = req.param("ids").split(',').collect();
+                let deleted: Vec<FakeConfig> = self
+                    .configs
+                    .extract_if(.., |c| ids.contains(&c.id.to_string().as_str()))
+                    .collect();
+                for config in deleted {
+                    self.clock += 1000;
+                    self.record_history(&config.tenant, &config.data_id, &config.group, &config.content, "D");
+                }
+                Response::json(200, json!({"code": 200, "message": null, "data": true}))
+            }
+            ("DELETE", "/nacos/v1/cs/configs") => {
+                match self.find_config(req.param("tenant"), req.param("dataId"), req.param("group")) {
+                    Some(index) => {
+                        let config = self.configs.remove(index);
+                        self.clock += 1000;
+                        self.record_history(&config.tenant, &config.data_id, &config.group, &config.content, "D");
+                        Response::text(200, "true")
+                    }
+                    None => Response::text(200, "true"),
+                }
+            }
+            ("GET", "/nacos/v1/ns/catalog/services") => self.list_services(&req),
+            ("GET", "/nacos/v1/ns/catalog/instances") => self.list_instances(&req),
+            ("GET", "/nacos/v1/ns/service/subscribers") => self.list_subscribers(&req),
+            ("PUT", "/nacos/v1/ns/instance") => self.update_instance(&req),
+            ("POST", "/nacos/v1/ns/instance") => self.register_instance(&req),
+            ("DELETE", "/nacos/v1/ns/instance") => {
+                let Some(service) =
+                    self.find_service_mut(req.param("namespaceId"), req.param("serviceName"), req.param("groupName"))
+                else {
+                    return Response::text(404, &format!("service not found: {}", req.param("serviceName")));
+                };
+                service.instances.retain(|i| {
+                    !(i.ip == req.param("ip") && i.port.to_string() == req.param("port") && i.cluster == req.param("clusterName"))
+                });
+                Response::text(200, "ok")
+            }
+            ("GET", "/nacos/v1/ns/service") => {
+                let found = self.services.iter().find(|s| {
+                    s.namespace == req.param("namespaceId")
+                        && s.name == req.param("serviceName")
+                        && s.group == req.param("groupName")
+                });
+                match found {
+                    Some(service) => Response::json(200, service_json(service)),
+                    None => Response::text(404, &format!("service not found: {}", req.param("serviceName"))),
+                }
+            }
+            ("POST", "/nacos/v1/ns/service") | ("PUT", "/nacos/v1/ns/service") => self.save_service(&req),
+            ("DELETE", "/nacos/v1/ns/service") => {
+                let (namespace, name, group) = (req.param("namespaceId"), req.param("serviceName"), req.param("groupName"));
+                match self.services.iter().position(|s| s.namespace == namespace && s.name == name && s.group == group) {
+                    Some(i) if !self.services[i].instances.is_empty() => {
+                        Response::text(400, &format!("service {} is not empty, can't be delete", name))
+                    }
+                    Some(i) => {
+                        self.services.remove(i);
+                        Response::text(200, "ok")
+                    }
+                    None => Response::text(404, &format!("service not found: {}", name)),
+                }
+            }
+            _ => Response::text(404, "no such api"),
+        }
+    }
+
+    fn login(&mut self, req: &Request) -> Response {
+        if req.param("username") != USERNAME || req.param("password") != PASSWORD {
+            return Response::text(403, "unknown user!");
+        }
+        self.login_count += 1;
+        let token = format!("token-{}", self.login_count);
+        self.tokens.push(token.clone());
+        Response::json(
+            200,
+            json!({
+                "accessToken": token,
+                "tokenTtl": self.token_ttl,
+                "globalAdmin": true,
+                "username": USERNAME,
+            }),
+        )
+    }
+
+    // 与控制台的服务列表一致，服务名和分组按包含匹配
+    fn list_services(&self, req: &Request) -> Response {
+        let services: Vec<_> = self
+            .services
+            .iter()
+            .filter(|s| s.namespace == req.param("namespaceId"))
+            .filter(|s| s.name.contains(req.param("serviceNameParam")) && s.group.contains(req.param("groupNameParam")))
+            .collect();
+        let page_no: usize = req.param("pageNo").parse().unwrap_or(1);
+        let page_size: usize = req.param("pageSize").parse().unwrap_or(10);
+        let list: Vec<_> = services
+            .iter()
+            .skip((page_no.max(1) - 1) * page_size)
+            .take(page_size)
+            .map(|s| {
+                json!({
+                    "name": s.name,
+                    "groupName": s.group,
+                    "clusterCount": s.clusters().len(),
+                    "ipCount": s.instances.len(),
+                    "healthyInstanceCount": s.instances.iter().filter(|i| i.healthy).count(),
+                    "triggerFlag": "false",
+                })
+            })
+            .collect();
+        Response::json(200, json!({"count": services.len(), "serviceList": list}))
+    }
+
+    // 与控制台一样按集群查询，集群为必填
+    fn list_instances(&self, req: &Request) -> Response {
+        let found = self.services.iter().find(|s| {
+            s.namespace == req.param("namespaceId")
+                && s.name == req.param("serviceName")
+                && s.group == req.param("groupName")
+        });
+        let Some(service) = found else {
+            return Response::text(404, &format!("service not found: {}", req.param("serviceName")));
+        };
+        let instances: Vec<_> = service.instances.iter().filter(|i| i.cluster == req.param("clusterName")).collect();
+        let page_no: usize = req.param("pageNo").parse().unwrap_or(1);
+        let page_size: usize = req.param("pageSize").parse().unwrap_or(10);
+        let list: Vec<_> = instances
+            .iter()
+            .skip((page_no.max(1) - 1) * page_size)
+            .take(page_size)
+            .map(|i| {
+                json!({
+                    "instanceId": format!("{}#{}#{}#{}@@{}", i.ip, i.port, i.cluster, service.group, service.name),
+                    "ip": i.ip,
+                    "port": i.port,
+                    "weight": i.weight,
+                    "healthy": i.healthy,
+                    "enabled": i.enabled,
+                    "ephemeral": i.ephemeral,
+                    "clusterName": i.cluster,
+                    "serviceName": format!("{}@@{}", service.group, service.name),
+                    "metadata": i.metadata,
+                })
+            })
+            .collect();
+        Response::json(200, json!({"count": instances.len(), "list": list}))
+    }
+
+    // 服务不存在时与 Nacos 一样返回空列表
+    fn list_subscribers(&self, req: &Request) -> Response {
+        let found = self.services.iter().find(|s| {
+            s.namespace == req.param("namespaceId")
+                && s.name == req.param("serviceName")
+                && s.group == req.param("groupName")
+        });
+        let subscribers = found.map_or(&[][..], |s| &s.subscribers[..]);
+        let page_no: usize = req.param("pageNo").parse().unwrap_or(1);
+        let page_size: usize = req.param("pageSize").parse().unwrap_or(10);
+        let list: Vec<_> = subscribers
+            .iter()
+            .skip((page_no.max(1) - 1) * page_size)
+            .take(page_size)
+            .map(|s| {
+                json!({
+                    "addrStr": format!("{}:{}", s.ip, s.port),
+                    "agent": s.agent,
+                    "app": s.app,
+                    "ip": s.ip,
+                    "port": s.port,
+                    "namespaceId": req.param("namespaceId"),
+                    "serviceName": format!("{}@@{}", req.param("groupName"), req.param("serviceName")),
+                    "cluster": "",
+                })
+            })
+            .collect();
+        Response::json(200, json!({"count": subscribers.len(), "subscribers": list}))
+    }
+
+    // POST 新建已存在的服务和 PUT 修改不存在的服务都会失败
+    fn save_service(&mut self, req: &Request) -> Response {
+        let (namespace, name, group) = (req.param("namespaceId"), req.param("serviceName"), req.param("groupName"));
+        let exists = self.find_service_mut(namespace, name, group).is_some();
+        match (req.method.as_str(), exists) {
+            ("POST", true) => return Response::text(400, &format!("specified service already exists, serviceName : {}", name)),
+            ("PUT", false) => return Response::text(400, &format!("service {} not found!", name)),
+            ("POST", false) => self.put_service(namespace, name, group, 0.0),
+            _ => {}
+        }
+        let Some(service) = self.find_service_mut(namespace, name, group) else {
+            return Response::text(500, "service lost");
+        };
+        service.protect_threshold = req.param("protectThreshold").parse().unwrap_or(0.0);
+        service.metadata = serde_json::from_str(req.param("metadata")).unwrap_or_default();
+        service.selector = serde_json::from_str(req.param("selector")).unwrap_or_else(|_| json!({"type": "none"}));
+        Response::text(200, "ok")
+    }
+
+    // 与 Nacos 一样，注册到不存在的服务时自动创建服务
+    fn register_instance(&mut self, req: &Request) -> Response {
+        let (namespace, name, group) = (req.param("namespaceId"), req.param("serviceName"), req.param("groupName"));
+        if self.find_service_mut(namespace, name, group).is_none() {
+            self.put_service(namespace, name, group, 0.0);
+        }
+        let Ok(port) = req.param("port").parse() else {
+            return Response::text(400, "invalid port");
+        };
+        let instance = FakeInstance {
+            ip: req.param("ip").to_string(),
+            port,
+            cluster: req.param("clusterName").to_string(),
+            weight: req.param("weight").parse().unwrap_or(1.0),
+            healthy: req.param("healthy") != "false",
+            enabled: req.param("enabled") != "false",
+            ephemeral: req.param("ephemeral") != "false",
+            metadata: serde_json::from_str(req.param("metadata")).unwrap_or_default(),
+        };
+        if let Some(service) = self.find_service_mut(namespace, name, group) {
+            service.instances.retain(|i| !(i.ip == instance.ip && i.port == instance.port && i.cluster == instance.cluster));
+            service.instances.push(instance);
+        }
+        Response::text(200, "ok")
+    }
+
+    fn update_instance(&mut self, req: &Request) -> Response {
+        let Some(service) = self.find_service_mut(req.param("namespaceId"), req.param("serviceName"), req.param("groupName")) else {
+            return Response::text(404, &format!("service not found: {}", req.param("serviceName")));
+        };
+        let found = service.instances.iter_mut().find(|i| {
+            i.ip == req.param("ip") && i.port.to_string() == req.param("port") && i.cluster == req.param("clusterName")
+        });
+        let Some(instance) = found else {
+            return Response::text(400, &format!("instance not found: {}:{}", req.param("ip"), req.param("port")));
+        };
+        instance.weight = req.param("weight").parse().unwrap_or(1.0);
+        instance.enabled = req.param("enabled") != "false";
+        instance.metadata = serde_json::from_str(req.param("metadata")).unwrap_or_default();
+        Response::text(200, "ok")
+    }
+
+    fn export_configs(&self, req: &Request) -> Response {
+        let ids: Vec<&str> = req.param("ids").split(',').filter(|id| !id.is_empty()).collect();
+        let configs: Vec<ZipConfig> = self
+            .configs
+            .iter()
+            .filter(|c| c.tenant == req.param("tenant"))
+            .filter(|c| ids.is_empty() || ids.contains(&c.id.to_string().as_str()))
+            .map(|c| ZipConfig {
+                data_id: c.data_id.clone(),
+                group: c.group.clone(),
+                type_: c.type_.clone(),
+                app_name: c.app_name.clone(),
+                desc: c.desc.clone(),
+                content: c.content.clone(),
+            })
+            .collect();
+        Response {
+            status: 200,
+            content_type: "application/zip",
+            body: write_zip(&configs).unwrap(),
+        }
+    }
+
+    // 与 Nacos 一致：abort 遇到已存在的配置时，它和之后的配置都算失败
+    fn import_configs(&mut self, req: &Request) -> Response {
+        let Some(configs) = req.multipart_part("file").and_then(|zip| read_zip(zip).ok()) else {
+            return Response::json(200, json!({"code": 100005, "message": "导入的文件数据为空", "data": null}));
+        };
+        let tenant = req.param("namespace");
+        let (mut succ, mut skip, mut fail) = (0, vec![], vec![]);
+        for config in configs {
+            let key = json!({"dataId": config.data_id, "group": config.group});
+            let exists = self.find_config(tenant, &config.data_id, &config.group).is_some();
+            if !fail.is_empty() || (exists && req.param("policy") == "ABORT") {
+                fail.push(key);
+                continue;
+            }
+            if exists && req.param("policy") == "SKIP" {
+                skip.push(key);
+                continue;
+            }
+            self.put_config(tenant, &config.data_id, &config.group, &config.type_, &config.content);
+            let index = self.find_config(tenant, &config.data_id, &config.group).unwrap();
+            self.configs[index].app_name = config.app_name;
+            self.configs[index].desc = config.desc;
+            succ += 1;
+        }
+        let data = json!({"succCount": succ, "skipCount": skip.len(), "skipData": skip, "failData": fail});
+        Response::json(200, json!({"code": 200, "message": "导入成功", "data": data}))
+    }
+
+    // 模糊查询：参数为空不过滤，含 * 时按通配符匹配，否则精确匹配
+    fn search_configs(&self, req: &Request) -> Response {
+        let matches = |pattern: &str, value: &str| pattern.is_empty() || glob_match(pattern, value);
+        let items: Vec<_> = self
+            .configs
+            .iter()
+            .filter(|c| c.tenant == req.param("tenant"))
+            .filter(|c| matches(req.param("dataId"), &c.data_id) && matches(req.param("group"), &c.group))
+            .collect();
+
+        let page_no: usize = req.param("pageNo").parse().unwrap_or(1);
+        let page_size: usize = req.param("pageSize").parse().unwrap_or(10);
+        let page: Vec<_> = items
+            .iter()
+            .skip((page_no - 1) * page_size)
+            .take(page_size)
+            .map(|c| config_json(c))
+            .collect();
+        Response::json(
+            200,
+            json!({
+                "totalCount": items.len(),
+                "pageNumber": page_no,
+                "pagesAvailable": items.len().div_ceil(page_size),
+                "pageItems": page,
+            }),
+        )
+    }
+
+    // 最新的记录在前
+    fn list_history(&self, req: &Request) -> Response {
+        let items: Vec<_> = self
+            .history
+            .iter()
+            .rev()
+            .filter(|h| {
+                h.tenant == req.param("namespaceId") && h.data_id == req.param("dataId") && h.group == req.param("group")
+            })
+            .collect();
+        let page_no: usize = req.param("pageNo").parse().unwrap_or(1);
+        let page_size: usize = req.param("pageSize").parse().unwrap_or(100);
+        let page: Vec<_> = items
+            .iter()
+            .skip((page_no - 1) * page_size)
+            .take(page_size)
+            .map(|h| history_json(h, false))
+            .collect();
+        let data = json!({
+            "totalCount": items.len(),
+            "pageNumber": page_no,
+            "pagesAvailable": items.len().div_ceil(page_size),
+            "pageItems": page,
+        });
+        Response::json(200, json!({"code": 0, "message": "success", "data": data}))
+    }
+
+    fn list_namespaces(&self) -> Response {
+        let data: Vec<_> = self
+            .namespaces
+            .iter()
+            .map(|ns| {
+                json!({
+                    "namespace": ns.id,
+                    "namespaceShowName": ns.name,
+                    "namespaceDesc": ns.desc,
+                    "quota": 200,
+                    "configCount": self.configs.iter().filter(|c| c.tenant == ns.id).count(),
+                    "type": if ns.id.is_empty() { 0 } else { 2 },
+                })
+            })
+            .collect();
+        Response::json(200, json!({"code": 200, "message": null, "data": data}))
+    }
+}
+
+fn glob_match(pattern: &str, value: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == value,
+        Some((prefix, rest)) => {
+            let Some(value) = value.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=value.len())
+                .filter(|i| value.is_char_boundary(*i))
+                .any(|i| glob_match(rest, &value[i..]))
+        }
+    }
+}
+
+fn config_json(config: &FakeConfig) -> serde_json::Value {
+    json!({
+        "id": config.id.to_string(),
+        "dataId": config.data_id,
+        "group": config.group,
+        "content": config.content,
+        "md5": null,
+        "encryptedDataKey": null,
+        "tenant": config.tenant,
+        "appName": config.app_name,
+        "type": config.type_,
+        "lastModified": config.last_modified,
+    })
+}
+
+fn service_json(service: &FakeService) -> serde_json::Value {
+    let clusters: Vec<_> = service
+        .clusters()
+        .into_iter()
+        .map(|name| json!({"name": name, "healthChecker": {"type": "TCP"}, "metadata": {}}))
+        .collect();
+    json!({
+        "namespaceId": service.namespace,
+        "groupName": service.group,
+        "name": service.name,
+        "protectThreshold": service.protect_threshold,
+        "metadata": service.metadata,
+        "selector": service.selector,
+        "clusters": clusters,
+    })
+}
+
+fn history_json(history: &FakeHistory, with_content: bool) -> serde_json::Value {
+    json!({
+        "id": history.id.to_string(),
+        "lastId": -1,
+        "dataId": history.data_id,
+        "group": history.group,
+        "tenant": history.tenant,
+        "appName": "",
+        "md5": null,
+        "content": if with_content { Some(&history.content) } else { None },
+        "srcIp": "127.0.0.1",
+        "srcUser": "nacos",
+        "opType": format!("{:<10}", history.op_type),
+        "createdTime": "2010-05-04T16:00:00.000+0000",
+        "lastModifiedTime": history.modified,
+    })
+}
+
+// Nacos 的 groupKey：dataId+group[+tenant]，其中的 '+' 和 '%' 转义为 %2B 和 %25
+pub fn group_key(data_id: &str, group: &str, tenant: &str) -> String {
+    let escape = |s: &str| s.replace('%', "%25").replace('+', "%2B");
+    let mut key = format!("{}+{}", escape(data_id), escape(group));
+    if !tenant.is_empty() {
+        key.push('+');
+        key.push_str(&escape(tenant));
+    }
+    key
+}
+
+// 按 Nacos 控制台导出的格式打包：{group}/{dataId} 文件加上根目录的 .metadata.yml
+pub fn write_zip(configs: &[ZipConfig]) -> Result<Vec<u8>, String> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    for config in configs {
+        zip.start_file(format!("{}/{}", config.group, config.data_id), options)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(config.content.as_bytes()).map_err(|e| e.to_string())?;
+    }
+
+    // 空字段不写，与 Nacos 导出的一致
+    let items: Vec<_> = configs
+        .iter()
+        .map(|c| {
+            let mut item = serde_json::Map::new();
+            item.insert("group".to_string(), json!(c.group));
+            item.insert("dataId".to_string(), json!(c.data_id));
+            for (key, value) in [("desc", &c.desc), ("type", &c.type_), ("appName", &c.app_name)] {
+                if !value.is_empty() {
+                    item.insert(key.to_string(), json!(value));
+                }
+            }
+            serde_json::Value::Object(item)
+        })
+        .collect();
+    let yaml = serde_yaml::to_string(&json!({ "metadata": items })).map_err(|e| e.to_string())?;
+    zip.start_file(".metadata.yml", options).map_err(|e| e.to_string())?;
+    zip.write_all(yaml.as_bytes()).map_err(|e| e.to_string())?;
+
+    Ok(zip.finish().map_err(|e| e.to_string())?.into_inner())
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use reqwest::Method;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::api::client::NacosClient;
+use crate::api::config::config_md5;
+use crate::api::error::NacosError;
+
+// 长轮询的挂起时间，期间没有变化时服务端返回空
+const LONG_POLLING_TIMEOUT: Duration = Duration::from_secs(30);
+// 请求超时要比挂起时间长，否则每一轮都会超时
+const LONG_POLLING_GRACE: Duration = Duration::from_secs(10);
+// 两轮之间的最小间隔，防止不挂起请求的代理或服务端让循环空转
+const MIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
+// 请求失败后的重试间隔
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+// 协议里的分隔符：字段之间用 \x02，配置之间用 \x01
+const WORD_SEPARATOR: char = '\u{2}';
+const LINE_SEPARATOR: char = '\u{1}';
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WatchKey {
+    pub tenant: String,
+    pub data_id: String,
+    pub group: String,
+}
+
+// 服务端内容变化，content 为 None 表示配置已被删除
+#[derive(Debug)]
+pub struct ConfigChange {
+    pub key: WatchKey,
+    pub content: Option<String>,
+}
+
+enum Command {
+    Watch(WatchKey, String), // 配置和本地内容的 md5
+    Unwatch(WatchKey),
+}
+
+// 用 Nacos 的长轮询监听一组配置：一个后台任务带着所有配置的 md5 发起长轮询，
+// 有变化时拉取新内容并回调 on_change。ConfigWatcher 被丢弃后后台任务随之结束
+pub struct ConfigWatcher {
+    commands: UnboundedSender<Command>,
+}
+
+impl ConfigWatcher {
+    pub fn spawn<F>(client: NacosClient, on_change: F) -> ConfigWatcher
+    where
+        F: Fn(ConfigChange) + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(client, rx, on_change));
+        ConfigWatcher { commands: tx }
+    }
+
+    // 开始监听，或者在本地内容变化后（例如自己发布之后）更新 md5
+    pub fn watch(&self, key: WatchKey, content: &str) {
+        let _ = self.commands.send(Command::Watch(key, config_md5(content)));
+    }
+
+    pub fn unwatch(&self, key: WatchKey) {
+        let _ = self.commands.send(Command::Unwatch(key));
+    }
+}
+
+fn apply(watched: &mut HashMap<WatchKey, String>, command: Command) {
+    match command {
+        Command::Watch(key, md5) => {
+            watched.insert(key, md5);
+        }
+        Command::Unwatch(key) => {
+            watched.remove(&key);
+        }
+    }
+}
+
+// 等待期间照常处理命令，通道关闭时返回 false
+async fn idle(commands: &mut UnboundedReceiver<Command>, watched: &mut HashMap<WatchKey, String>, duration: Duration) -> bool {
+    let deadline = tokio::time::sleep(duration);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => return true,
+            command = commands.recv() => match command {
+                Some(command) => apply(watched, command),
+                None => return false,
+            },
+        }
+    }
+}
+
+async fn run<F>(client: NacosClient, mut commands: UnboundedReceiver<Command>, on_change: F)
+where
+    F: Fn(ConfigChange),
+{
+    let mut watched: HashMap<WatchKey, String> = HashMap::new();
+    loop {
+        if watched.is_empty() {
+            match commands.recv().await {
+                Some(command) => apply(&mut watched, command),
+                None => return,
+            }
+            continue;
+        }
+
+        // 监听的配置有变化时放弃当前这一轮，带着新的 md5 重新发起
+        let started = Instant::now();
+        let result = tokio::select! {
+            command = commands.recv() => match command {
+                Some(command) => {
+                    apply(&mut watched, command);
+                    continue;
+                }
+                None => return,
+            },
+            result = client.poll_config_changes(&watched, LONG_POLLING_TIMEOUT) => result,
+        };
+        let Ok(changed) = result else {
+            if !idle(&mut commands, &mut watched, RETRY_DELAY).await {
+                return;
+            }
+            continue;
+        };
+
+        for key in changed {
+            let Some(md5) = watched.get(&key) else {
+                continue;
+            };
+            let content = match client.get_config(Some(key.tenant.as_str()), &key.data_id, &key.group).await {
+                Ok(content) => Some(content),
+                Err(NacosError::NotFound(_)) => None,
+                // md5 保持不变，下一轮服务端会再次报告这个配置
+                Err(_) => continue,
+            };
+            // 已删除的配置按 Nacos 的约定用空 md5 继续监听，重新创建时也能收到通知
+            let new_md5 = content.as_deref().map(config_md5).unwrap_or_default();
+            if *md5 == new_md5 {
+                continue;
+            }
+            watched.insert(key.clone(), new_md5);
+            on_change(ConfigChange { key, content });
+        }
+
+        if let Some(rest) = MIN_POLL_INTERVAL.checked_sub(started.elapsed())
+            && !idle(&mut commands, &mut watched, rest).await
+        {
+            return;
+        }
+    }
+}
+
+// Listening-Configs 参数：dataId^2group^2md5[^2tenant]^1，public 命名空间不带 tenant
+pub fn listening_configs(watched: &HashMap<WatchKey, String>) -> String {
+    let mut keys: Vec<_> = watched.iter().collect();
+    keys.sort();
+    keys.iter()
+        .map(|(key, md5)| {
+            let mut line = [key.data_id.as_str(), key.group.as_str(), md5.as_str()].join(&WORD_SEPARATOR.to_string());
+            if !key.tenant.is_empty() {
+                line.push(WORD_SEPARATOR);
+                line.push_str(&key.tenant);
+            }
+            line.push(LINE_SEPARATOR);
+            line
+        })
+        .collect()
+}
+
+// 长轮询的响应是 url 编码后的 dataId^2group[^2tenant]^1 列表
+pub fn parse_changed_keys(body: &str) -> Vec<WatchKey> {
+    url_decode(body.trim())
+        .split(LINE_SEPARATOR)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split(WORD_SEPARATOR).collect();
+            match words[..] {
+                [data_id, group] => Some((data_id, group, "")),
+                [data_id, group, tenant] => Some((data_id, group, tenant)),
+                _ => None,
+            }
+        })
+        .map(|(data_id, group, tenant)| WatchKey {
+            tenant: tenant.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+        })
+        .collect()
+}
+
+fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => match u8::from_str_radix(&s[i + 1..i + 3], 16) {
+                Ok(b) => {
+                    out.push(b);
+                    i += 2;
+                }
+                Err(_) => out.push(b'%'),
+            },
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+impl NacosClient {
+    // 一轮长轮询：服务端挂起请求直到有配置的 md5 与 watched 不一致或超时，返回有变化的配置
+    pub async fn poll_config_changes(
+        &self,
+        watched: &HashMap<WatchKey, String>,
+        timeout: Duration,
+    ) -> Result<Vec<WatchKey>, NacosError> {
+        let listening = listening_configs(watched);
+        let timeout_ms = timeout.as_millis().to_string();
+        let text = self
+            .send(Method::POST, "/nacos/v1/cs/configs/listener", |req| {
+                req.header("Long-Pulling-Timeout", timeout_ms.as_str())
+                    .timeout(timeout + LONG_POLLING_GRACE)
+                    .form(&[("Listening-Configs", listening.as_str())])
+            })
+            .await?;
+        Ok(parse_changed_keys(&text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::api::test_server::FakeNacos;
+
+    const NS_ID: &str = "dev";
+
+    fn key(tenant: &str, data_id: &str) -> WatchKey {
+        WatchKey {
+            tenant: tenant.to_string(),
+            data_id: data_id.to_string(),
+            group: "DEFAULT_GROUP".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_listening_configs_protocol() {
+        let watched = HashMap::from([(key("", "a.yml"), "m1".to_string()), (key(NS_ID, "b.yml"), "m2".to_string())]);
+        assert_eq!(
+            listening_configs(&watched),
+            "a.yml\u{2}DEFAULT_GROUP\u{2}m1\u{1}b.yml\u{2}DEFAULT_GROUP\u{2}m2\u{2}dev\u{1}"
+        );
+        let keys = parse_changed_keys("a.yml%02DEFAULT_GROUP%01b.yml%02DEFAULT_GROUP%02dev%01\n");
+        assert_eq!(keys, vec![key("", "a.yml"), key(NS_ID, "b.yml")]);
+        assert!(parse_changed_keys("").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_watch_changes() {
+        let (url, fake) = FakeNacos::spawn().await;
+        {
+            let mut fake = fake.lock().unwrap();
+            fake.put_config(NS_ID, "a.yml", "DEFAULT_GROUP", "yaml", "a: 1\n");
+            fake.put_config("", "b.yml", "DEFAULT_GROUP", "yaml", "b: 1\n");
+        }
+        let client = NacosClient::new(&url, "nacos", "nacos");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let watcher = ConfigWatcher::spawn(client.clone(), move |change| {
+            let _ = tx.send(change);
+        });
+        watcher.watch(key(NS_ID, "a.yml"), "a: 1\n");
+        watcher.watch(key("", "b.yml"), "b: 1\n");
+
+        client.publish_config(Some(NS_ID), "a.yml", "DEFAULT_GROUP", "a: 2\n", "yaml", None).await.unwrap();
+        let change = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(change.key, key(NS_ID, "a.yml"));
+        assert_eq!(change.content.as_deref(), Some("a: 2\n"));
+
+        // 不再监听的配置没有通知，删除的配置内容为 None
+        watcher.unwatch(key(NS_ID, "a.yml"));
+        client.publish_config(Some(NS_ID), "a.yml", "DEFAULT_GROUP", "a: 3\n", "yaml", None).await.unwrap();
+        client.delete_config(None, "b.yml", "DEFAULT_GROUP").await.unwrap();
+        let change = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!((change.key, change.content), (key("", "b.yml"), None));
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -1,6 +1,67 @@
-use ratatui::widgets::{Block, Borders};
+pub mod clone;
+pub mod create;
+pub mod delete;
+pub mod diff;
+pub mod editor;
+pub mod external;
+pub mod filter;
+pub mod history;
+pub mod instance;
+pub mod instance_edit;
+pub mod listener;
+pub mod merge;
+pub mod publish;
+pub mod register;
+pub mod search;
+pub mod service;
+pub mod service_edit;
+pub mod subscriber;
+pub mod transfer;
+pub mod viewer;
+pub mod watch;
+
+use std::future::Future;
+
+use ratatui::widgets::ListState;
+use tokio::sync::mpsc::UnboundedSender;
 use tui_textarea::{CursorMove, Input, TextArea};
 
+use crate::api::client::NacosClient;
+use crate::api::config::config_md5;
+use crate::api::error::NacosError;
+use crate::api::watch::ConfigWatcher;
+use crate::app::clone::ConfigClone;
+use crate::app::create::NewConfigForm;
+use crate::app::delete::ConfigDelete;
+use crate::app::editor::ConfigEditor;
+use crate::app::external::ExternalEdit;
+use crate::app::filter::{ConfigQueryForm, ListFilter};
+use crate::app::history::ConfigHistoryBrowser;
+use crate::app::instance::InstanceBrowser;
+use crate::app::instance_edit::InstanceEdit;
+use crate::app::listener::ListenerBrowser;
+use crate::app::merge::MergeView;
+use crate::app::publish::PendingPublish;
+use crate::app::register::InstanceRegisterForm;
+use crate::app::search::ContentSearch;
+use crate::app::service::{ServiceItem, ServiceSort};
+use crate::app::service_edit::ServiceEditForm;
+use crate::app::subscriber::SubscriberBrowser;
+use crate::app::transfer::ConfigTransfer;
+use crate::app::viewer::ConfigViewer;
+use crate::config::ConfigTemplate;
+use crate::event::{ApiEvent, Event};
+use crate::highlight::Format;
+use crate::resp::config_page_resp::ConfigInfo;
+use crate::resp::namespace_list_resp::Namespace;
+use crate::validate::validate;
+
+// 配置列表每页条数，以及距离已加载末尾多少行时预取下一页
+const CONFIG_PAGE_SIZE: u32 = 50;
+const CONFIG_PREFETCH_ROWS: usize = 10;
+
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     Running,
@@ -20,13 +81,47 @@ pub enum CurrentScreen {
     NamespaceDelete,
     NamespaceAdd,
     NamespaceEdit,
+    ConfigView,
+    ConfigEdit,
+    ConfigPublishConfirm,
+    ConfigMerge,
+    ConfigHistory,
+    ConfigListeners,
+    ConfigDelete,
+    ConfigNew,
+    ConfigClone,
+    ConfigTransfer,
+    ConfigQuery,
+    ConfigSearch,
+    ServiceInstances,
+    ServiceSubscribers,
+    InstanceEdit,
+    InstanceRegister,
+    ServiceEdit,
 }
 
 pub struct ConfigItem {
+    pub id: String,
     pub data_id: String,
     pub group: String,
     pub format: String,
     pub content: String,
+    pub app_name: String,
+    pub marked: bool, // 空格标记，用于批量操作
+}
+
+impl From<ConfigInfo> for ConfigItem {
+    fn from(config: ConfigInfo) -> ConfigItem {
+        ConfigItem {
+            id: config.id,
+            data_id: config.dataId,
+            group: config.group,
+            format: config.type_.unwrap_or_else(|| "text".to_string()),
+            content: config.content.unwrap_or_default(),
+            app_name: config.appName.unwrap_or_default(),
+            marked: false,
+        }
+    }
 }
 
 pub struct NamespaceItem {
@@ -38,80 +133,762 @@ pub struct NamespaceItem {
     pub ns_typs: i32, //0:默认，2:用户创建
 }
 
+impl From<Namespace> for NamespaceItem {
+    fn from(ns: Namespace) -> NamespaceItem {
+        NamespaceItem {
+            ns_name: ns.namespaceShowName,
+            ns_id: ns.namespace,
+            ns_desc: ns.namespaceDesc.filter(|desc| !desc.is_empty()),
+            quota: ns.quota,
+            config_count: ns.configCount,
+            ns_typs: ns.type_,
+        }
+    }
+}
+
 pub struct App<'a> {
+    pub client: NacosClient,
+    event_tx: UnboundedSender<Event>,
+
+    // 进行中的请求(id, 描述)，非空时显示loading动画
+    pub in_flight: Vec<(u64, &'static str)>,
+    next_request_id: u64,
+    spinner_index: usize,
 
     pub state: AppState,
     pub current_screen: CurrentScreen,
     pub current_menu: CurrentMenu,
 
-    //config配置列表
+    // 最近一次请求失败/成功的提示，显示在hint栏，按任意键清除
+    pub error_message: Option<String>,
+    pub info_message: Option<String>,
+
+    //config配置列表，按页从服务端懒加载
     pub config_current_tab: usize,
     pub config_list: Vec<ConfigItem>,
+    pub config_list_state: ListState,
+    pub config_total: usize,
+    config_ns_id: Option<String>, // 当前列表所属的命名空间
+    config_page_no: u32,          // 已加载的页数
+    config_request: Option<u64>,  // 正在加载的分页请求
+    pub config_query: (String, String), // 服务端查询条件(dataId, group)，支持 * 通配符
+    pub config_query_form: Option<ConfigQueryForm>,
+    pub config_filter: Option<ListFilter>,
+    pub config_search: Option<ContentSearch>,
+    pub config_contents: Option<(String, Vec<ConfigItem>)>, // 内容搜索用的缓存(命名空间, 带内容的所有配置)
+    pub config_viewer: Option<ConfigViewer>,
+    config_watcher: Option<ConfigWatcher>, // 第一次打开查看器时启动
+    pub config_editor: Option<ConfigEditor>,
+    // 外部编辑器，内容写好临时文件后由主循环挂起终端去启动
+    pub external_edit: Option<ExternalEdit>,
+    pub config_publish: Option<PendingPublish>,
+    pub config_merge: Option<MergeView>,
+    pub config_history: Option<ConfigHistoryBrowser>,
+    pub config_listeners: Option<ListenerBrowser>,
+    pub config_delete: Option<ConfigDelete>,
+    pub config_new: Option<NewConfigForm>,
+    pub config_clone: Option<ConfigClone>,
+    pub config_transfer: Option<ConfigTransfer>,
+    pub config_templates: Vec<ConfigTemplate>, // 新建配置时可选的本地模板
 
-    // service服务列表
+    // service服务列表，按页加载，排序只在当前页内
+    pub service_current_tab: usize,
+    pub service_list: Vec<ServiceItem>,
+    pub service_list_state: ListState,
+    pub service_total: usize,
+    pub service_page_no: u32,
+    service_ns_id: Option<String>, // 当前列表所属的命名空间
+    service_request: Option<u64>,
+    pub service_sort: ServiceSort,
+    pub service_sort_desc: bool,
+    pub service_filter: Option<ListFilter>,
+    pub service_edit: Option<ServiceEditForm>,
+    pub service_instances: Option<InstanceBrowser>,
+    pub service_subscribers: Option<SubscriberBrowser>,
+    pub instance_edit: Option<InstanceEdit>,
+    pub instance_register: Option<InstanceRegisterForm>,
 
     // namespace命名空间列表
     pub namespace_list: Vec<NamespaceItem>,
     pub namespace_current_line: u8, 
+    pub namespace_filter: Option<ListFilter>,
 
     pub namespace_current_edit_index: usize,  
     pub ns_add_textarea_vec: Vec<TextArea<'a>>, //0-id, 1-name, 2-desc
+    ns_edit_id: Option<String>, // 正在编辑的命名空间，列表刷新后按 id 找回
 
 }
 
 impl App<'_> {
-    pub fn new() -> App<'static> {
+    pub fn new(client: NacosClient, event_tx: UnboundedSender<Event>) -> App<'static> {
 
         App {
+            client,
+            event_tx,
+
+            in_flight: vec![],
+            next_request_id: 0,
+            spinner_index: 0,
+
             state: AppState::Running,
             current_screen: CurrentScreen::Main,
             current_menu: CurrentMenu::Config,
 
-            config_list: vec![
-                ConfigItem {
-                    data_id: "example-data-id".to_string(),
-                    group: "example-group".to_string(),
-                    format: "json".to_string(),
-                    content: "example content".to_string(),
-                },
-                ConfigItem {
-                    data_id: "another-data-id".to_string(),
-                    group: "another-group".to_string(),
-                    format: "xml".to_string(),
-                    content: "another content".to_string(),
-                },
-            ],
+            error_message: None,
+            info_message: None,
+
+            config_list: vec![],
+            config_list_state: ListState::default(),
+            config_total: 0,
+            config_ns_id: None,
+            config_page_no: 0,
+            config_request: None,
+            config_query: (String::new(), String::new()),
+            config_query_form: None,
+            config_filter: None,
+            config_search: None,
+            config_contents: None,
+            config_viewer: None,
+            config_watcher: None,
+            config_editor: None,
+            external_edit: None,
+            config_publish: None,
+            config_merge: None,
+            config_history: None,
+            config_listeners: None,
+            config_delete: None,
+            config_new: None,
+            config_clone: None,
+            config_transfer: None,
+            config_templates: vec![],
             config_current_tab: 0,
 
-            namespace_list:  vec![
-                NamespaceItem { ns_name: "public".to_string(),
-                    ns_id: "".to_string(),
-                    ns_desc: None,
-                    quota: 200,
-                    config_count: 2,
-                    ns_typs: 0,
-                },
-                NamespaceItem {
-                    ns_name: "cc-test".to_string(),
-                    ns_id: "12a6c145-6632-4131-9f1d-a04e46d9e714".to_string(),
-                    ns_desc: Some("cc-test desc".to_string()),
-                    quota: 200,
-                    config_count: 2,
-                    ns_typs: 2,
-                },
-                NamespaceItem {
-                    ns_name: "cc-test2".to_string(),
-                    ns_id: "12a6c145-6632-4131-9f1d-a04e46d9e715".to_string(),
-                    ns_desc: Some("cc-test2 desc".to_string()),
-                    quota: 200,
-                    config_count: 0,
-                    ns_typs: 2,
-                },
-            ],
+            service_current_tab: 0,
+            service_list: vec![],
+            service_list_state: ListState::default(),
+            service_total: 0,
+            service_page_no: 1,
+            service_ns_id: None,
+            service_request: None,
+            service_sort: ServiceSort::Name,
+            service_sort_desc: false,
+            service_filter: None,
+            service_edit: None,
+            service_instances: None,
+            service_subscribers: None,
+            instance_edit: None,
+            instance_register: None,
+
+            namespace_list: vec![],
             namespace_current_line: 0,
+            namespace_filter: None,
 
             namespace_current_edit_index: 0,
             ns_add_textarea_vec: vec![],
+            ns_edit_id: None,
+        }
+    }
+
+    // 在后台执行请求，完成后结果以 Event::Api 回到事件循环
+    pub fn spawn<F>(&mut self, label: &'static str, future: F) -> u64
+    where
+        F: Future<Output = ApiEvent> + Send + 'static,
+    {
+        self.next_request_id += 1;
+        let id = self.next_request_id;
+        self.in_flight.push((id, label));
+
+        let tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            let event = future.await;
+            let _ = tx.send(Event::Api(id, event));
+        });
+        id
+    }
+
+    pub fn is_loading(&self, label: &str) -> bool {
+        self.in_flight.iter().any(|(_, l)| *l == label)
+    }
+
+    pub fn spinner(&self) -> &'static str {
+        SPINNER_FRAMES[self.spinner_index % SPINNER_FRAMES.len()]
+    }
+
+    pub fn on_tick(&mut self) {
+        if !self.in_flight.is_empty() {
+            self.spinner_index = self.spinner_index.wrapping_add(1);
+        }
+        self.instance_auto_refresh();
+    }
+
+    pub fn on_api_event(&mut self, id: u64, event: ApiEvent) {
+        self.in_flight.retain(|(i, _)| *i != id);
+
+        match event {
+            ApiEvent::NamespacesLoaded(Ok(resp)) => {
+                self.namespace_list = resp.data.into_iter().map(NamespaceItem::from).collect();
+                let len = self.namespace_list.len();
+                if self.namespace_current_line as usize >= len {
+                    self.namespace_current_line = len.saturating_sub(1) as u8;
+                }
+                if self.config_current_tab >= len {
+                    self.config_current_tab = len.saturating_sub(1);
+                }
+                if self.service_current_tab >= len {
+                    self.service_current_tab = len.saturating_sub(1);
+                }
+                self.namespace_filter_refresh();
+                // 命名空间变化后，配置列表跟随当前tab重新加载
+                if self.config_ns_id.as_deref() != self.current_tab_ns_id() {
+                    self.config_reload();
+                }
+                if self.current_menu == CurrentMenu::Service {
+                    self.service_open();
+                }
+            }
+            ApiEvent::NamespacesLoaded(Err(err)) => self.show_error("Load namespaces", err),
+            // 成功后关闭弹窗并刷新，失败时保留弹窗，方便修改后重新提交
+            ApiEvent::NamespaceCreated(Ok(true)) => {
+                if self.current_screen == CurrentScreen::NamespaceAdd {
+                    self.move_screen_ns_add_to_main();
+                }
+                self.ns_refresh();
+            }
+            ApiEvent::NamespaceCreated(Ok(false)) => {
+                self.error_message = Some("Create namespace: rejected by server".to_string());
+            }
+            ApiEvent::NamespaceCreated(Err(err)) => self.show_error("Create namespace", err),
+            ApiEvent::NamespaceUpdated(Ok(true)) => {
+                if self.current_screen == CurrentScreen::NamespaceEdit {
+                    self.move_screen_ns_add_to_main();
+                }
+                self.ns_refresh();
+            }
+            ApiEvent::NamespaceUpdated(Ok(false)) => {
+                self.error_message = Some("Update namespace: rejected by server".to_string());
+            }
+            ApiEvent::NamespaceUpdated(Err(err)) => self.show_error("Update namespace", err),
+            ApiEvent::NamespaceDeleted(Ok(true)) => self.ns_refresh(),
+            ApiEvent::NamespaceDeleted(Ok(false)) => {
+                self.error_message = Some("Delete namespace: rejected by server".to_string());
+            }
+            ApiEvent::NamespaceDeleted(Err(err)) => self.show_error("Delete namespace", err),
+            // 切换tab或刷新后，旧请求的结果直接丢弃
+            ApiEvent::ConfigsLoaded(_) if self.config_request != Some(id) => {}
+            ApiEvent::ConfigsLoaded(Ok(page)) => {
+                self.config_request = None;
+                self.config_page_no += 1;
+                self.config_total = page.totalCount.max(0) as usize;
+                self.config_list.extend(page.pageItems.into_iter().map(ConfigItem::from));
+                if self.config_list_state.selected().is_none() && !self.config_list.is_empty() {
+                    self.config_list_state.select(Some(0));
+                }
+                self.config_filter_refresh();
+            }
+            ApiEvent::ConfigsLoaded(Err(err)) => {
+                self.config_request = None;
+                self.show_error("Load configs", err);
+            }
+            ApiEvent::ConfigContentsLoaded(result) => self.on_config_contents_loaded(id, result),
+            ApiEvent::ConfigContentLoaded(result) => {
+                let Some(viewer) = self.config_viewer.as_mut().filter(|v| v.request == Some(id)) else {
+                    return;
+                };
+                viewer.request = None;
+                match result {
+                    Ok(content) => {
+                        // 从搜索结果打开时已经显示了缓存的内容，没有变化就保留匹配和滚动位置
+                        let changed = !viewer.loaded || viewer.content != content;
+                        if changed {
+                            viewer.set_content(&content);
+                        }
+                        let edit_on_load = std::mem::take(&mut viewer.edit_on_load);
+                        if changed {
+                            self.search_sync_viewer();
+                        }
+                        self.config_watch_viewer();
+                        if edit_on_load {
+                            self.config_open_editor();
+                        }
+                    }
+                    Err(err) => self.show_error("Load config content", err),
+                }
+            }
+            ApiEvent::ExternalContentLoaded(result) => {
+                let Some(edit) = self.external_edit.as_mut().filter(|e| e.request == Some(id)) else {
+                    return;
+                };
+                edit.request = None;
+                let result = match result {
+                    Ok(content) => edit.write(&content).map_err(|err| format!("Write temp file: {err}")),
+                    Err(err) => Err(format!("Load config content: {err}")),
+                };
+                if let Err(message) = result {
+                    self.external_edit = None;
+                    self.error_message = Some(message);
+                }
+            }
+            ApiEvent::ConfigPublished(result) => {
+                let Some(publish) = self.config_publish.as_mut().filter(|p| p.request == Some(id)) else {
+                    return;
+                };
+                publish.request = None;
+                match result {
+                    Ok(true) => self.on_config_published(),
+                    Ok(false) => self.error_message = Some("Publish config: rejected by server".to_string()),
+                    // 别人已经修改了配置，拉取服务端最新内容做三方对比
+                    Err(NacosError::Conflict(_)) => self.config_load_conflict(),
+                    Err(err) => self.show_error("Publish config", err),
+                }
+            }
+            ApiEvent::HistoryLoaded(_) | ApiEvent::HistoryCurrentLoaded(_) | ApiEvent::HistoryDetailLoaded(_) => {
+                self.on_history_event(id, event);
+            }
+            ApiEvent::ListenersLoaded(result) => self.on_listeners_loaded(id, result),
+            ApiEvent::ConfigsDeleted(results) => self.on_configs_deleted(id, results),
+            ApiEvent::NewConfigChecked(result) => self.on_new_config_checked(id, result),
+            event @ (ApiEvent::ClonePreviewLoaded(_) | ApiEvent::ConfigsCloned(_)) => self.on_clone_event(id, event),
+            ApiEvent::ConfigsExported(result) => self.on_configs_exported(id, result),
+            ApiEvent::ConfigsImported(result) => self.on_configs_imported(id, result),
+            ApiEvent::ServicesLoaded(page_no, result) => self.on_services_loaded(id, page_no, result),
+            ApiEvent::InstancesLoaded(result) => self.on_instances_loaded(id, result),
+            ApiEvent::SubscribersLoaded(page_no, result) => self.on_subscribers_loaded(id, page_no, result),
+            ApiEvent::InstanceUpdated(result) => self.on_instance_updated(id, result),
+            ApiEvent::InstanceRegistered(result) => self.on_instance_registered(id, result),
+            ApiEvent::ServiceDefinitionLoaded(result) => self.on_service_definition_loaded(id, result),
+            ApiEvent::ServiceSaved(result) => self.on_service_saved(id, result),
+            ApiEvent::ConflictContentLoaded(result) => {
+                let Some(publish) = self.config_publish.as_mut().filter(|p| p.request == Some(id)) else {
+                    return;
+                };
+                publish.request = None;
+                match result {
+                    Ok(server) => {
+                        self.config_merge = Some(MergeView::new(&publish.base, &publish.content, &server));
+                        self.current_screen = CurrentScreen::ConfigMerge;
+                    }
+                    Err(err) => self.show_error("Load server content", err),
+                }
+            }
+        }
+    }
+
+    pub fn current_tab_ns_id(&self) -> Option<&str> {
+        self.namespace_list
+            .get(self.config_current_tab)
+            .map(|ns| ns.ns_id.as_str())
+    }
+
+    // 清空当前配置列表，从第一页重新加载
+    pub fn config_reload(&mut self) {
+        self.config_list.clear();
+        self.config_list_state.select(None);
+        self.config_total = 0;
+        self.config_page_no = 0;
+        self.config_request = None;
+        self.config_ns_id = self.current_tab_ns_id().map(|id| id.to_string());
+        self.config_filter_refresh();
+        // 配置可能有增删，内容搜索的缓存也作废
+        self.config_contents = None;
+        self.config_load_next_page();
+    }
+
+    pub(super) fn config_load_next_page(&mut self) {
+        let Some(ns_id) = self.config_ns_id.clone() else {
+            return;
+        };
+        let all_loaded = self.config_page_no > 0 && self.config_list.len() >= self.config_total;
+        if self.config_request.is_some() || all_loaded {
+            return;
+        }
+
+        let client = self.client.clone();
+        let page_no = self.config_page_no + 1;
+        let (data_id, group) = self.config_query.clone();
+        let id = self.spawn("Loading configs", async move {
+            ApiEvent::ConfigsLoaded(client.search_configs(&ns_id, &data_id, &group, page_no, CONFIG_PAGE_SIZE).await)
+        });
+        self.config_request = Some(id);
+    }
+
+    pub fn config_next_tab(&mut self) {
+        if self.config_current_tab + 1 < self.namespace_list.len() {
+            self.config_current_tab += 1;
+            self.config_reload();
+        }
+    }
+
+    pub fn config_prev_tab(&mut self) {
+        if self.config_current_tab > 0 {
+            self.config_current_tab -= 1;
+            self.config_reload();
+        }
+    }
+
+    pub fn config_select_next(&mut self) {
+        if self.config_filter.is_some() {
+            self.config_filter_step(true, false);
+            return;
+        }
+        let len = self.config_list.len();
+        let next = self.config_list_state.selected().map_or(0, |i| i + 1);
+        if next < len {
+            self.config_list_state.select(Some(next));
+        }
+        // 接近已加载的末尾时预取下一页
+        if next + CONFIG_PREFETCH_ROWS >= len {
+            self.config_load_next_page();
+        }
+    }
+
+    pub fn config_select_prev(&mut self) {
+        if self.config_filter.is_some() {
+            self.config_filter_step(false, false);
+            return;
+        }
+        if let Some(i) = self.config_list_state.selected() {
+            self.config_list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    pub fn selected_config(&self) -> Option<&ConfigItem> {
+        self.config_list_state
+            .selected()
+            .and_then(|i| self.config_list.get(i))
+    }
+
+    // 批量操作的对象：有标记的行时为所有标记的配置，否则为选中的配置
+    pub fn marked_or_selected_configs(&self) -> Vec<&ConfigItem> {
+        let marked: Vec<&ConfigItem> = self.config_list.iter().filter(|c| c.marked).collect();
+        if marked.is_empty() {
+            self.selected_config().into_iter().collect()
+        } else {
+            marked
+        }
+    }
+
+    // 打开选中配置的内容查看器，内容从服务端重新获取
+    pub fn config_open_viewer(&mut self) {
+        let (Some(ns_id), Some(config)) = (self.config_ns_id.clone(), self.selected_config()) else {
+            return;
+        };
+        let mut viewer = ConfigViewer::new(&ns_id, &config.data_id, &config.group, &config.format);
+        self.current_screen = CurrentScreen::ConfigView;
+
+        let client = self.client.clone();
+        let (data_id, group) = (viewer.data_id.clone(), viewer.group.clone());
+        let id = self.spawn("Loading config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            ApiEvent::ConfigContentLoaded(client.get_config(tenant, &data_id, &group).await)
+        });
+        viewer.request = Some(id);
+        self.config_viewer = Some(viewer);
+    }
+
+    // 从内容搜索打开的查看器，关闭后回到搜索结果
+    pub fn config_close_viewer(&mut self) {
+        self.config_unwatch_viewer();
+        self.config_viewer = None;
+        self.current_screen = if self.config_search.is_some() {
+            CurrentScreen::ConfigSearch
+        } else {
+            CurrentScreen::Main
+        };
+    }
+
+    // 在列表上按 e 时先加载内容，加载完成后再进入编辑器
+    pub fn config_edit_selected(&mut self) {
+        self.config_open_viewer();
+        if let Some(viewer) = self.config_viewer.as_mut() {
+            viewer.edit_on_load = true;
+        }
+    }
+
+    // 用查看器里已加载的内容打开编辑器
+    pub fn config_open_editor(&mut self) {
+        let Some(viewer) = self.config_viewer.as_ref().filter(|v| v.loaded) else {
+            return;
+        };
+        self.config_editor = Some(ConfigEditor::new(
+            &viewer.ns_id,
+            &viewer.data_id,
+            &viewer.group,
+            &viewer.type_,
+            &viewer.content,
+        ));
+        self.current_screen = CurrentScreen::ConfigEdit;
+    }
+
+    pub fn config_close_editor(&mut self) {
+        self.config_editor = None;
+        self.current_screen = if self.config_viewer.is_some() {
+            CurrentScreen::ConfigView
+        } else {
+            CurrentScreen::Main
+        };
+    }
+
+    // 校验通过且内容有变化时，先看差异再确认发布
+    pub fn config_editor_save(&mut self) {
+        let Some(editor) = self.config_editor.as_mut() else {
+            return;
+        };
+        if editor.meta.is_some() && editor.content().trim().is_empty() {
+            self.error_message = Some("Content is required".to_string());
+            return;
+        }
+        if !editor.is_modified() {
+            self.info_message = Some("Nothing changed, not published".to_string());
+            return;
+        }
+        if !editor.validate() {
+            return;
+        }
+        let mut publish = PendingPublish::new(
+            &editor.ns_id,
+            &editor.data_id,
+            &editor.group,
+            &editor.type_,
+            &editor.original,
+            &editor.content(),
+        );
+        publish.create = editor.meta.is_some();
+        publish.meta = editor.meta.clone();
+        self.config_publish = Some(publish);
+        self.current_screen = CurrentScreen::ConfigPublishConfirm;
+    }
+
+    fn on_config_published(&mut self) {
+        let Some(publish) = self.config_publish.take() else {
+            return;
+        };
+        self.on_published(&publish.ns_id, &publish.data_id, &publish.group, &publish.content);
+        // 从编辑器发布的，发布成功后编辑器也一起关闭
+        self.config_editor = None;
+        self.config_close_publish();
+        // 回滚后刷新历史，能看到新产生的记录
+        if self.config_history.is_some() {
+            self.history_reload();
+        }
+        // 新建的配置要重新加载列表才能看到
+        if publish.meta.is_some() {
+            self.config_reload();
+        }
+    }
+
+    // 发布成功后同步查看器和列表里缓存的内容，不同命名空间下可能有同名的配置
+    fn on_published(&mut self, ns_id: &str, data_id: &str, group: &str, content: &str) {
+        if let Some(viewer) = self
+            .config_viewer
+            .as_mut()
+            .filter(|v| v.ns_id == ns_id && v.data_id == data_id && v.group == group)
+        {
+            viewer.set_content(content);
+            // 自己发布的不算外部变化，用新内容的 md5 继续监听
+            viewer.remote_change = None;
+            self.config_watch_viewer();
+        }
+        if self.config_ns_id.as_deref() == Some(ns_id)
+            && let Some(config) = self
+                .config_list
+                .iter_mut()
+                .find(|c| c.data_id == data_id && c.group == group)
+        {
+            config.content = content.to_string();
+        }
+        if let Some(config) = self
+            .config_contents
+            .iter_mut()
+            .filter(|(contents_ns, _)| contents_ns == ns_id)
+            .flat_map(|(_, configs)| configs.iter_mut())
+            .find(|c| c.data_id == data_id && c.group == group)
+        {
+            config.content = content.to_string();
+        }
+        self.info_message = Some(format!("Published {data_id}"));
+    }
+
+    // 拉取最新内容后交给主循环启动外部编辑器；查看器打开时编辑查看器里的配置
+    pub fn config_edit_external(&mut self) {
+        if self.external_edit.is_some() {
+            return;
+        }
+        let mut edit = match (self.config_viewer.as_ref(), self.config_ns_id.as_ref(), self.selected_config()) {
+            (Some(viewer), _, _) => ExternalEdit::new(&viewer.ns_id, &viewer.data_id, &viewer.group, &viewer.type_),
+            (None, Some(ns_id), Some(config)) => ExternalEdit::new(ns_id, &config.data_id, &config.group, &config.format),
+            _ => return,
+        };
+
+        let client = self.client.clone();
+        let (ns_id, data_id, group) = (edit.ns_id.clone(), edit.data_id.clone(), edit.group.clone());
+        let id = self.spawn("Loading config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            ApiEvent::ExternalContentLoaded(client.get_config(tenant, &data_id, &group).await)
+        });
+        edit.request = Some(id);
+        self.external_edit = Some(edit);
+    }
+
+    // 临时文件写好后交给主循环启动编辑器
+    pub fn take_external_edit(&mut self) -> Option<ExternalEdit> {
+        if self.external_edit.as_ref().is_some_and(|e| e.written) {
+            self.external_edit.take()
+        } else {
+            None
+        }
+    }
+
+    // 外部编辑器退出后：没有修改或校验失败就不发布，否则等待确认
+    pub fn on_external_edit_done(&mut self, edit: ExternalEdit, result: std::io::Result<String>) {
+        let content = match result {
+            Ok(content) => content,
+            Err(err) => {
+                self.error_message = Some(format!("Run editor: {err}"));
+                return;
+            }
+        };
+        if content == edit.original {
+            self.info_message = Some("Nothing changed, not published".to_string());
+            return;
+        }
+        let format = Format::detect(&edit.type_, &edit.data_id);
+        if let Err(err) = validate(format, &content) {
+            self.error_message = Some(format!("{format:?} {err}, not published"));
+            return;
+        }
+
+        self.config_publish = Some(PendingPublish::new(
+            &edit.ns_id,
+            &edit.data_id,
+            &edit.group,
+            &edit.type_,
+            &edit.original,
+            &content,
+        ));
+        self.current_screen = CurrentScreen::ConfigPublishConfirm;
+    }
+
+    pub fn config_confirm_publish(&mut self) {
+        let Some(publish) = self.config_publish.as_ref().filter(|p| p.request.is_none()) else {
+            return;
+        };
+        let client = self.client.clone();
+        let (ns_id, data_id, group) = (publish.ns_id.clone(), publish.data_id.clone(), publish.group.clone());
+        let type_ = if publish.type_.is_empty() { "text".to_string() } else { publish.type_.clone() };
+        let content = publish.content.clone();
+        let cas_md5 = (!publish.create).then(|| config_md5(&publish.base));
+        let meta = publish.meta.clone();
+        let id = self.spawn("Publishing config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            let result = match meta {
+                Some(meta) => client.create_config(tenant, &data_id, &group, &content, &type_, &meta).await,
+                None => client.publish_config(tenant, &data_id, &group, &content, &type_, cas_md5.as_deref()).await,
+            };
+            ApiEvent::ConfigPublished(result)
+        });
+        if let Some(publish) = self.config_publish.as_mut() {
+            publish.request = Some(id);
+        }
+    }
+
+    fn config_load_conflict(&mut self) {
+        let Some(publish) = self.config_publish.as_ref() else {
+            return;
+        };
+        let client = self.client.clone();
+        let (ns_id, data_id, group) = (publish.ns_id.clone(), publish.data_id.clone(), publish.group.clone());
+        let id = self.spawn("Loading config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            ApiEvent::ConflictContentLoaded(client.get_config(tenant, &data_id, &group).await)
+        });
+        if let Some(publish) = self.config_publish.as_mut() {
+            publish.request = Some(id);
+        }
+    }
+
+    // 在编辑器里修改自动合并的结果，之后以服务端当前版本为基准发布
+    pub fn config_merge_edit(&mut self) {
+        let (Some(merge), Some(publish)) = (self.config_merge.take(), self.config_publish.take()) else {
+            return;
+        };
+        let mut editor = ConfigEditor::new(&publish.ns_id, &publish.data_id, &publish.group, &publish.type_, &merge.merged);
+        editor.original = merge.server.clone();
+        if merge.conflicts > 0 {
+            editor.merge_pending = true;
+            self.info_message = Some(format!("{} conflict(s), resolve the <<<<<<< markers before publishing", merge.conflicts));
+        }
+        self.config_editor = Some(editor);
+        self.current_screen = CurrentScreen::ConfigEdit;
+    }
+
+    // 用本地内容覆盖服务端当前版本，仍然先看差异再确认
+    pub fn config_merge_keep_mine(&mut self) {
+        let (Some(merge), Some(publish)) = (self.config_merge.take(), self.config_publish.as_mut()) else {
+            return;
+        };
+        *publish = PendingPublish::new(
+            &publish.ns_id,
+            &publish.data_id,
+            &publish.group,
+            &publish.type_,
+            &merge.server,
+            &merge.mine,
+        );
+        self.current_screen = CurrentScreen::ConfigPublishConfirm;
+    }
+
+    pub fn config_merge_abort(&mut self) {
+        self.config_merge = None;
+        self.config_cancel_publish();
+    }
+
+    // 取消发布：从编辑器来的回到编辑器继续修改，外部编辑的修改直接丢弃
+    pub fn config_cancel_publish(&mut self) {
+        // 发布请求已经发出去了，等结果回来再说，否则结果会被当成过期的丢掉
+        if self.config_publish.as_ref().is_some_and(|p| p.request.is_some()) {
+            return;
+        }
+        if let Some(publish) = self.config_publish.take()
+            && self.config_editor.is_none()
+        {
+            self.info_message = Some(format!("Discarded changes to {}", publish.data_id));
+        }
+        self.config_close_publish();
+    }
+
+    fn config_close_publish(&mut self) {
+        self.current_screen = if self.config_editor.is_some() {
+            CurrentScreen::ConfigEdit
+        } else if self.config_history.is_some() {
+            CurrentScreen::ConfigHistory
+        } else if self.config_viewer.is_some() {
+            CurrentScreen::ConfigView
+        } else {
+            CurrentScreen::Main
+        };
+    }
+
+    // 过滤后选中行不可见时视为没有选中，避免对看不到的命名空间进行操作
+    pub fn selected_namespace(&self) -> Option<&NamespaceItem> {
+        if self.namespace_filter.as_ref().is_some_and(|f| f.list_state.selected().is_none()) {
+            return None;
+        }
+        self.namespace_list.get(self.namespace_current_line as usize)
+    }
+
+    pub fn namespace_select_next(&mut self) {
+        if self.namespace_filter.is_some() {
+            self.namespace_filter_step(true, false);
+        } else if (self.namespace_current_line as usize) + 1 < self.namespace_list.len() {
+            self.namespace_current_line += 1;
+        }
+    }
+
+    pub fn namespace_select_prev(&mut self) {
+        if self.namespace_filter.is_some() {
+            self.namespace_filter_step(false, false);
+        } else {
+            self.namespace_current_line = self.namespace_current_line.saturating_sub(1);
         }
     }
 
@@ -123,30 +900,38 @@ impl App<'_> {
     pub fn move_screen_main_to_ns_add(&mut self) {
         self.current_screen = CurrentScreen::NamespaceAdd;
 
-        let mut id_textarea = TextArea::default();
-        let mut name_textarea = TextArea::default();
-        let mut desc_textarea = TextArea::default();
+        let id_textarea = TextArea::default();
+        let name_textarea = TextArea::default();
+        let desc_textarea = TextArea::default();
 
         self.ns_add_textarea_vec = vec![id_textarea, name_textarea, desc_textarea];
     }
 
     pub fn move_screen_main_to_ns_edit(&mut self) {
-        self.current_screen = CurrentScreen::NamespaceEdit;
-
-        let ns_item = &self.namespace_list[self.namespace_current_line as usize];
-
-        let mut name = vec![ns_item.ns_name.clone()];
+        let Some(ns_item) = self.selected_namespace() else {
+            return;
+        };
+        if ns_item.ns_typs == 0 {
+            self.error_message = Some("The public namespace cannot be edited".to_string());
+            return;
+        }
+        let name = vec![ns_item.ns_name.clone()];
         let mut name_textarea = TextArea::new(name);
         name_textarea.move_cursor(CursorMove::End);
 
-        let mut desc = vec![ns_item.ns_desc.clone().unwrap_or_default()]; 
+        let desc = vec![ns_item.ns_desc.clone().unwrap_or_default()]; 
         let mut desc_textarea = TextArea::new(desc);
         desc_textarea.move_cursor(CursorMove::End);
 
+        self.ns_edit_id = Some(ns_item.ns_id.clone());
+        self.current_screen = CurrentScreen::NamespaceEdit;
         self.ns_add_textarea_vec = vec![name_textarea, desc_textarea];
     }
 
     pub fn move_screen_main_to_ns_delete(&mut self) {
+        if self.selected_namespace().is_none() {
+            return;
+        }
         self.current_screen = CurrentScreen::NamespaceDelete;
     }
 
@@ -156,44 +941,87 @@ impl App<'_> {
         self.ns_add_textarea_vec.clear();
     }
 
+    pub fn ns_refresh(&mut self) {
+        if self.is_loading("Loading namespaces") {
+            return;
+        }
+        let client = self.client.clone();
+        self.spawn("Loading namespaces", async move {
+            ApiEvent::NamespacesLoaded(client.list_namespaces().await)
+        });
+    }
+
     pub fn ns_add_submit(&mut self) {
+        if self.is_loading("Creating namespace") {
+            return;
+        }
         //get info from ns_add_textarea_vec
         let id = self.ns_add_textarea_vec[0].lines().join("\n");
         let name = self.ns_add_textarea_vec[1].lines().join("\n");
         let desc = self.ns_add_textarea_vec[2].lines().join("\n");
-        let new_ns = NamespaceItem {
-            ns_name: name,
-            ns_id: id,
-            ns_desc: if desc.is_empty() { None } else { Some(desc) },
-            quota: 200, // default quota
-            config_count: 0, // default config count
-            ns_typs: 2, // user created type
-        };
-        self.namespace_list.push(new_ns);
-        self.move_screen_ns_add_to_main();
+        if name.trim().is_empty() {
+            self.error_message = Some("ns_name is required".to_string());
+            return;
+        }
+
+        let client = self.client.clone();
+        self.spawn("Creating namespace", async move {
+            ApiEvent::NamespaceCreated(client.create_namespace(&id, &name, &desc).await)
+        });
     }
 
     pub fn ns_edit_submit(&mut self) {
+        if self.is_loading("Updating namespace") {
+            return;
+        }
         //get info from ns_add_textarea_vec
         let name = self.ns_add_textarea_vec[0].lines().join("\n");
         let desc = self.ns_add_textarea_vec[1].lines().join("\n");
+        if name.trim().is_empty() {
+            self.error_message = Some("ns_name is required".to_string());
+            return;
+        }
 
-        let ns_item = &mut self.namespace_list[self.namespace_current_line as usize];
-        ns_item.ns_name = name;
-        ns_item.ns_desc = if desc.is_empty() { None } else { Some(desc) };
-
-        self.move_screen_ns_add_to_main();
+        // 弹窗打开期间刷新过列表，命名空间可能已经不在了
+        let Some(ns_id) = self
+            .ns_edit_id
+            .clone()
+            .filter(|id| self.namespace_list.iter().any(|ns| ns.ns_id == *id))
+        else {
+            self.error_message = Some("The namespace no longer exists, refresh and try again".to_string());
+            return;
+        };
+        let client = self.client.clone();
+        self.spawn("Updating namespace", async move {
+            ApiEvent::NamespaceUpdated(client.update_namespace(&ns_id, &name, &desc).await)
+        });
     }
 
     pub fn ns_delete(&mut self) {
-        // 移除当前选项
-        if self.namespace_current_line < self.namespace_list.len() as u8 {
-            self.namespace_list.remove(self.namespace_current_line as usize);
-            if self.namespace_current_line > 0 {
-                self.namespace_current_line -= 1;
-            }
-        }
         self.current_screen = CurrentScreen::Main;
+        let Some(ns_item) = self.selected_namespace() else {
+            return;
+        };
+        // public 命名空间由 Nacos 内置，不能删除
+        if ns_item.ns_typs == 0 {
+            self.error_message = Some("The public namespace cannot be deleted".to_string());
+            return;
+        }
+
+        let ns_id = ns_item.ns_id.clone();
+        let client = self.client.clone();
+        self.spawn("Deleting namespace", async move {
+            ApiEvent::NamespaceDeleted(client.delete_namespace(&ns_id).await)
+        });
     }
 
-}
\ No newline at end of file
+    pub fn show_error(&mut self, action: &str, err: NacosError) {
+        let message = if err.needs_login() {
+            format!("{action}: {err}, check username/password in config")
+        } else {
+            format!("{action}: {err}")
+        };
+        self.error_message = Some(message);
+    }
+
+}
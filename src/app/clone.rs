@@ -0,0 +1,407 @@
+use ratatui::widgets::ListState;
+use tui_textarea::{Input, TextArea};
+
+use crate::api::config::{config_md5, ConfigMeta, ConflictPolicy};
+use crate::api::error::NacosError;
+use crate::app::create::is_valid_name;
+use crate::app::diff::DiffView;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+
+// 预览中每个配置将要执行的操作
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CloneAction {
+    Create,
+    Overwrite,
+    Unchanged, // 目标内容相同，不需要复制
+    Skip,
+    Conflict, // 策略为 abort 时目标已存在
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CloneField {
+    Namespace,
+    Group,
+    Policy,
+}
+
+pub struct CloneItem {
+    pub data_id: String,
+    pub group: String, // 源配置的 group
+    pub type_: String,
+    pub app_name: String,
+    pub content: String,
+    pub target: Option<String>, // 目标中现有的内容，None 表示不存在
+    pub result: Option<Result<(), String>>,
+}
+
+impl CloneItem {
+    pub fn action(&self, policy: ConflictPolicy) -> CloneAction {
+        match (&self.target, policy) {
+            (None, _) => CloneAction::Create,
+            (Some(target), _) if *target == self.content => CloneAction::Unchanged,
+            (Some(_), ConflictPolicy::Skip) => CloneAction::Skip,
+            (Some(_), ConflictPolicy::Overwrite) => CloneAction::Overwrite,
+            (Some(_), ConflictPolicy::Abort) => CloneAction::Conflict,
+        }
+    }
+}
+
+// 把当前命名空间的配置复制到另一个命名空间（可换 group）
+// 先选目标，再预览每个配置是新建还是覆盖，确认后逐个发布
+pub struct ConfigClone {
+    pub src_ns: String,
+    pub target_ns: String, // 目标命名空间 id，刷新命名空间列表不影响选中的目标
+    pub group: TextArea<'static>, // 为空时沿用源配置的 group
+    pub policy: ConflictPolicy,
+    pub focus: CloneField,
+    pub items: Vec<CloneItem>,
+    pub previewed: bool, // items 中的内容已加载，处于预览阶段
+    pub list_state: ListState,
+    pub diff: Option<DiffView>,
+    pub request: Option<u64>,
+    pub finished: bool,
+}
+
+impl ConfigClone {
+    pub fn target_group<'a>(&'a self, item: &'a CloneItem) -> &'a str {
+        match self.group.lines()[0].trim() {
+            "" => &item.group,
+            group => group,
+        }
+    }
+
+    pub fn count(&self, action: CloneAction) -> usize {
+        self.items.iter().filter(|i| i.action(self.policy) == action).count()
+    }
+
+    pub fn selected(&self) -> Option<&CloneItem> {
+        self.list_state.selected().and_then(|i| self.items.get(i))
+    }
+}
+
+impl App<'_> {
+    pub fn config_clone_start(&mut self) {
+        let Some(src_ns) = self.config_ns_id.clone() else {
+            return;
+        };
+        let items: Vec<CloneItem> = self
+            .marked_or_selected_configs()
+            .into_iter()
+            .map(|c| CloneItem {
+                data_id: c.data_id.clone(),
+                group: c.group.clone(),
+                type_: c.format.clone(),
+                app_name: c.app_name.clone(),
+                content: String::new(),
+                target: None,
+                result: None,
+            })
+            .collect();
+        if items.is_empty() {
+            return;
+        }
+        // 默认复制到当前 tab 的下一个命名空间
+        let len = self.namespace_list.len().max(1);
+        let Some(target_ns) = self.namespace_list.get((self.config_current_tab + 1) % len).map(|ns| ns.ns_id.clone())
+        else {
+            return;
+        };
+        self.config_clone = Some(ConfigClone {
+            src_ns,
+            target_ns,
+            group: TextArea::default(),
+            policy: ConflictPolicy::Skip,
+            focus: CloneField::Namespace,
+            items,
+            previewed: false,
+            list_state: ListState::default(),
+            diff: None,
+            request: None,
+            finished: false,
+        });
+        self.current_screen = CurrentScreen::ConfigClone;
+    }
+
+    pub fn config_clone_close(&mut self) {
+        if self.config_clone.as_ref().is_some_and(|c| c.request.is_some()) {
+            return;
+        }
+        self.config_clone = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn clone_focus(&mut self, forward: bool) {
+        if let Some(clone) = self.config_clone.as_mut() {
+            clone.focus = match (clone.focus, forward) {
+                (CloneField::Namespace, true) | (CloneField::Policy, false) => CloneField::Group,
+                (CloneField::Group, true) | (CloneField::Namespace, false) => CloneField::Policy,
+                (CloneField::Policy, true) | (CloneField::Group, false) => CloneField::Namespace,
+            };
+        }
+    }
+
+    // 左右切换目标命名空间或冲突策略
+    pub fn clone_cycle(&mut self, forward: bool) {
+        let len = self.namespace_list.len();
+        let Some(clone) = self.config_clone.as_mut() else {
+            return;
+        };
+        match clone.focus {
+            CloneField::Namespace if len > 0 => {
+                // 目标已经不在列表里时从头开始
+                let next = match self.namespace_list.iter().position(|ns| ns.ns_id == clone.target_ns) {
+                    Some(i) if forward => (i + 1) % len,
+                    Some(i) => (i + len - 1) % len,
+                    None => 0,
+                };
+                clone.target_ns = self.namespace_list[next].ns_id.clone();
+            }
+            CloneField::Policy => clone.policy = clone.policy.next(),
+            _ => {}
+        }
+    }
+
+    pub fn clone_cycle_policy(&mut self) {
+        if let Some(clone) = self.config_clone.as_mut() {
+            clone.policy = clone.policy.next();
+        }
+    }
+
+    pub fn clone_input(&mut self, input: Input) {
+        if let Some(clone) = self.config_clone.as_mut()
+            && clone.focus == CloneField::Group
+        {
+            clone.group.input(input);
+        }
+    }
+
+    // 拉取源配置和目标中同名配置的内容，得到预览
+    pub fn clone_preview(&mut self) {
+        let Some(clone) = self.config_clone.as_ref().filter(|c| c.request.is_none()) else {
+            return;
+        };
+        if !self.namespace_list.iter().any(|ns| ns.ns_id == clone.target_ns) {
+            self.error_message = Some("The target namespace no longer exists, choose another one".to_string());
+            return;
+        }
+        let target_ns = clone.target_ns.clone();
+        let group = clone.group.lines()[0].trim();
+        if !group.is_empty() && !is_valid_name(group) {
+            self.error_message = Some("group may only contain letters, digits and . : _ -".to_string());
+            return;
+        }
+        if target_ns == clone.src_ns && clone.items.iter().any(|item| clone.target_group(item) == item.group) {
+            self.error_message = Some("Target is the same as the source, choose another namespace or group".to_string());
+            return;
+        }
+
+        let client = self.client.clone();
+        let src_ns = clone.src_ns.clone();
+        let pairs: Vec<(String, String, String)> = clone
+            .items
+            .iter()
+            .map(|item| (item.data_id.clone(), item.group.clone(), clone.target_group(item).to_string()))
+            .collect();
+        let id = self.spawn("Loading clone preview", async move {
+            let src = Some(src_ns.as_str()).filter(|id| !id.is_empty());
+            let target = Some(target_ns.as_str()).filter(|id| !id.is_empty());
+            let mut contents = vec![];
+            for (data_id, group, target_group) in pairs {
+                let content = match client.get_config(src, &data_id, &group).await {
+                    Ok(content) => content,
+                    Err(err) => return ApiEvent::ClonePreviewLoaded(Err(err)),
+                };
+                let existing = match client.get_config(target, &data_id, &target_group).await {
+                    Ok(existing) => Some(existing),
+                    Err(NacosError::NotFound(_)) => None,
+                    Err(err) => return ApiEvent::ClonePreviewLoaded(Err(err)),
+                };
+                contents.push((content, existing));
+            }
+            ApiEvent::ClonePreviewLoaded(Ok(contents))
+        });
+        if let Some(clone) = self.config_clone.as_mut() {
+            clone.request = Some(id);
+        }
+    }
+
+    // 回到第一步修改目标
+    pub fn clone_back(&mut self) {
+        if let Some(clone) = self.config_clone.as_mut().filter(|c| c.request.is_none()) {
+            clone.previewed = false;
+            clone.diff = None;
+        }
+    }
+
+    pub fn clone_select_next(&mut self) {
+        if let Some(clone) = self.config_clone.as_mut() {
+            let next = clone.list_state.selected().map_or(0, |i| i + 1);
+            if next < clone.items.len() {
+                clone.list_state.select(Some(next));
+            }
+        }
+    }
+
+    pub fn clone_select_prev(&mut self) {
+        if let Some(clone) = self.config_clone.as_mut()
+            && let Some(i) = clone.list_state.selected()
+        {
+            clone.list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    // 对比目标中现有的内容和将要复制过去的内容
+    pub fn clone_show_diff(&mut self) {
+        let Some(clone) = self.config_clone.as_mut() else {
+            return;
+        };
+        let Some(item) = clone.selected() else {
+            return;
+        };
+        match &item.target {
+            Some(target) if *target != item.content => {
+                clone.diff = Some(DiffView::new("target", target, "source", &item.content));
+            }
+            Some(_) => self.info_message = Some("Target content is the same".to_string()),
+            None => self.info_message = Some("Target does not exist, it will be created".to_string()),
+        }
+    }
+
+    pub fn clone_close_diff(&mut self) {
+        if let Some(clone) = self.config_clone.as_mut() {
+            clone.diff = None;
+        }
+    }
+
+    // 按预览逐个发布；覆盖时带上预览时目标内容的 md5，期间被别人改过就不覆盖
+    pub fn clone_confirm(&mut self) {
+        let Some(clone) = self.config_clone.as_ref().filter(|c| c.previewed && !c.finished && c.request.is_none())
+        else {
+            return;
+        };
+        let conflicts = clone.count(CloneAction::Conflict);
+        if conflicts > 0 {
+            self.error_message = Some(format!(
+                "{conflicts} config(s) already exist in the target, aborted (press p to change the policy)"
+            ));
+            return;
+        }
+        let target_ns = clone.target_ns.clone();
+
+        let client = self.client.clone();
+        let jobs: Vec<_> = clone
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item.action(clone.policy),
+                    item.data_id.clone(),
+                    clone.target_group(item).to_string(),
+                    item.type_.clone(),
+                    item.app_name.clone(),
+                    item.content.clone(),
+                    item.target.as_deref().map(config_md5),
+                )
+            })
+            .collect();
+        let id = self.spawn("Cloning configs", async move {
+            let tenant = Some(target_ns.as_str()).filter(|id| !id.is_empty());
+            let mut results = vec![];
+            for (action, data_id, group, type_, app_name, content, target_md5) in jobs {
+                let type_ = if type_.is_empty() { "text".to_string() } else { type_ };
+                let result = match action {
+                    // create_config 会直接覆盖同名配置，预览之后被别人建了就不复制
+                    CloneAction::Create => match client.get_config(tenant, &data_id, &group).await {
+                        Ok(_) => Err(NacosError::Conflict("created in the target".to_string())),
+                        Err(NacosError::NotFound(_)) => {
+                            let meta = ConfigMeta {
+                                app_name,
+                                ..ConfigMeta::default()
+                            };
+                            client.create_config(tenant, &data_id, &group, &content, &type_, &meta).await
+                        }
+                        Err(err) => Err(err),
+                    },
+                    CloneAction::Overwrite => {
+                        client.publish_config(tenant, &data_id, &group, &content, &type_, target_md5.as_deref()).await
+                    }
+                    _ => Ok(true),
+                };
+                results.push(match result {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err("rejected by server".to_string()),
+                    Err(NacosError::Conflict(_)) => Err("changed in the target since the preview".to_string()),
+                    Err(err) => Err(err.to_string()),
+                });
+            }
+            ApiEvent::ConfigsCloned(results)
+        });
+        if let Some(clone) = self.config_clone.as_mut() {
+            clone.request = Some(id);
+        }
+    }
+
+    pub(super) fn on_clone_event(&mut self, id: u64, event: ApiEvent) {
+        let Some(clone) = self.config_clone.as_mut().filter(|c| c.request == Some(id)) else {
+            return;
+        };
+        clone.request = None;
+        match event {
+            ApiEvent::ClonePreviewLoaded(Ok(contents)) => {
+                for (item, (content, target)) in clone.items.iter_mut().zip(contents) {
+                    item.content = content;
+                    item.target = target;
+                }
+                clone.previewed = true;
+                clone.list_state.select(Some(0));
+            }
+            ApiEvent::ClonePreviewLoaded(Err(err)) => self.show_error("Load clone preview", err),
+            ApiEvent::ConfigsCloned(results) => {
+                let cloned = results.iter().filter(|r| r.is_ok()).count();
+                let failed = results.len() - cloned;
+                for (item, result) in clone.items.iter_mut().zip(results) {
+                    item.result = Some(result);
+                }
+                clone.finished = true;
+                if failed == 0 {
+                    self.info_message = Some("Clone finished".to_string());
+                } else {
+                    self.error_message = Some(format!("Clone finished, {failed} config(s) failed"));
+                }
+                // 复制到当前 tab 的命名空间时刷新列表
+                if self.config_ns_id.as_ref() == Some(&clone.target_ns) {
+                    self.config_reload();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_action() {
+        let mut item = CloneItem {
+            data_id: "a.yaml".to_string(),
+            group: "DEFAULT_GROUP".to_string(),
+            type_: "yaml".to_string(),
+            app_name: String::new(),
+            content: "a: 1\n".to_string(),
+            target: None,
+            result: None,
+        };
+        assert_eq!(item.action(ConflictPolicy::Abort), CloneAction::Create);
+
+        item.target = Some("a: 1\n".to_string());
+        assert_eq!(item.action(ConflictPolicy::Abort), CloneAction::Unchanged);
+
+        item.target = Some("a: 2\n".to_string());
+        assert_eq!(item.action(ConflictPolicy::Skip), CloneAction::Skip);
+        assert_eq!(item.action(ConflictPolicy::Overwrite), CloneAction::Overwrite);
+        assert_eq!(item.action(ConflictPolicy::Abort), CloneAction::Conflict);
+    }
+}
@@ -0,0 +1,258 @@
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::api::config::ConfigMeta;
+use crate::api::error::NacosError;
+use crate::app::editor::ConfigEditor;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+
+// Nacos 控制台可选的配置类型
+pub const CONFIG_TYPES: [&str; 6] = ["text", "json", "xml", "yaml", "html", "properties"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NewConfigField {
+    DataId,
+    Group,
+    Format,
+    Template,
+    AppName,
+    Tags,
+    Desc,
+}
+
+impl NewConfigField {
+    pub const ALL: [NewConfigField; 7] = [
+        NewConfigField::DataId,
+        NewConfigField::Group,
+        NewConfigField::Format,
+        NewConfigField::Template,
+        NewConfigField::AppName,
+        NewConfigField::Tags,
+        NewConfigField::Desc,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NewConfigField::DataId => "data_id",
+            NewConfigField::Group => "group",
+            NewConfigField::Format => "format",
+            NewConfigField::Template => "template",
+            NewConfigField::AppName => "app_name",
+            NewConfigField::Tags => "tags(comma separated)",
+            NewConfigField::Desc => "desc",
+        }
+    }
+
+    // 格式和模板用左右键选择，其余是文本输入框
+    pub fn is_picker(self) -> bool {
+        matches!(self, NewConfigField::Format | NewConfigField::Template)
+    }
+}
+
+// 新建配置弹窗，填完后进入编辑器编写内容
+pub struct NewConfigForm {
+    pub ns_id: String,
+    pub data_id: TextArea<'static>,
+    pub group: TextArea<'static>,
+    pub app_name: TextArea<'static>,
+    pub tags: TextArea<'static>,
+    pub desc: TextArea<'static>,
+    pub format: usize,           // CONFIG_TYPES 的下标
+    pub template: Option<usize>, // App::config_templates 的下标，None 表示空白内容
+    pub focus: NewConfigField,
+    format_chosen: bool, // 手动选过格式后不再按 dataId 扩展名推断
+    request: Option<u64>, // 检查配置是否已存在
+}
+
+impl NewConfigForm {
+    fn new(ns_id: &str) -> NewConfigForm {
+        let mut group = TextArea::new(vec!["DEFAULT_GROUP".to_string()]);
+        group.move_cursor(CursorMove::End);
+        NewConfigForm {
+            ns_id: ns_id.to_string(),
+            data_id: TextArea::default(),
+            group,
+            app_name: TextArea::default(),
+            tags: TextArea::default(),
+            desc: TextArea::default(),
+            format: 0,
+            template: None,
+            focus: NewConfigField::DataId,
+            format_chosen: false,
+            request: None,
+        }
+    }
+
+    pub fn input(&mut self, field: NewConfigField) -> Option<&mut TextArea<'static>> {
+        match field {
+            NewConfigField::DataId => Some(&mut self.data_id),
+            NewConfigField::Group => Some(&mut self.group),
+            NewConfigField::AppName => Some(&mut self.app_name),
+            NewConfigField::Tags => Some(&mut self.tags),
+            NewConfigField::Desc => Some(&mut self.desc),
+            NewConfigField::Format | NewConfigField::Template => None,
+        }
+    }
+
+    pub fn is_checking(&self) -> bool {
+        self.request.is_some()
+    }
+}
+
+fn text(textarea: &TextArea) -> String {
+    textarea.lines().join("").trim().to_string()
+}
+
+// 按 dataId 扩展名推断配置类型，推断不出时为 None
+fn type_for_data_id(data_id: &str) -> Option<usize> {
+    let ext = data_id.rsplit_once('.')?.1.to_ascii_lowercase();
+    let ext = if ext == "yml" { "yaml".to_string() } else { ext };
+    CONFIG_TYPES.iter().position(|t| *t == ext)
+}
+
+// Nacos 的 dataId 和 group 只允许字母、数字和 . : _ -
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | ':' | '_' | '-'))
+}
+
+impl App<'_> {
+    pub fn config_new_open(&mut self) {
+        let Some(ns_id) = self.config_ns_id.as_ref() else {
+            return;
+        };
+        self.config_new = Some(NewConfigForm::new(ns_id));
+        self.current_screen = CurrentScreen::ConfigNew;
+    }
+
+    pub fn config_new_close(&mut self) {
+        self.config_new = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn config_new_focus(&mut self, forward: bool) {
+        if let Some(form) = self.config_new.as_mut() {
+            let len = NewConfigField::ALL.len();
+            let i = NewConfigField::ALL.iter().position(|f| *f == form.focus).unwrap_or(0);
+            let next = if forward { (i + 1) % len } else { (i + len - 1) % len };
+            form.focus = NewConfigField::ALL[next];
+        }
+    }
+
+    // 在格式或模板上左右切换；选中模板时格式跟随模板的类型
+    pub fn config_new_cycle(&mut self, forward: bool) {
+        let Some(form) = self.config_new.as_mut() else {
+            return;
+        };
+        match form.focus {
+            NewConfigField::Format => {
+                let len = CONFIG_TYPES.len();
+                form.format = if forward { (form.format + 1) % len } else { (form.format + len - 1) % len };
+                form.format_chosen = true;
+            }
+            NewConfigField::Template => {
+                // 0 表示空白，1.. 对应模板
+                let len = self.config_templates.len() + 1;
+                let i = form.template.map_or(0, |t| t + 1);
+                let next = if forward { (i + 1) % len } else { (i + len - 1) % len };
+                form.template = next.checked_sub(1);
+                if let Some(template) = form.template.map(|t| &self.config_templates[t])
+                    && let Some(format) = CONFIG_TYPES.iter().position(|t| *t == template.type_)
+                {
+                    form.format = format;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn config_new_input(&mut self, input: Input) {
+        let Some(form) = self.config_new.as_mut() else {
+            return;
+        };
+        let focus = form.focus;
+        if let Some(textarea) = form.input(focus) {
+            textarea.input(input);
+        }
+        if focus == NewConfigField::DataId
+            && !form.format_chosen
+            && form.template.is_none()
+            && let Some(format) = type_for_data_id(&text(&form.data_id))
+        {
+            form.format = format;
+        }
+    }
+
+    // 先确认配置不存在，避免把别人的配置覆盖掉
+    pub fn config_new_submit(&mut self) {
+        let Some(form) = self.config_new.as_ref().filter(|f| f.request.is_none()) else {
+            return;
+        };
+        let data_id = text(&form.data_id);
+        let group = Some(text(&form.group)).filter(|g| !g.is_empty()).unwrap_or("DEFAULT_GROUP".to_string());
+        if !is_valid_name(&data_id) || !is_valid_name(&group) {
+            self.error_message = Some("data_id and group may only contain letters, digits and . : _ -".to_string());
+            return;
+        }
+
+        let client = self.client.clone();
+        let ns_id = form.ns_id.clone();
+        let id = self.spawn("Checking config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            ApiEvent::NewConfigChecked(client.get_config(tenant, &data_id, &group).await)
+        });
+        if let Some(form) = self.config_new.as_mut() {
+            form.request = Some(id);
+        }
+    }
+
+    pub(super) fn on_new_config_checked(&mut self, id: u64, result: Result<String, NacosError>) {
+        let Some(form) = self.config_new.as_mut().filter(|f| f.request == Some(id)) else {
+            return;
+        };
+        form.request = None;
+        let data_id = text(&form.data_id);
+        let group = Some(text(&form.group)).filter(|g| !g.is_empty()).unwrap_or("DEFAULT_GROUP".to_string());
+        match result {
+            Ok(_) => self.error_message = Some(format!("{data_id} already exists in {group}")),
+            Err(NacosError::NotFound(_)) => {
+                let content = form.template.map_or("", |t| &self.config_templates[t].content);
+                let mut editor = ConfigEditor::new(&form.ns_id, &data_id, &group, CONFIG_TYPES[form.format], content);
+                // 服务端还没有这个配置，模板内容也算作修改
+                editor.original = String::new();
+                editor.meta = Some(ConfigMeta {
+                    app_name: text(&form.app_name),
+                    tags: text(&form.tags),
+                    desc: text(&form.desc),
+                });
+                self.config_new = None;
+                self.config_editor = Some(editor);
+                self.current_screen = CurrentScreen::ConfigEdit;
+            }
+            Err(err) => self.show_error("Check config", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_for_data_id() {
+        assert_eq!(type_for_data_id("application.yml").map(|i| CONFIG_TYPES[i]), Some("yaml"));
+        assert_eq!(type_for_data_id("app.Properties").map(|i| CONFIG_TYPES[i]), Some("properties"));
+        assert_eq!(type_for_data_id("app.conf"), None);
+        assert_eq!(type_for_data_id("app"), None);
+    }
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("order-service:v1_2.yaml"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("has space"));
+        assert!(!is_valid_name("中文"));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::api::error::NacosError;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+
+#[derive(Clone)]
+pub struct DeleteTarget {
+    pub id: String,
+    pub data_id: String,
+    pub group: String,
+}
+
+// 删除确认弹窗：确认前列出要删除的配置，完成后列出每个配置的结果
+pub struct ConfigDelete {
+    pub ns_id: String,
+    pub targets: Vec<DeleteTarget>,
+    pub request: Option<u64>,
+    pub results: Option<Vec<Result<(), String>>>, // 与 targets 一一对应
+}
+
+impl App<'_> {
+    // 空格标记当前行并移到下一行，用于批量删除
+    pub fn config_toggle_mark(&mut self) {
+        let Some(i) = self.config_list_state.selected() else {
+            return;
+        };
+        if let Some(config) = self.config_list.get_mut(i) {
+            config.marked = !config.marked;
+        }
+        self.config_select_next();
+    }
+
+    // 有标记的行时删除所有标记的配置，否则删除选中的配置
+    pub fn config_delete_start(&mut self) {
+        let Some(ns_id) = self.config_ns_id.clone() else {
+            return;
+        };
+        let targets: Vec<DeleteTarget> = self
+            .marked_or_selected_configs()
+            .into_iter()
+            .map(|c| DeleteTarget {
+                id: c.id.clone(),
+                data_id: c.data_id.clone(),
+                group: c.group.clone(),
+            })
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        self.config_delete = Some(ConfigDelete {
+            ns_id,
+            targets,
+            request: None,
+            results: None,
+        });
+        self.current_screen = CurrentScreen::ConfigDelete;
+    }
+
+    pub fn config_delete_confirm(&mut self) {
+        let Some(delete) = self.config_delete.as_ref().filter(|d| d.request.is_none() && d.results.is_none()) else {
+            return;
+        };
+        let client = self.client.clone();
+        let ns_id = delete.ns_id.clone();
+        let targets = delete.targets.clone();
+        let id = self.spawn("Deleting configs", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            // 单个配置按 dataId/group 删除，多个配置一次批量请求按 id 删除
+            let deleted = match targets.as_slice() {
+                [target] => client.delete_config(tenant, &target.data_id, &target.group).await,
+                _ => {
+                    let ids: Vec<String> = targets.iter().map(|t| t.id.clone()).collect();
+                    client.delete_configs(&ids).await
+                }
+            };
+            if let Err(err) = deleted {
+                let message = err.to_string();
+                return ApiEvent::ConfigsDeleted(targets.iter().map(|_| Err(message.clone())).collect());
+            }
+
+            // 批量接口只返回整体结果，逐个确认配置是否真的不存在了
+            let mut results = vec![];
+            for target in &targets {
+                results.push(match client.get_config(tenant, &target.data_id, &target.group).await {
+                    Err(NacosError::NotFound(_)) => Ok(()),
+                    Ok(_) => Err("still exists after delete".to_string()),
+                    Err(err) => Err(err.to_string()),
+                });
+            }
+            ApiEvent::ConfigsDeleted(results)
+        });
+        if let Some(delete) = self.config_delete.as_mut() {
+            delete.request = Some(id);
+        }
+    }
+
+    // 全部成功直接关闭弹窗，有失败时留在弹窗里显示每个配置的结果
+    pub(super) fn on_configs_deleted(&mut self, id: u64, results: Vec<Result<(), String>>) {
+        let Some(delete) = self.config_delete.as_mut().filter(|d| d.request == Some(id)) else {
+            return;
+        };
+        delete.request = None;
+        let deleted = results.iter().filter(|r| r.is_ok()).count();
+        if deleted == results.len() {
+            self.info_message = Some(format!("Deleted {deleted} config(s)"));
+            self.config_delete_close();
+        } else {
+            delete.results = Some(results);
+        }
+        if deleted > 0 {
+            self.config_reload();
+        }
+    }
+
+    pub fn config_delete_close(&mut self) {
+        // 删除进行中不能关闭，避免看不到结果
+        if self.config_delete.as_ref().is_some_and(|d| d.request.is_some()) {
+            return;
+        }
+        self.config_delete = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+}
@@ -0,0 +1,214 @@
+use similar::{ChangeTag, TextDiff};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiffTag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    pub old_no: Option<usize>, // 在旧内容中的行号（从 1 开始）
+    pub new_no: Option<usize>,
+    pub text: String,
+}
+
+// 两个版本内容的逐行对比，可在统一视图和左右并排视图之间切换
+pub struct DiffView {
+    pub old_label: String,
+    pub new_label: String,
+    pub lines: Vec<DiffLine>,                           // 统一视图
+    pub rows: Vec<(Option<DiffLine>, Option<DiffLine>)>, // 并排视图：(左, 右)
+    pub side_by_side: bool,
+    pub scroll: usize,
+    pub height: usize, // 上次渲染时的可视行数
+}
+
+impl DiffView {
+    pub fn new(old_label: &str, old: &str, new_label: &str, new: &str) -> DiffView {
+        let diff = TextDiff::from_lines(old, new);
+        let lines: Vec<DiffLine> = diff
+            .iter_all_changes()
+            .map(|change| DiffLine {
+                tag: match change.tag() {
+                    ChangeTag::Equal => DiffTag::Equal,
+                    ChangeTag::Delete => DiffTag::Delete,
+                    ChangeTag::Insert => DiffTag::Insert,
+                },
+                old_no: change.old_index().map(|i| i + 1),
+                new_no: change.new_index().map(|i| i + 1),
+                text: change.value().trim_end_matches(['\r', '\n']).replace('\t', "    "),
+            })
+            .collect();
+        let rows = side_by_side_rows(&lines);
+        DiffView {
+            old_label: old_label.to_string(),
+            new_label: new_label.to_string(),
+            lines,
+            rows,
+            side_by_side: false,
+            scroll: 0,
+            height: 1,
+        }
+    }
+
+    // (新增行数, 删除行数)
+    pub fn stats(&self) -> (usize, usize) {
+        let count = |tag| self.lines.iter().filter(|l| l.tag == tag).count();
+        (count(DiffTag::Insert), count(DiffTag::Delete))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.iter().all(|l| l.tag == DiffTag::Equal)
+    }
+
+    fn row_count(&self) -> usize {
+        if self.side_by_side { self.rows.len() } else { self.lines.len() }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.row_count().saturating_sub(self.height)
+    }
+
+    // 渲染时更新可视行数，窗口变大后滚动位置不能超出末尾
+    pub fn set_height(&mut self, height: usize) {
+        self.height = height;
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    pub fn toggle_side_by_side(&mut self) {
+        self.side_by_side = !self.side_by_side;
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    pub fn scroll_down(&mut self, n: usize) {
+        self.scroll = (self.scroll + n).min(self.max_scroll());
+    }
+
+    pub fn scroll_up(&mut self, n: usize) {
+        self.scroll = self.scroll.saturating_sub(n);
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.scroll = self.max_scroll();
+    }
+
+    // 跳到下一处/上一处修改块的开头
+    pub fn next_change(&mut self) {
+        let starts = self.change_starts();
+        if let Some(&row) = starts.iter().find(|&&row| row > self.scroll) {
+            self.scroll = row.min(self.max_scroll());
+        }
+    }
+
+    pub fn prev_change(&mut self) {
+        let starts = self.change_starts();
+        if let Some(&row) = starts.iter().rev().find(|&&row| row < self.scroll) {
+            self.scroll = row;
+        }
+    }
+
+    fn change_starts(&self) -> Vec<usize> {
+        let changed: Vec<bool> = if self.side_by_side {
+            self.rows
+                .iter()
+                .map(|(l, r)| l.iter().chain(r).any(|line| line.tag != DiffTag::Equal))
+                .collect()
+        } else {
+            self.lines.iter().map(|l| l.tag != DiffTag::Equal).collect()
+        };
+        (0..changed.len())
+            .filter(|&i| changed[i] && (i == 0 || !changed[i - 1]))
+            .collect()
+    }
+}
+
+// 连续的删除和新增行左右配对，相同的行两边都显示
+fn side_by_side_rows(lines: &[DiffLine]) -> Vec<(Option<DiffLine>, Option<DiffLine>)> {
+    let mut rows = vec![];
+    let mut deleted = vec![];
+    let mut inserted = vec![];
+    let flush = |rows: &mut Vec<_>, deleted: &mut Vec<DiffLine>, inserted: &mut Vec<DiffLine>| {
+        let len = deleted.len().max(inserted.len());
+        let mut left = deleted.drain(..);
+        let mut right = inserted.drain(..);
+        for _ in 0..len {
+            rows.push((left.next(), right.next()));
+        }
+    };
+    for line in lines {
+        match line.tag {
+            DiffTag::Delete => deleted.push(line.clone()),
+            DiffTag::Insert => inserted.push(line.clone()),
+            DiffTag::Equal => {
+                flush(&mut rows, &mut deleted, &mut inserted);
+                rows.push((Some(line.clone()), Some(line.clone())));
+            }
+        }
+    }
+    flush(&mut rows, &mut deleted, &mut inserted);
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_lines() {
+        let diff = DiffView::new("server", "a: 1\nb: 2\nc: 3\n", "local", "a: 1\nb: 20\nc: 3\nd: 4\n");
+        let tags: Vec<_> = diff.lines.iter().map(|l| (l.tag, l.old_no, l.new_no, l.text.as_str())).collect();
+        assert_eq!(
+            tags,
+            vec![
+                (DiffTag::Equal, Some(1), Some(1), "a: 1"),
+                (DiffTag::Delete, Some(2), None, "b: 2"),
+                (DiffTag::Insert, None, Some(2), "b: 20"),
+                (DiffTag::Equal, Some(3), Some(3), "c: 3"),
+                (DiffTag::Insert, None, Some(4), "d: 4"),
+            ]
+        );
+        assert_eq!(diff.stats(), (2, 1));
+        assert!(!diff.is_empty());
+        assert!(DiffView::new("a", "x\n", "b", "x\n").is_empty());
+    }
+
+    #[test]
+    fn test_side_by_side_rows() {
+        let diff = DiffView::new("server", "a\nb\nc\n", "local", "a\nB\nB2\nc\n");
+        let rows: Vec<_> = diff
+            .rows
+            .iter()
+            .map(|(l, r)| (l.as_ref().map(|l| l.text.as_str()), r.as_ref().map(|r| r.text.as_str())))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("a"), Some("a")),
+                (Some("b"), Some("B")),
+                (None, Some("B2")),
+                (Some("c"), Some("c")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_change() {
+        let old: String = (0..40).map(|i| format!("{i}\n")).collect();
+        let new = old.replace("10\n", "ten\n").replace("30\n", "thirty\n");
+        let mut diff = DiffView::new("a", &old, "b", &new);
+        diff.height = 5;
+        diff.next_change();
+        assert_eq!(diff.scroll, 10);
+        diff.next_change();
+        assert_eq!(diff.scroll, 31);
+        diff.prev_change();
+        assert_eq!(diff.scroll, 10);
+    }
+}
@@ -0,0 +1,99 @@
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::api::config::ConfigMeta;
+use crate::highlight::Format;
+use crate::validate::{validate, ValidationError};
+
+const CONFLICT_MARKERS: [&str; 3] = ["<<<<<<< ", "=======", ">>>>>>> "];
+
+// 全屏配置编辑器的状态
+pub struct ConfigEditor {
+    pub ns_id: String,
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub format: Format,
+    pub original: String, // 打开时服务端的内容，用来判断是否有修改
+    pub textarea: TextArea<'static>,
+    pub error: Option<ValidationError>,
+    pub confirm_discard: bool, // 有未保存修改时，再按一次 esc 才放弃
+    pub meta: Option<ConfigMeta>, // 新建配置时才有，发布时一起写入
+    pub merge_pending: bool,      // 合并留下了冲突块，冲突标记没删干净之前不能发布
+    trailing_newline: bool,
+}
+
+impl ConfigEditor {
+    pub fn new(ns_id: &str, data_id: &str, group: &str, type_: &str, content: &str) -> ConfigEditor {
+        let lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+        ConfigEditor {
+            ns_id: ns_id.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            type_: type_.to_string(),
+            format: Format::detect(type_, data_id),
+            original: content.to_string(),
+            textarea: TextArea::new(lines),
+            error: None,
+            confirm_discard: false,
+            meta: None,
+            merge_pending: false,
+            trailing_newline: content.ends_with('\n'),
+        }
+    }
+
+    // TextArea 按行保存，拼回去时保留打开时内容末尾的换行
+    pub fn content(&self) -> String {
+        let mut content = self.textarea.lines().join("\n");
+        if self.trailing_newline && !content.is_empty() {
+            content.push('\n');
+        }
+        content
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.content() != self.original
+    }
+
+    // 检查内容能否按声明的格式解析，失败时把光标移到出错位置
+    pub fn validate(&mut self) -> bool {
+        let content = self.content();
+        let marker = if self.merge_pending { conflict_marker(&content) } else { None };
+        self.error = marker.or_else(|| validate(self.format, &content).err());
+        if let Some(err) = &self.error {
+            let row = err.line.saturating_sub(1) as u16;
+            let col = err.column.saturating_sub(1) as u16;
+            self.textarea.move_cursor(CursorMove::Jump(row, col));
+        }
+        self.error.is_none()
+    }
+}
+
+// 合并冲突后留下的标记不能发布出去
+fn conflict_marker(content: &str) -> Option<ValidationError> {
+    content.lines().enumerate().find_map(|(i, line)| {
+        CONFLICT_MARKERS
+            .iter()
+            .any(|marker| line.starts_with(marker))
+            .then(|| ValidationError {
+                line: i + 1,
+                column: 1,
+                message: "unresolved merge conflict marker".to_string(),
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_markers_only_after_merge() {
+        let content = "Title\n=======\n\nbody\n";
+        let mut editor = ConfigEditor::new("", "README.md", "DEFAULT_GROUP", "text", content);
+        assert!(editor.validate());
+
+        editor.merge_pending = true;
+        assert!(!editor.validate());
+        assert_eq!(editor.error.as_ref().unwrap().line, 2);
+    }
+}
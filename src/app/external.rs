@@ -0,0 +1,127 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::PathBuf;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+// 用 $VISUAL/$EDITOR 编辑配置：内容写到临时文件，编辑器退出后再读回来
+pub struct ExternalEdit {
+    pub ns_id: String,
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub original: String, // 打开编辑器前服务端的内容
+    pub path: PathBuf,
+    pub written: bool,        // 临时文件已写好，可以启动编辑器
+    pub request: Option<u64>, // 正在拉取内容的请求
+    // 只有自己能访问的临时目录，内容里可能有密码等敏感信息；丢弃时连同文件一起删除
+    dir: Option<TempDir>,
+}
+
+impl ExternalEdit {
+    pub fn new(ns_id: &str, data_id: &str, group: &str, type_: &str) -> ExternalEdit {
+        ExternalEdit {
+            ns_id: ns_id.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            type_: type_.to_string(),
+            original: String::new(),
+            path: PathBuf::new(),
+            written: false,
+            request: None,
+            dir: None,
+        }
+    }
+
+    // 新建的文件只有自己可读写，create_new 保证不会写到已有的文件或符号链接上
+    pub fn write(&mut self, content: &str) -> io::Result<()> {
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("lazynacos-");
+        #[cfg(unix)]
+        builder.permissions(std::fs::Permissions::from_mode(0o700));
+        let dir = builder.tempdir()?;
+        let path = dir.path().join(temp_file_name(&self.data_id, &self.type_));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        options.open(&path)?.write_all(content.as_bytes())?;
+        self.path = path;
+        self.dir = Some(dir);
+        self.original = content.to_string();
+        self.written = true;
+        Ok(())
+    }
+
+    // 阻塞直到编辑器退出，返回编辑后的内容
+    pub fn run(&self, editor: &str) -> io::Result<String> {
+        // 编辑器可能带参数（如 "code -w"），交给 shell 拆分
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{editor} \"$1\""))
+            .arg("sh")
+            .arg(&self.path)
+            .status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("{editor} exited with {status}")));
+        }
+        std::fs::read_to_string(&self.path)
+    }
+}
+
+// 优先 $VISUAL，其次 $EDITOR，都没有时用 vi
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+// 临时文件名带上配置类型的扩展名，编辑器才能识别语法
+fn temp_file_name(data_id: &str, type_: &str) -> String {
+    let ext = match type_ {
+        "" | "text" => "txt",
+        other => other,
+    };
+    let name: String = data_id
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let name = name.strip_suffix(&format!(".{ext}")).unwrap_or(&name);
+    format!("{name}.{ext}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temp_file_extension() {
+        assert_eq!(temp_file_name("app.yaml", "yaml"), "app.yaml");
+        assert_eq!(temp_file_name("app", "properties"), "app.properties");
+        assert_eq!(temp_file_name("a/b c", "text"), "a_b_c.txt");
+    }
+
+    #[test]
+    fn test_run_fake_editor() {
+        // 用 sed 模拟编辑器修改文件
+        let mut edit = ExternalEdit::new("", "fake-editor.yaml", "DEFAULT_GROUP", "yaml");
+        edit.write("port: 8080\n").unwrap();
+        #[cfg(unix)]
+        {
+            assert_eq!(std::fs::metadata(&edit.path).unwrap().permissions().mode() & 0o777, 0o600);
+            assert_eq!(std::fs::metadata(edit.path.parent().unwrap()).unwrap().permissions().mode() & 0o777, 0o700);
+        }
+        assert_eq!(edit.run("sed -i s/8080/9090/").unwrap(), "port: 9090\n");
+
+        assert!(edit.run("false").is_err());
+
+        let dir = edit.path.parent().unwrap().to_path_buf();
+        drop(edit);
+        assert!(!dir.exists());
+    }
+}
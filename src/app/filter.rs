@@ -0,0 +1,367 @@
+use ratatui::widgets::ListState;
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::app::{App, CurrentMenu, CurrentScreen};
+
+// 列表上按 / 打开的模糊过滤：只显示匹配的行，n/N 在匹配的行之间循环
+pub struct ListFilter {
+    pub input: TextArea<'static>,
+    pub editing: bool,         // 正在输入过滤词，enter 后保留过滤结果
+    pub matches: Vec<usize>,   // 匹配的行在完整列表中的下标
+    pub list_state: ListState, // 过滤后列表的选中行和滚动位置
+}
+
+impl ListFilter {
+    pub(super) fn new() -> ListFilter {
+        ListFilter {
+            input: TextArea::default(),
+            editing: true,
+            matches: vec![],
+            list_state: ListState::default(),
+        }
+    }
+
+    pub fn query(&self) -> String {
+        self.input.lines().join("")
+    }
+
+    // 重新计算匹配的行，rows 为每一行参与匹配的字段，任一字段匹配即可
+    pub(super) fn update<'a, R>(&mut self, rows: impl Iterator<Item = R>)
+    where
+        R: IntoIterator<Item = &'a str>,
+    {
+        let query = self.query();
+        self.matches = rows
+            .enumerate()
+            .filter_map(|(i, fields)| {
+                let matched = query.is_empty() || fields.into_iter().any(|f| fuzzy_match(&query, f).is_some());
+                matched.then_some(i)
+            })
+            .collect();
+    }
+
+    // 选中行仍然匹配时保持不动，否则跳到第一个匹配；返回完整列表中的下标
+    pub(super) fn sync(&mut self, selected: Option<usize>) -> Option<usize> {
+        let pos = selected
+            .and_then(|i| self.matches.iter().position(|m| *m == i))
+            .or((!self.matches.is_empty()).then_some(0));
+        self.list_state.select(pos);
+        pos.map(|p| self.matches[p])
+    }
+
+    // 在匹配的行之间移动，wrap 为 true 时到头后回到另一端
+    pub(super) fn step(&mut self, forward: bool, wrap: bool) -> Option<usize> {
+        let len = self.matches.len();
+        if len == 0 {
+            return None;
+        }
+        let pos = match self.list_state.selected() {
+            None => 0,
+            Some(p) if forward && p + 1 < len => p + 1,
+            Some(p) if !forward && p > 0 => p - 1,
+            Some(_) if wrap => if forward { 0 } else { len - 1 },
+            Some(p) => p,
+        };
+        self.list_state.select(Some(pos));
+        Some(self.matches[pos])
+    }
+}
+
+// 按顺序在 text 中找出 query 的每个字符，返回匹配字符的字节区间（相邻的合并）；
+// 能连续匹配时优先取连续的一段。与查看器的搜索一样，query 全小写时忽略大小写
+pub fn fuzzy_match(query: &str, text: &str) -> Option<Vec<(usize, usize)>> {
+    if query.is_empty() {
+        return Some(vec![]);
+    }
+    let ignore_case = !query.chars().any(|c| c.is_uppercase());
+    let fold = |c: char| if ignore_case { c.to_ascii_lowercase() } else { c };
+    let needle: Vec<char> = query.chars().map(fold).collect();
+    let chars: Vec<(usize, char)> = text.char_indices().map(|(i, c)| (i, fold(c))).collect();
+
+    // 连续匹配
+    if let Some(start) = chars
+        .windows(needle.len())
+        .position(|w| w.iter().map(|(_, c)| *c).eq(needle.iter().copied()))
+    {
+        let begin = chars[start].0;
+        let last = chars[start + needle.len() - 1].0;
+        return Some(vec![(begin, last + text[last..].chars().next().map_or(0, char::len_utf8))]);
+    }
+
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    let mut rest = needle.iter().peekable();
+    for (i, c) in text.char_indices() {
+        let Some(want) = rest.peek() else {
+            break;
+        };
+        if fold(c) != **want {
+            continue;
+        }
+        rest.next();
+        match ranges.last_mut() {
+            Some((_, end)) if *end == i => *end = i + c.len_utf8(),
+            _ => ranges.push((i, i + c.len_utf8())),
+        }
+    }
+    rest.peek().is_none().then_some(ranges)
+}
+
+// 服务端按 dataId/group 查询配置，支持 * 通配符，适合整个命名空间装不进内存的情况
+pub struct ConfigQueryForm {
+    pub data_id: TextArea<'static>,
+    pub group: TextArea<'static>,
+    pub group_focused: bool,
+}
+
+impl App<'_> {
+    fn current_filter_mut(&mut self) -> Option<&mut ListFilter> {
+        match self.current_menu {
+            CurrentMenu::Config => self.config_filter.as_mut(),
+            CurrentMenu::Namespace => self.namespace_filter.as_mut(),
+            CurrentMenu::Service => self.service_filter.as_mut(),
+        }
+    }
+
+    pub fn current_filter(&self) -> Option<&ListFilter> {
+        match self.current_menu {
+            CurrentMenu::Config => self.config_filter.as_ref(),
+            CurrentMenu::Namespace => self.namespace_filter.as_ref(),
+            CurrentMenu::Service => self.service_filter.as_ref(),
+        }
+    }
+
+    // 已有过滤词时继续编辑
+    pub fn filter_open(&mut self) {
+        if let Some(filter) = self.current_filter_mut() {
+            filter.editing = true;
+            filter.input.move_cursor(CursorMove::End);
+            return;
+        }
+        match self.current_menu {
+            CurrentMenu::Config => self.config_filter = Some(ListFilter::new()),
+            CurrentMenu::Namespace => self.namespace_filter = Some(ListFilter::new()),
+            CurrentMenu::Service => self.service_filter = Some(ListFilter::new()),
+        }
+        self.filter_refresh();
+    }
+
+    pub fn filter_input(&mut self, input: Input) {
+        if let Some(filter) = self.current_filter_mut() {
+            filter.input.input(input);
+            self.filter_refresh();
+        }
+    }
+
+    // 结束输入，保留过滤结果；过滤词为空时直接关闭
+    pub fn filter_submit(&mut self) {
+        let Some(filter) = self.current_filter_mut() else {
+            return;
+        };
+        filter.editing = false;
+        if filter.query().is_empty() {
+            self.filter_clear();
+        }
+    }
+
+    pub fn filter_clear(&mut self) {
+        match self.current_menu {
+            CurrentMenu::Config => self.config_filter = None,
+            CurrentMenu::Namespace => self.namespace_filter = None,
+            CurrentMenu::Service => self.service_filter = None,
+        }
+    }
+
+    // 按当前过滤词重新计算匹配的行，并让选中行落在匹配的行上
+    pub fn filter_refresh(&mut self) {
+        match self.current_menu {
+            CurrentMenu::Config => self.config_filter_refresh(),
+            CurrentMenu::Namespace => self.namespace_filter_refresh(),
+            CurrentMenu::Service => self.service_filter_refresh(),
+        }
+    }
+
+    pub(super) fn config_filter_refresh(&mut self) {
+        let Some(filter) = self.config_filter.as_mut() else {
+            return;
+        };
+        filter.update(
+            self.config_list
+                .iter()
+                .map(|c| [c.data_id.as_str(), c.group.as_str(), c.app_name.as_str()]),
+        );
+        // 没有匹配的行时取消选中，避免对看不到的配置进行操作
+        let selected = filter.sync(self.config_list_state.selected());
+        self.config_list_state.select(selected);
+    }
+
+    pub(super) fn namespace_filter_refresh(&mut self) {
+        let Some(filter) = self.namespace_filter.as_mut() else {
+            return;
+        };
+        filter.update(self.namespace_list.iter().map(|ns| {
+            [ns.ns_name.as_str(), ns.ns_id.as_str(), ns.ns_desc.as_deref().unwrap_or_default()]
+        }));
+        // 没有匹配的行时过滤结果里没有选中行，selected_namespace 随之返回 None
+        if let Some(selected) = filter.sync(Some(self.namespace_current_line as usize)) {
+            self.namespace_current_line = selected as u8;
+        }
+    }
+
+    pub(super) fn service_filter_refresh(&mut self) {
+        let Some(filter) = self.service_filter.as_mut() else {
+            return;
+        };
+        filter.update(self.service_list.iter().map(|s| [s.name.as_str(), s.group.as_str()]));
+        let selected = filter.sync(self.service_list_state.selected());
+        self.service_list_state.select(selected);
+    }
+
+    // n/N 在匹配的行之间循环，到头后回到另一端
+    pub fn filter_step(&mut self, forward: bool) {
+        match self.current_menu {
+            CurrentMenu::Config => self.config_filter_step(forward, true),
+            CurrentMenu::Namespace => self.namespace_filter_step(forward, true),
+            CurrentMenu::Service => self.service_filter_step(forward, true),
+        }
+    }
+
+    pub(super) fn config_filter_step(&mut self, forward: bool, wrap: bool) {
+        let Some(filter) = self.config_filter.as_mut() else {
+            return;
+        };
+        if let Some(selected) = filter.step(forward, wrap) {
+            self.config_list_state.select(Some(selected));
+        }
+        // 走到最后一个匹配时继续加载下一页，后面的页里可能还有匹配的配置
+        if forward && filter.list_state.selected().is_none_or(|p| p + 1 >= filter.matches.len()) {
+            self.config_load_next_page();
+        }
+    }
+
+    pub(super) fn namespace_filter_step(&mut self, forward: bool, wrap: bool) {
+        if let Some(selected) = self.namespace_filter.as_mut().and_then(|f| f.step(forward, wrap)) {
+            self.namespace_current_line = selected as u8;
+        }
+    }
+
+    pub(super) fn service_filter_step(&mut self, forward: bool, wrap: bool) {
+        if let Some(selected) = self.service_filter.as_mut().and_then(|f| f.step(forward, wrap)) {
+            self.service_list_state.select(Some(selected));
+        }
+    }
+
+    pub fn config_query_open(&mut self) {
+        if self.config_ns_id.is_none() {
+            return;
+        }
+        let field = |value: &str| {
+            let mut textarea = TextArea::new(vec![value.to_string()]);
+            textarea.move_cursor(CursorMove::End);
+            textarea
+        };
+        self.config_query_form = Some(ConfigQueryForm {
+            data_id: field(&self.config_query.0.clone()),
+            group: field(&self.config_query.1.clone()),
+            group_focused: false,
+        });
+        self.current_screen = CurrentScreen::ConfigQuery;
+    }
+
+    pub fn config_query_close(&mut self) {
+        self.config_query_form = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn config_query_toggle_focus(&mut self) {
+        if let Some(form) = self.config_query_form.as_mut() {
+            form.group_focused = !form.group_focused;
+        }
+    }
+
+    pub fn config_query_input(&mut self, input: Input) {
+        if let Some(form) = self.config_query_form.as_mut() {
+            let textarea = if form.group_focused { &mut form.group } else { &mut form.data_id };
+            textarea.input(input);
+        }
+    }
+
+    // 两个输入框都为空时恢复为整个命名空间
+    pub fn config_query_submit(&mut self) {
+        let Some(form) = self.config_query_form.take() else {
+            return;
+        };
+        let text = |textarea: &TextArea| textarea.lines().join("").trim().to_string();
+        self.set_config_query(text(&form.data_id), text(&form.group));
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn set_config_query(&mut self, data_id: String, group: String) {
+        self.config_query = (data_id, group);
+        self.config_reload();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tui_textarea::Key;
+
+    use super::*;
+    use crate::api::client::NacosClient;
+    use crate::app::NamespaceItem;
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "anything"), Some(vec![]));
+        // 连续的一段优先于分散的字符
+        assert_eq!(fuzzy_match("ord", "o-r-d-order"), Some(vec![(6, 9)]));
+        assert_eq!(fuzzy_match("osvc", "order-service"), Some(vec![(0, 1), (6, 7), (9, 10), (11, 12)]));
+        assert_eq!(fuzzy_match("ORD", "order"), None);
+        assert_eq!(fuzzy_match("ord", "ORDER"), Some(vec![(0, 3)]));
+        assert_eq!(fuzzy_match("配置", "测试配置a"), Some(vec![(6, 12)]));
+        assert_eq!(fuzzy_match("xyz", "order"), None);
+    }
+
+    #[test]
+    fn test_filter_step() {
+        let mut filter = ListFilter::new();
+        filter.input = TextArea::new(vec!["app".to_string()]);
+        filter.update([["app-a"], ["db"], ["my-app"], ["apple"]].into_iter());
+        assert_eq!(filter.matches, vec![0, 2, 3]);
+        // 选中行不匹配时跳到第一个匹配
+        assert_eq!(filter.sync(Some(1)), Some(0));
+        assert_eq!(filter.step(true, false), Some(2));
+        assert_eq!(filter.step(true, false), Some(3));
+        assert_eq!(filter.step(true, false), Some(3));
+        assert_eq!(filter.step(true, true), Some(0));
+        assert_eq!(filter.step(false, true), Some(3));
+    }
+
+    #[test]
+    fn test_namespace_filter_without_matches() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(NacosClient::new("http://127.0.0.1:8848", "nacos", "nacos"), tx);
+        app.namespace_list = ["public", "dev"]
+            .iter()
+            .map(|name| NamespaceItem {
+                ns_name: name.to_string(),
+                ns_id: name.to_string(),
+                ns_desc: None,
+                quota: 200,
+                config_count: 0,
+                ns_typs: 2,
+            })
+            .collect();
+        app.current_menu = CurrentMenu::Namespace;
+        app.namespace_current_line = 1;
+        app.filter_open();
+        assert_eq!(app.selected_namespace().map(|ns| ns.ns_id.as_str()), Some("dev"));
+
+        // 没有匹配的行时 d/e 不作用于看不到的命名空间
+        app.filter_input(Input { key: Key::Char('x'), ..Default::default() });
+        assert!(app.selected_namespace().is_none());
+        app.move_screen_main_to_ns_delete();
+        app.move_screen_main_to_ns_edit();
+        assert!(app.current_screen == CurrentScreen::Main);
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, TimeZone};
+use ratatui::widgets::ListState;
+
+use crate::api::error::NacosError;
+use crate::app::diff::DiffView;
+use crate::app::publish::PendingPublish;
+use crate::app::viewer::ConfigViewer;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::config_history_resp::ConfigHistory;
+
+const HISTORY_PAGE_SIZE: u32 = 50;
+const HISTORY_PREFETCH_ROWS: usize = 10;
+
+pub struct HistoryItem {
+    pub id: String,
+    pub op_type: &'static str,
+    pub src_user: String,
+    pub src_ip: String,
+    pub modified: String,
+}
+
+impl From<ConfigHistory> for HistoryItem {
+    fn from(history: ConfigHistory) -> HistoryItem {
+        HistoryItem {
+            op_type: match history.opType.trim() {
+                "I" => "insert",
+                "U" => "update",
+                "D" => "delete",
+                _ => "unknown",
+            },
+            id: history.id,
+            src_user: history.srcUser.unwrap_or_default(),
+            src_ip: history.srcIp.unwrap_or_default(),
+            modified: history.lastModifiedTime.as_ref().map(format_time).unwrap_or_default(),
+        }
+    }
+}
+
+// 时间戳（毫秒）或带时区的时间字符串，统一转成本地时间显示
+fn format_time(value: &serde_json::Value) -> String {
+    let time: Option<DateTime<Local>> = match value {
+        serde_json::Value::Number(n) => n.as_i64().and_then(|ms| Local.timestamp_millis_opt(ms).single()),
+        serde_json::Value::String(s) => DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.3f%z")
+            .ok()
+            .map(|t| t.with_timezone(&Local)),
+        _ => None,
+    };
+    match time {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()),
+    }
+}
+
+// 需要历史版本内容的操作，内容没有缓存时先拉取详情再执行
+#[derive(Clone, Copy)]
+pub enum HistoryAction {
+    View,
+    DiffCurrent,
+    DiffMarked,
+    Rollback,
+}
+
+// 某个配置的历史版本列表
+pub struct ConfigHistoryBrowser {
+    pub ns_id: String,
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub items: Vec<HistoryItem>,
+    pub list_state: ListState,
+    pub total: usize,
+    page_no: u32,
+    request: Option<u64>, // 正在加载的历史列表分页
+    // 服务端当前内容，外层 None 表示还在加载，内层 None 表示配置已被删除
+    pub current: Option<Option<String>>,
+    current_request: Option<u64>,
+    contents: HashMap<String, String>, // 历史 id -> 当时的内容
+    detail_request: Option<(u64, String, HistoryAction)>,
+    pub marked: Option<usize>, // 用来和选中版本对比的版本
+    pub viewer: Option<ConfigViewer>,
+    pub diff: Option<DiffView>,
+}
+
+impl ConfigHistoryBrowser {
+    fn new(ns_id: &str, data_id: &str, group: &str, type_: &str) -> ConfigHistoryBrowser {
+        ConfigHistoryBrowser {
+            ns_id: ns_id.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            type_: type_.to_string(),
+            items: vec![],
+            list_state: ListState::default(),
+            total: 0,
+            page_no: 0,
+            request: None,
+            current: None,
+            current_request: None,
+            contents: HashMap::new(),
+            detail_request: None,
+            marked: None,
+            viewer: None,
+            diff: None,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&HistoryItem> {
+        self.list_state.selected().and_then(|i| self.items.get(i))
+    }
+}
+
+impl App<'_> {
+    // 查看器打开时看查看器里的配置，否则看列表中选中的配置
+    pub fn config_open_history(&mut self) {
+        let history = match (self.config_viewer.as_ref(), self.config_ns_id.as_ref(), self.selected_config()) {
+            (Some(v), _, _) => ConfigHistoryBrowser::new(&v.ns_id, &v.data_id, &v.group, &v.type_),
+            (None, Some(ns_id), Some(c)) => ConfigHistoryBrowser::new(ns_id, &c.data_id, &c.group, &c.format),
+            _ => return,
+        };
+        self.config_history = Some(history);
+        self.current_screen = CurrentScreen::ConfigHistory;
+        self.history_reload();
+    }
+
+    pub fn config_close_history(&mut self) {
+        self.config_history = None;
+        self.current_screen = if self.config_viewer.is_some() {
+            CurrentScreen::ConfigView
+        } else {
+            CurrentScreen::Main
+        };
+    }
+
+    // 重新加载历史列表和服务端当前内容，回滚成功后也会调用
+    pub fn history_reload(&mut self) {
+        let Some(history) = self.config_history.as_mut() else {
+            return;
+        };
+        history.items.clear();
+        history.list_state.select(None);
+        history.total = 0;
+        history.page_no = 0;
+        history.request = None;
+        history.marked = None;
+        history.current = None;
+
+        let client = self.client.clone();
+        let (ns_id, data_id, group) = (history.ns_id.clone(), history.data_id.clone(), history.group.clone());
+        let id = self.spawn("Loading config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            ApiEvent::HistoryCurrentLoaded(client.get_config(tenant, &data_id, &group).await)
+        });
+        if let Some(history) = self.config_history.as_mut() {
+            history.current_request = Some(id);
+        }
+        self.history_load_next_page();
+    }
+
+    fn history_load_next_page(&mut self) {
+        let Some(history) = self.config_history.as_ref() else {
+            return;
+        };
+        let all_loaded = history.page_no > 0 && history.items.len() >= history.total;
+        if history.request.is_some() || all_loaded {
+            return;
+        }
+
+        let client = self.client.clone();
+        let (ns_id, data_id, group) = (history.ns_id.clone(), history.data_id.clone(), history.group.clone());
+        let page_no = history.page_no + 1;
+        let id = self.spawn("Loading history", async move {
+            let result = client.list_config_history(&ns_id, &data_id, &group, page_no, HISTORY_PAGE_SIZE).await;
+            ApiEvent::HistoryLoaded(result.map(|resp| resp.data))
+        });
+        if let Some(history) = self.config_history.as_mut() {
+            history.request = Some(id);
+        }
+    }
+
+    pub fn history_select_next(&mut self) {
+        let Some(history) = self.config_history.as_mut() else {
+            return;
+        };
+        let len = history.items.len();
+        let next = history.list_state.selected().map_or(0, |i| i + 1);
+        if next < len {
+            history.list_state.select(Some(next));
+        }
+        if next + HISTORY_PREFETCH_ROWS >= len {
+            self.history_load_next_page();
+        }
+    }
+
+    pub fn history_select_prev(&mut self) {
+        if let Some(history) = self.config_history.as_mut()
+            && let Some(i) = history.list_state.selected()
+        {
+            history.list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    // 标记选中的版本，之后可以和其他版本对比；再按一次取消
+    pub fn history_toggle_mark(&mut self) {
+        if let Some(history) = self.config_history.as_mut() {
+            let selected = history.list_state.selected();
+            history.marked = if history.marked == selected { None } else { selected };
+        }
+    }
+
+    pub fn history_run(&mut self, action: HistoryAction) {
+        let Some(history) = self.config_history.as_mut() else {
+            return;
+        };
+        let Some(item) = history.selected() else {
+            return;
+        };
+        if history.detail_request.is_some() {
+            return;
+        }
+
+        // 需要的版本内容都已缓存才能执行，否则先拉取缺少的那个
+        let mut needed = vec![item.id.clone()];
+        if let HistoryAction::DiffMarked = action {
+            match history.marked.and_then(|i| history.items.get(i)) {
+                Some(marked) => needed.insert(0, marked.id.clone()),
+                None => {
+                    self.error_message = Some("Mark a revision with space first".to_string());
+                    return;
+                }
+            }
+        }
+        if let Some(missing) = needed.into_iter().find(|id| !history.contents.contains_key(id)) {
+            let client = self.client.clone();
+            let (ns_id, data_id, group) = (history.ns_id.clone(), history.data_id.clone(), history.group.clone());
+            let nid = missing.clone();
+            let id = self.spawn("Loading revision", async move {
+                let result = client.get_config_history(&ns_id, &data_id, &group, &nid).await;
+                ApiEvent::HistoryDetailLoaded(result.map(|resp| resp.data.content.unwrap_or_default()))
+            });
+            if let Some(history) = self.config_history.as_mut() {
+                history.detail_request = Some((id, missing, action));
+            }
+            return;
+        }
+
+        let content = &history.contents[&item.id];
+        let label = format!("{} ({})", item.modified, item.op_type);
+        match action {
+            HistoryAction::View => {
+                let mut viewer = ConfigViewer::new(&history.ns_id, &history.data_id, &history.group, &history.type_);
+                viewer.revision = Some(label);
+                viewer.set_content(content);
+                history.viewer = Some(viewer);
+            }
+            HistoryAction::DiffCurrent => {
+                let Some(current) = history.current.as_ref() else {
+                    return;
+                };
+                let current_label = if current.is_some() { "current" } else { "current (deleted)" };
+                let current = current.as_deref().unwrap_or_default();
+                history.diff = Some(DiffView::new(&label, content, current_label, current));
+            }
+            HistoryAction::DiffMarked => {
+                let Some(marked) = history.marked.and_then(|i| history.items.get(i)) else {
+                    return;
+                };
+                let marked_label = format!("{} ({})", marked.modified, marked.op_type);
+                history.diff = Some(DiffView::new(&marked_label, &history.contents[&marked.id], &label, content));
+            }
+            // 回滚就是重新发布这个版本的内容，照常先看差异再确认
+            HistoryAction::Rollback => {
+                let Some(current) = history.current.as_ref() else {
+                    return;
+                };
+                let mut publish = PendingPublish::new(
+                    &history.ns_id,
+                    &history.data_id,
+                    &history.group,
+                    &history.type_,
+                    current.as_deref().unwrap_or_default(),
+                    content,
+                );
+                // 配置已被删除时相当于重新创建，不做 cas 检查
+                publish.create = current.is_none();
+                if publish.diff.is_empty() {
+                    self.info_message = Some("This revision is the same as the current content".to_string());
+                    return;
+                }
+                self.config_publish = Some(publish);
+                self.current_screen = CurrentScreen::ConfigPublishConfirm;
+            }
+        }
+    }
+
+    pub(super) fn on_history_event(&mut self, id: u64, event: ApiEvent) {
+        let Some(history) = self.config_history.as_mut() else {
+            return;
+        };
+        match event {
+            ApiEvent::HistoryLoaded(result) if history.request == Some(id) => {
+                history.request = None;
+                match result {
+                    Ok(page) => {
+                        history.page_no += 1;
+                        history.total = page.totalCount.max(0) as usize;
+                        history.items.extend(page.pageItems.into_iter().map(HistoryItem::from));
+                        if history.list_state.selected().is_none() && !history.items.is_empty() {
+                            history.list_state.select(Some(0));
+                        }
+                    }
+                    Err(err) => self.show_error("Load history", err),
+                }
+            }
+            ApiEvent::HistoryCurrentLoaded(result) if history.current_request == Some(id) => {
+                history.current_request = None;
+                match result {
+                    Ok(content) => history.current = Some(Some(content)),
+                    Err(NacosError::NotFound(_)) => history.current = Some(None),
+                    Err(err) => self.show_error("Load config content", err),
+                }
+            }
+            ApiEvent::HistoryDetailLoaded(result) => {
+                let Some((_, nid, action)) = history.detail_request.take_if(|(request, _, _)| *request == id) else {
+                    return;
+                };
+                match result {
+                    Ok(content) => {
+                        history.contents.insert(nid, content);
+                        self.history_run(action);
+                    }
+                    Err(err) => self.show_error("Load revision", err),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_time() {
+        let local = |ms: i64| Local.timestamp_millis_opt(ms).unwrap().format("%Y-%m-%d %H:%M:%S").to_string();
+        assert_eq!(format_time(&serde_json::json!(1_700_000_000_000i64)), local(1_700_000_000_000));
+        assert_eq!(
+            format_time(&serde_json::json!("2010-05-04T16:00:00.000+0000")),
+            local(1_272_988_800_000)
+        );
+        assert_eq!(format_time(&serde_json::json!("yesterday")), "yesterday");
+    }
+}
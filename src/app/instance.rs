@@ -0,0 +1,188 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use ratatui::widgets::ListState;
+
+use crate::api::error::NacosError;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::instance_list_resp::Instance;
+
+// 自动刷新的间隔，滚动发布时可以看到实例逐个上下线
+pub const INSTANCE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+// 服务下所有集群的实例，打开时默认开启自动刷新
+pub struct InstanceBrowser {
+    pub ns_id: String,
+    pub service: String,
+    pub group: String,
+    pub instances: Vec<Instance>,
+    pub list_state: ListState,
+    pub auto_refresh: bool,
+    pub updated_at: Option<DateTime<Local>>, // 上次加载成功的时间
+    next_refresh: Instant,
+    pub request: Option<u64>,
+}
+
+impl InstanceBrowser {
+    pub(super) fn new(ns_id: &str, service: &str, group: &str) -> InstanceBrowser {
+        InstanceBrowser {
+            ns_id: ns_id.to_string(),
+            service: service.to_string(),
+            group: group.to_string(),
+            instances: vec![],
+            list_state: ListState::default(),
+            auto_refresh: true,
+            updated_at: None,
+            next_refresh: Instant::now() + INSTANCE_REFRESH_INTERVAL,
+            request: None,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Instance> {
+        self.list_state.selected().and_then(|i| self.instances.get(i))
+    }
+
+    pub fn healthy_count(&self) -> usize {
+        self.instances.iter().filter(|i| i.healthy).count()
+    }
+
+    // 刷新后按集群、IP、端口排序，选中行跟随同一个实例
+    fn set_instances(&mut self, mut instances: Vec<Instance>) {
+        let key = |i: &Instance| (i.clusterName.clone(), i.ip.clone(), i.port);
+        let selected = self.selected().map(key);
+        instances.sort_by_key(key);
+        let index = selected
+            .and_then(|k| instances.iter().position(|i| key(i) == k))
+            .or_else(|| self.list_state.selected().map(|i| i.min(instances.len().saturating_sub(1))))
+            .or(Some(0))
+            .filter(|_| !instances.is_empty());
+        self.instances = instances;
+        self.list_state.select(index);
+    }
+}
+
+impl App<'_> {
+    pub fn service_open_instances(&mut self) {
+        let (Some(ns_id), Some(service)) = (self.service_ns_id.as_deref(), self.selected_service()) else {
+            return;
+        };
+        self.service_instances = Some(InstanceBrowser::new(ns_id, &service.name, &service.group));
+        self.current_screen = CurrentScreen::ServiceInstances;
+        self.instance_refresh();
+    }
+
+    // 实例和订阅者是同一个弹窗的两个标签页，一起关闭
+    pub fn instance_close(&mut self) {
+        self.service_instances = None;
+        self.service_subscribers = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn instance_refresh(&mut self) {
+        let Some(browser) = self.service_instances.as_ref() else {
+            return;
+        };
+        let client = self.client.clone();
+        let (ns_id, service, group) = (browser.ns_id.clone(), browser.service.clone(), browser.group.clone());
+        let id = self.spawn("Loading instances", async move {
+            ApiEvent::InstancesLoaded(client.fetch_all_instances(&ns_id, &service, &group).await)
+        });
+        if let Some(browser) = self.service_instances.as_mut() {
+            browser.request = Some(id);
+        }
+    }
+
+    // 由 tick 驱动，上一次请求还没返回时不重复发送
+    pub(super) fn instance_auto_refresh(&mut self) {
+        let due = self
+            .service_instances
+            .as_ref()
+            .is_some_and(|b| b.auto_refresh && b.request.is_none() && Instant::now() >= b.next_refresh);
+        if due {
+            self.instance_refresh();
+        }
+    }
+
+    pub(super) fn on_instances_loaded(&mut self, id: u64, result: Result<Vec<Instance>, NacosError>) {
+        let Some(browser) = self.service_instances.as_mut().filter(|b| b.request == Some(id)) else {
+            return;
+        };
+        browser.request = None;
+        browser.next_refresh = Instant::now() + INSTANCE_REFRESH_INTERVAL;
+        match result {
+            Ok(instances) => {
+                browser.set_instances(instances);
+                browser.updated_at = Some(Local::now());
+            }
+            Err(err) => self.show_error("Load instances", err),
+        }
+    }
+
+    pub fn instance_toggle_auto_refresh(&mut self) {
+        if let Some(browser) = self.service_instances.as_mut() {
+            browser.auto_refresh = !browser.auto_refresh;
+            browser.next_refresh = Instant::now() + INSTANCE_REFRESH_INTERVAL;
+        }
+    }
+
+    pub fn instance_select_next(&mut self) {
+        if let Some(browser) = self.service_instances.as_mut() {
+            let next = browser.list_state.selected().map_or(0, |i| i + 1);
+            if next < browser.instances.len() {
+                browser.list_state.select(Some(next));
+            }
+        }
+    }
+
+    pub fn instance_select_prev(&mut self) {
+        if let Some(browser) = self.service_instances.as_mut()
+            && let Some(i) = browser.list_state.selected()
+        {
+            browser.list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn instance(ip: &str, cluster: &str) -> Instance {
+        Instance {
+            ip: ip.to_string(),
+            port: 8080,
+            weight: 1.0,
+            healthy: true,
+            enabled: true,
+            ephemeral: true,
+            clusterName: cluster.to_string(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_set_instances_keeps_selection() {
+        let mut browser = InstanceBrowser::new("", "order-service", "DEFAULT_GROUP");
+        browser.set_instances(vec![instance("10.0.0.2", "DEFAULT"), instance("10.0.0.1", "DEFAULT")]);
+        assert_eq!(browser.selected().unwrap().ip, "10.0.0.1");
+        browser.list_state.select(Some(1));
+
+        // 新实例排在前面，选中的仍然是 10.0.0.2
+        browser.set_instances(vec![
+            instance("10.0.0.2", "DEFAULT"),
+            instance("10.0.0.9", "BACKUP"),
+            instance("10.0.0.1", "DEFAULT"),
+        ]);
+        assert_eq!(browser.selected().unwrap().ip, "10.0.0.2");
+
+        // 选中的实例下线后停在原来的位置
+        browser.set_instances(vec![instance("10.0.0.9", "BACKUP"), instance("10.0.0.1", "DEFAULT")]);
+        assert_eq!(browser.selected().unwrap().ip, "10.0.0.1");
+
+        browser.set_instances(vec![]);
+        assert!(browser.selected().is_none());
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::api::error::NacosError;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::instance_list_resp::Instance;
+
+// Nacos 允许的最大权重
+const MAX_WEIGHT: f64 = 10000.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InstanceEditKind {
+    Enabled,
+    Weight,
+    Metadata,
+    Deregister,
+}
+
+// 修改实例的弹窗：权重和元数据先编辑再确认，上下线和注销直接确认；确认页列出修改的影响
+pub struct InstanceEdit {
+    pub ns_id: String,
+    pub service: String,
+    pub group: String,
+    pub kind: InstanceEditKind,
+    pub before: Instance,
+    pub after: Option<Instance>, // 确认页要提交的实例，编辑中为 None
+    pub impact: Vec<String>,
+    pub input: TextArea<'static>,
+    pub request: Option<u64>,
+}
+
+impl InstanceEdit {
+    pub fn confirming(&self) -> bool {
+        self.after.is_some()
+    }
+}
+
+fn edit_kind_has_input(kind: InstanceEditKind) -> bool {
+    matches!(kind, InstanceEditKind::Weight | InstanceEditKind::Metadata)
+}
+
+pub fn parse_weight(text: &str) -> Result<f64, String> {
+    let weight: f64 = text.trim().parse().map_err(|_| format!("Invalid weight: {}", text.trim()))?;
+    if !(0.0..=MAX_WEIGHT).contains(&weight) {
+        return Err(format!("Weight must be between 0 and {}", MAX_WEIGHT));
+    }
+    Ok(weight)
+}
+
+// 每行一个 key=value，空行忽略
+pub fn parse_metadata(text: &str) -> Result<HashMap<String, String>, String> {
+    let mut metadata = HashMap::new();
+    for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("Line {}: expected key=value", i + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("Line {}: empty key", i + 1));
+        }
+        if metadata.insert(key.to_string(), value.trim().to_string()).is_some() {
+            return Err(format!("Line {}: duplicate key {}", i + 1, key));
+        }
+    }
+    Ok(metadata)
+}
+
+// 单行输入框里逗号分隔的 key=value；逗号后面不是 key=value 时逗号属于前一个值，如 tags=a,b
+pub fn parse_inline_metadata(text: &str) -> Result<HashMap<String, String>, String> {
+    let mut entries: Vec<String> = vec![];
+    for piece in text.trim().trim_end_matches(',').split(',') {
+        match entries.last_mut() {
+            Some(entry) if !piece.contains('=') => {
+                entry.push(',');
+                entry.push_str(piece);
+            }
+            _ => entries.push(piece.to_string()),
+        }
+    }
+    parse_metadata(&entries.join("\n"))
+}
+
+// 元数据的变化，每个键一行：+ 新增，- 删除，~ 修改
+pub fn metadata_changes(before: &HashMap<String, String>, after: &HashMap<String, String>) -> Vec<String> {
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    keys.into_iter()
+        .filter_map(|key| match (before.get(key), after.get(key)) {
+            (None, Some(new)) => Some(format!("+ {}={}", key, new)),
+            (Some(old), None) => Some(format!("- {}={}", key, old)),
+            (Some(old), Some(new)) if old != new => Some(format!("~ {}: {} -> {}", key, old, new)),
+            _ => None,
+        })
+        .collect()
+}
+
+// 实例能分到的流量比例：权重占所有可用（在线且健康）实例权重之和的比例
+fn traffic_share(target: &Instance, instances: &[Instance]) -> Option<f64> {
+    let available = |i: &Instance| i.enabled && i.healthy;
+    let others: f64 = instances
+        .iter()
+        .filter(|i| !same_instance(i, target) && available(i))
+        .map(|i| i.weight)
+        .sum();
+    let own = if available(target) { target.weight } else { 0.0 };
+    (others + own > 0.0).then(|| own / (others + own) * 100.0)
+}
+
+fn same_instance(a: &Instance, b: &Instance) -> bool {
+    a.ip == b.ip && a.port == b.port && a.clusterName == b.clusterName
+}
+
+fn format_share(share: Option<f64>) -> String {
+    share.map_or("-".to_string(), |s| format!("{:.1}%", s))
+}
+
+// 确认页显示的影响说明，instances 为服务当前的全部实例
+pub fn describe_impact(kind: InstanceEditKind, before: &Instance, after: &Instance, instances: &[Instance]) -> Vec<String> {
+    let mut after_instances: Vec<Instance> = instances.iter().filter(|i| !same_instance(i, before)).cloned().collect();
+    after_instances.push(after.clone());
+    let remaining = after_instances.iter().filter(|i| i.enabled && i.healthy).count();
+
+    let mut lines = vec![];
+    match kind {
+        InstanceEditKind::Enabled if !after.enabled => {
+            lines.push("Take the instance offline: consumers stop routing traffic to it.".to_string());
+            lines.push(format!("{} healthy online instance(s) will remain.", remaining));
+            if remaining == 0 {
+                lines.push("WARNING: no instance will be left to serve this service!".to_string());
+            }
+        }
+        InstanceEditKind::Enabled => {
+            lines.push("Bring the instance online: consumers start routing traffic to it again.".to_string());
+            if !after.healthy {
+                lines.push("The instance is unhealthy and receives no traffic until it passes health checks.".to_string());
+            }
+        }
+        InstanceEditKind::Weight => {
+            lines.push(format!("Weight: {:?} -> {:?}", before.weight, after.weight));
+            lines.push(format!(
+                "Traffic share: {} -> {}",
+                format_share(traffic_share(before, instances)),
+                format_share(traffic_share(after, &after_instances))
+            ));
+            if after.weight == 0.0 {
+                lines.push("Weight 0 drains all traffic from this instance.".to_string());
+                if remaining == 0 || after_instances.iter().all(|i| !(i.enabled && i.healthy) || i.weight == 0.0) {
+                    lines.push("WARNING: no instance with a positive weight will be left!".to_string());
+                }
+            }
+        }
+        InstanceEditKind::Deregister => {
+            let remaining = instances.iter().filter(|i| !same_instance(i, before) && i.enabled && i.healthy).count();
+            lines.push("Remove the instance from the registry: consumers stop routing traffic to it.".to_string());
+            lines.push(format!("{} healthy online instance(s) will remain.", remaining));
+            if before.ephemeral {
+                lines.push("An ephemeral instance whose process is still running re-registers on its next heartbeat.".to_string());
+            }
+            if remaining == 0 {
+                lines.push("WARNING: no instance will be left to serve this service!".to_string());
+            }
+        }
+        InstanceEditKind::Metadata => {
+            lines.extend(metadata_changes(&before.metadata, &after.metadata));
+            lines.push("Consumers see the new metadata on their next instance list update.".to_string());
+        }
+    }
+    lines
+}
+
+impl App<'_> {
+    fn instance_edit_open(&mut self, kind: InstanceEditKind) {
+        let Some(browser) = self.service_instances.as_ref() else {
+            return;
+        };
+        let Some(before) = browser.selected().cloned() else {
+            return;
+        };
+        let input = match kind {
+            InstanceEditKind::Enabled | InstanceEditKind::Deregister => TextArea::default(),
+            InstanceEditKind::Weight => TextArea::new(vec![format!("{:?}", before.weight)]),
+            InstanceEditKind::Metadata => {
+                let mut lines: Vec<String> = before.metadata.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                lines.sort();
+                TextArea::new(lines)
+            }
+        };
+        let mut edit = InstanceEdit {
+            ns_id: browser.ns_id.clone(),
+            service: browser.service.clone(),
+            group: browser.group.clone(),
+            kind,
+            before,
+            after: None,
+            impact: vec![],
+            input,
+            request: None,
+        };
+        edit.input.move_cursor(CursorMove::Bottom);
+        edit.input.move_cursor(CursorMove::End);
+        self.instance_edit = Some(edit);
+        self.current_screen = CurrentScreen::InstanceEdit;
+        // 上下线和注销没有要编辑的内容，直接进入确认
+        if !edit_kind_has_input(kind) {
+            self.instance_edit_submit();
+        }
+    }
+
+    pub fn instance_toggle_enabled(&mut self) {
+        self.instance_edit_open(InstanceEditKind::Enabled);
+    }
+
+    pub fn instance_edit_weight(&mut self) {
+        self.instance_edit_open(InstanceEditKind::Weight);
+    }
+
+    pub fn instance_edit_metadata(&mut self) {
+        self.instance_edit_open(InstanceEditKind::Metadata);
+    }
+
+    pub fn instance_deregister(&mut self) {
+        self.instance_edit_open(InstanceEditKind::Deregister);
+    }
+
+    pub fn instance_edit_input(&mut self, input: Input) {
+        if let Some(edit) = self.instance_edit.as_mut().filter(|e| !e.confirming()) {
+            edit.input.input(input);
+        }
+    }
+
+    // 校验输入并进入确认页
+    pub fn instance_edit_submit(&mut self) {
+        let Some(edit) = self.instance_edit.as_mut().filter(|e| !e.confirming()) else {
+            return;
+        };
+        let text = edit.input.lines().join("\n");
+        let mut after = edit.before.clone();
+        let parsed = match edit.kind {
+            InstanceEditKind::Enabled => {
+                after.enabled = !after.enabled;
+                Ok(())
+            }
+            InstanceEditKind::Deregister => Ok(()),
+            InstanceEditKind::Weight => parse_weight(&text).map(|w| after.weight = w),
+            InstanceEditKind::Metadata => parse_metadata(&text).map(|m| after.metadata = m),
+        };
+        if let Err(message) = parsed {
+            self.error_message = Some(message);
+            return;
+        }
+        if edit_kind_has_input(edit.kind)
+            && after.weight == edit.before.weight
+            && after.metadata == edit.before.metadata
+        {
+            self.info_message = Some("Nothing changed".to_string());
+            return;
+        }
+        let instances = self.service_instances.as_ref().map(|b| b.instances.as_slice()).unwrap_or_default();
+        edit.impact = describe_impact(edit.kind, &edit.before, &after, instances);
+        edit.after = Some(after);
+    }
+
+    // 确认页返回编辑，编辑中或上下线则关闭弹窗
+    pub fn instance_edit_back(&mut self) {
+        let Some(edit) = self.instance_edit.as_mut().filter(|e| e.request.is_none()) else {
+            return;
+        };
+        if edit.confirming() && edit_kind_has_input(edit.kind) {
+            edit.after = None;
+            return;
+        }
+        self.instance_edit_close();
+    }
+
+    pub fn instance_edit_close(&mut self) {
+        self.instance_edit = None;
+        self.current_screen = CurrentScreen::ServiceInstances;
+    }
+
+    pub fn instance_edit_confirm(&mut self) {
+        let Some(edit) = self.instance_edit.as_ref().filter(|e| e.request.is_none()) else {
+            return;
+        };
+        let Some(after) = edit.after.clone() else {
+            return;
+        };
+        let client = self.client.clone();
+        let (ns_id, service, group) = (edit.ns_id.clone(), edit.service.clone(), edit.group.clone());
+        let id = if edit.kind == InstanceEditKind::Deregister {
+            self.spawn("Deregistering instance", async move {
+                ApiEvent::InstanceUpdated(client.deregister_instance(&ns_id, &service, &group, &after).await)
+            })
+        } else {
+            self.spawn("Updating instance", async move {
+                ApiEvent::InstanceUpdated(client.update_instance(&ns_id, &service, &group, &after).await)
+            })
+        };
+        if let Some(edit) = self.instance_edit.as_mut() {
+            edit.request = Some(id);
+        }
+    }
+
+    // 成功后关闭弹窗并立即刷新实例列表，失败时保留弹窗
+    pub(super) fn on_instance_updated(&mut self, id: u64, result: Result<(), NacosError>) {
+        let Some(edit) = self.instance_edit.as_mut().filter(|e| e.request == Some(id)) else {
+            return;
+        };
+        edit.request = None;
+        match result {
+            Ok(()) => {
+                let action = if edit.kind == InstanceEditKind::Deregister { "deregistered" } else { "updated" };
+                self.info_message = Some(format!("Instance {}:{} {}", edit.before.ip, edit.before.port, action));
+                self.instance_edit_close();
+                self.instance_refresh();
+            }
+            Err(err) if edit.kind == InstanceEditKind::Deregister => self.show_error("Deregister instance", err),
+            Err(err) => self.show_error("Update instance", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance(ip: &str, weight: f64, healthy: bool) -> Instance {
+        Instance {
+            ip: ip.to_string(),
+            port: 8080,
+            weight,
+            healthy,
+            enabled: true,
+            ephemeral: true,
+            clusterName: "DEFAULT".to_string(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(parse_weight(" 0.5 "), Ok(0.5));
+        assert!(parse_weight("-1").is_err());
+        assert!(parse_weight("abc").is_err());
+
+        let metadata = parse_metadata("version = 2.0\n\nzone=a=b\n").unwrap();
+        assert_eq!(metadata.get("version").map(String::as_str), Some("2.0"));
+        assert_eq!(metadata.get("zone").map(String::as_str), Some("a=b"));
+        assert!(parse_metadata("novalue").is_err());
+        assert!(parse_metadata("a=1\na=2").is_err());
+    }
+
+    #[test]
+    fn test_describe_impact() {
+        let instances = vec![instance("10.0.0.1", 1.0, true), instance("10.0.0.2", 1.0, true), instance("10.0.0.3", 1.0, false)];
+
+        let mut after = instances[0].clone();
+        after.weight = 3.0;
+        let impact = describe_impact(InstanceEditKind::Weight, &instances[0], &after, &instances);
+        assert_eq!(impact[1], "Traffic share: 50.0% -> 75.0%");
+
+        after.weight = 0.0;
+        let impact = describe_impact(InstanceEditKind::Weight, &instances[0], &after, &instances);
+        assert_eq!(impact[1], "Traffic share: 50.0% -> 0.0%");
+        assert_eq!(impact.len(), 3);
+
+        let mut after = instances[1].clone();
+        after.enabled = false;
+        let impact = describe_impact(InstanceEditKind::Enabled, &instances[1], &after, &instances[1..]);
+        assert_eq!(impact[1], "0 healthy online instance(s) will remain.");
+        assert!(impact[2].starts_with("WARNING"));
+
+        let mut before = instances[0].clone();
+        before.metadata = parse_metadata("a=1\nb=2").unwrap();
+        let mut after = before.clone();
+        after.metadata = parse_metadata("a=1\nb=3\nc=4").unwrap();
+        let impact = describe_impact(InstanceEditKind::Metadata, &before, &after, &instances);
+        assert_eq!(&impact[..2], ["~ b: 2 -> 3", "+ c=4"]);
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local};
+use ratatui::widgets::ListState;
+use tokio::task::JoinSet;
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::api::config::{config_md5, ConfigListener};
+use crate::api::error::NacosError;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+
+// 按配置查看监听的客户端，或按客户端 ip 反查它监听的配置
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ListenerQuery {
+    Config { ns_id: String, data_id: String, group: String },
+    Ip(String),
+}
+
+pub struct ListenerRow {
+    pub listener: ConfigListener,
+    pub server_md5: Option<String>, // 服务端当前内容的 md5，None 表示配置已被删除
+}
+
+impl ListenerRow {
+    // 客户端的 md5 与服务端不一致，说明还没拿到最近一次发布
+    pub fn stale(&self) -> bool {
+        self.server_md5.as_deref() != Some(self.listener.md5.as_str())
+    }
+}
+
+pub struct ListenerBrowser {
+    pub query: ListenerQuery,
+    back: Vec<ListenerQuery>, // enter 钻取前的查询，esc 逐个返回
+    pub rows: Vec<ListenerRow>,
+    pub list_state: ListState,
+    pub updated_at: Option<DateTime<Local>>,
+    pub ip_input: Option<TextArea<'static>>, // 输入要反查的客户端 ip
+    pub request: Option<u64>,
+}
+
+impl ListenerBrowser {
+    fn new(query: ListenerQuery) -> ListenerBrowser {
+        ListenerBrowser {
+            query,
+            back: vec![],
+            rows: vec![],
+            list_state: ListState::default(),
+            updated_at: None,
+            ip_input: None,
+            request: None,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&ListenerRow> {
+        self.list_state.selected().and_then(|i| self.rows.get(i))
+    }
+
+    pub fn stale_count(&self) -> usize {
+        self.rows.iter().filter(|r| r.stale()).count()
+    }
+
+    fn set_rows(&mut self, rows: Vec<ListenerRow>) {
+        let selected = self.list_state.selected().map_or(0, |i| i.min(rows.len().saturating_sub(1)));
+        self.list_state.select((!rows.is_empty()).then_some(selected));
+        self.rows = rows;
+    }
+
+    fn push(&mut self, query: ListenerQuery) {
+        let previous = std::mem::replace(&mut self.query, query);
+        self.back.push(previous);
+        self.rows.clear();
+        self.list_state.select(None);
+        self.updated_at = None;
+    }
+}
+
+impl App<'_> {
+    // 查看器打开时看查看器里的配置，否则看列表中选中的配置
+    pub fn config_open_listeners(&mut self) {
+        let query = match (self.config_viewer.as_ref(), self.config_ns_id.as_ref(), self.selected_config()) {
+            (Some(v), _, _) => ListenerQuery::Config {
+                ns_id: v.ns_id.clone(),
+                data_id: v.data_id.clone(),
+                group: v.group.clone(),
+            },
+            (None, Some(ns_id), Some(c)) => ListenerQuery::Config {
+                ns_id: ns_id.clone(),
+                data_id: c.data_id.clone(),
+                group: c.group.clone(),
+            },
+            _ => return,
+        };
+        self.config_listeners = Some(ListenerBrowser::new(query));
+        self.current_screen = CurrentScreen::ConfigListeners;
+        self.listener_reload();
+    }
+
+    pub fn listener_close(&mut self) {
+        self.config_listeners = None;
+        self.current_screen = if self.config_viewer.is_some() {
+            CurrentScreen::ConfigView
+        } else {
+            CurrentScreen::Main
+        };
+    }
+
+    // 钻取过时先回到上一个查询，否则关闭
+    pub fn listener_back(&mut self) {
+        let Some(browser) = self.config_listeners.as_mut() else {
+            return;
+        };
+        match browser.back.pop() {
+            Some(query) => {
+                browser.query = query;
+                browser.rows.clear();
+                browser.list_state.select(None);
+                browser.updated_at = None;
+                self.listener_reload();
+            }
+            None => self.listener_close(),
+        }
+    }
+
+    // 每个配置取一次服务端内容算 md5，与客户端上报的 md5 比较
+    pub fn listener_reload(&mut self) {
+        let Some(browser) = self.config_listeners.as_ref() else {
+            return;
+        };
+        let client = self.client.clone();
+        let query = browser.query.clone();
+        let id = self.spawn("Loading listeners", async move {
+            let listeners = match &query {
+                ListenerQuery::Config { ns_id, data_id, group } => {
+                    client.config_listeners(Some(ns_id.as_str()), data_id, group).await
+                }
+                ListenerQuery::Ip(ip) => client.listening_configs(ip).await,
+            };
+            let listeners = match listeners {
+                Ok(listeners) => listeners,
+                Err(err) => return ApiEvent::ListenersLoaded(Err(err)),
+            };
+            let mut keys: Vec<(String, String, String)> = listeners
+                .iter()
+                .map(|l| (l.tenant.clone(), l.data_id.clone(), l.group.clone()))
+                .collect();
+            keys.sort();
+            keys.dedup();
+            let mut tasks = JoinSet::new();
+            for key in keys {
+                let client = client.clone();
+                tasks.spawn(async move {
+                    let result = client.get_config(Some(key.0.as_str()), &key.1, &key.2).await;
+                    (key, result)
+                });
+            }
+            let mut server_md5: HashMap<(String, String, String), Option<String>> = HashMap::new();
+            while let Some(joined) = tasks.join_next().await {
+                // 任务失败时不能把剩下的配置当成已删除
+                let (key, result) = match joined {
+                    Ok(joined) => joined,
+                    Err(err) => return ApiEvent::ListenersLoaded(Err(NacosError::Network(err.to_string()))),
+                };
+                let md5 = match result {
+                    Ok(content) => Some(config_md5(&content)),
+                    Err(NacosError::NotFound(_)) => None,
+                    Err(err) => return ApiEvent::ListenersLoaded(Err(err)),
+                };
+                server_md5.insert(key, md5);
+            }
+            let rows = listeners
+                .into_iter()
+                .map(|l| {
+                    let md5 = server_md5
+                        .get(&(l.tenant.clone(), l.data_id.clone(), l.group.clone()))
+                        .cloned()
+                        .flatten();
+                    (l, md5)
+                })
+                .collect();
+            ApiEvent::ListenersLoaded(Ok(rows))
+        });
+        if let Some(browser) = self.config_listeners.as_mut() {
+            browser.request = Some(id);
+        }
+    }
+
+    pub(super) fn on_listeners_loaded(&mut self, id: u64, result: Result<Vec<(ConfigListener, Option<String>)>, NacosError>) {
+        let Some(browser) = self.config_listeners.as_mut().filter(|b| b.request == Some(id)) else {
+            return;
+        };
+        browser.request = None;
+        match result {
+            Ok(rows) => {
+                browser.set_rows(
+                    rows.into_iter()
+                        .map(|(listener, server_md5)| ListenerRow { listener, server_md5 })
+                        .collect(),
+                );
+                browser.updated_at = Some(Local::now());
+            }
+            Err(err) => self.show_error("Load listeners", err),
+        }
+    }
+
+    // 按配置查看时反查选中客户端监听的配置，按 ip 查看时查看选中配置的所有监听者
+    pub fn listener_drill(&mut self) {
+        let Some(browser) = self.config_listeners.as_mut() else {
+            return;
+        };
+        let Some(row) = browser.selected() else {
+            return;
+        };
+        let query = match browser.query {
+            ListenerQuery::Config { .. } => ListenerQuery::Ip(row.listener.ip.clone()),
+            ListenerQuery::Ip(_) => ListenerQuery::Config {
+                ns_id: row.listener.tenant.clone(),
+                data_id: row.listener.data_id.clone(),
+                group: row.listener.group.clone(),
+            },
+        };
+        browser.push(query);
+        self.listener_reload();
+    }
+
+    // 默认填入选中行的 ip
+    pub fn listener_ip_open(&mut self) {
+        if let Some(browser) = self.config_listeners.as_mut() {
+            let ip = browser.selected().map(|r| r.listener.ip.clone()).unwrap_or_default();
+            let mut input = TextArea::new(vec![ip]);
+            input.move_cursor(CursorMove::End);
+            browser.ip_input = Some(input);
+        }
+    }
+
+    pub fn listener_ip_close(&mut self) {
+        if let Some(browser) = self.config_listeners.as_mut() {
+            browser.ip_input = None;
+        }
+    }
+
+    pub fn listener_ip_input(&mut self, input: Input) {
+        if let Some(input_area) = self.config_listeners.as_mut().and_then(|b| b.ip_input.as_mut()) {
+            input_area.input(input);
+        }
+    }
+
+    pub fn listener_ip_submit(&mut self) {
+        let Some(browser) = self.config_listeners.as_mut() else {
+            return;
+        };
+        let Some(input) = browser.ip_input.take() else {
+            return;
+        };
+        let ip = input.lines().join("").trim().to_string();
+        if ip.is_empty() {
+            return;
+        }
+        browser.push(ListenerQuery::Ip(ip));
+        self.listener_reload();
+    }
+
+    pub fn listener_select_next(&mut self) {
+        if let Some(browser) = self.config_listeners.as_mut() {
+            let next = browser.list_state.selected().map_or(0, |i| i + 1);
+            if next < browser.rows.len() {
+                browser.list_state.select(Some(next));
+            }
+        }
+    }
+
+    pub fn listener_select_prev(&mut self) {
+        if let Some(browser) = self.config_listeners.as_mut()
+            && let Some(i) = browser.list_state.selected()
+        {
+            browser.list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(ip: &str, md5: &str, server_md5: Option<&str>) -> ListenerRow {
+        ListenerRow {
+            listener: ConfigListener {
+                ip: ip.to_string(),
+                data_id: "app.yml".to_string(),
+                group: "DEFAULT_GROUP".to_string(),
+                tenant: "dev".to_string(),
+                md5: md5.to_string(),
+            },
+            server_md5: server_md5.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_stale_listeners() {
+        let mut browser = ListenerBrowser::new(ListenerQuery::Ip("10.0.0.1".to_string()));
+        browser.set_rows(vec![
+            row("10.0.0.1", "new", Some("new")),
+            row("10.0.0.2", "old", Some("new")),
+            row("10.0.0.3", "old", None),
+        ]);
+        assert!(!browser.rows[0].stale());
+        assert_eq!(browser.stale_count(), 2);
+        assert_eq!(browser.selected().unwrap().listener.ip, "10.0.0.1");
+
+        browser.push(ListenerQuery::Ip("10.0.0.2".to_string()));
+        assert!(browser.rows.is_empty());
+        assert_eq!(browser.back, vec![ListenerQuery::Ip("10.0.0.1".to_string())]);
+    }
+}
@@ -0,0 +1,176 @@
+use similar::{Algorithm, DiffOp, capture_diff_slices};
+
+use crate::app::diff::{DiffTag, DiffView};
+
+// 发布冲突时的三方对比：开始编辑时的版本(base)、本地修改(mine)和服务端当前版本(server)
+pub struct MergeView {
+    pub mine: String,
+    pub server: String,
+    pub merged: String,   // 自动合并的结果，冲突处带 <<<<<<< 标记
+    pub conflicts: usize, // 无法自动合并的块数
+    pub base_lines: Vec<(String, DiffTag)>,   // Delete 表示被任意一方修改
+    pub mine_lines: Vec<(String, DiffTag)>,   // Insert 表示相对 base 新增或修改
+    pub server_lines: Vec<(String, DiffTag)>,
+    pub scroll: usize,
+    pub height: usize, // 上次渲染时的可视行数
+}
+
+impl MergeView {
+    pub fn new(base: &str, mine: &str, server: &str) -> MergeView {
+        let (merged, conflicts) = merge3(base, mine, server);
+        let mine_diff = DiffView::new("base", base, "mine", mine);
+        let server_diff = DiffView::new("base", base, "server", server);
+
+        // base 中被任意一方删除或修改的行
+        let mut changed = vec![false; base.lines().count()];
+        for line in mine_diff.lines.iter().chain(&server_diff.lines) {
+            if let (DiffTag::Delete, Some(no)) = (line.tag, line.old_no) {
+                changed[no - 1] = true;
+            }
+        }
+        let base_lines = base
+            .lines()
+            .zip(changed)
+            .map(|(line, changed)| (line.to_string(), if changed { DiffTag::Delete } else { DiffTag::Equal }))
+            .collect();
+        let side_lines = |diff: &DiffView| {
+            diff.lines
+                .iter()
+                .filter(|l| l.tag != DiffTag::Delete)
+                .map(|l| (l.text.clone(), l.tag))
+                .collect()
+        };
+
+        MergeView {
+            mine: mine.to_string(),
+            server: server.to_string(),
+            merged,
+            conflicts,
+            base_lines,
+            mine_lines: side_lines(&mine_diff),
+            server_lines: side_lines(&server_diff),
+            scroll: 0,
+            height: 1,
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        let rows = self.base_lines.len().max(self.mine_lines.len()).max(self.server_lines.len());
+        rows.saturating_sub(self.height)
+    }
+
+    pub fn set_height(&mut self, height: usize) {
+        self.height = height;
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+
+    pub fn scroll_down(&mut self, n: usize) {
+        self.scroll = (self.scroll + n).min(self.max_scroll());
+    }
+
+    pub fn scroll_up(&mut self, n: usize) {
+        self.scroll = self.scroll.saturating_sub(n);
+    }
+}
+
+// 按行做三方合并：只有一方修改的块直接采用，两方改得不同的块保留两边内容并加冲突标记
+pub fn merge3(base: &str, mine: &str, server: &str) -> (String, usize) {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let mine_lines: Vec<&str> = mine.split_inclusive('\n').collect();
+    let server_lines: Vec<&str> = server.split_inclusive('\n').collect();
+    let to_mine = matched_lines(&base_lines, &mine_lines);
+    let to_server = matched_lines(&base_lines, &server_lines);
+
+    let mut merged = String::new();
+    let mut conflicts = 0;
+    let (mut b, mut m, mut s) = (0, 0, 0);
+    loop {
+        // 下一个三方都没有改动的 base 行作为同步点
+        let sync = (b..base_lines.len()).find_map(|i| Some((i, to_mine[i]?, to_server[i]?)));
+        let (nb, nm, ns) = sync.unwrap_or((base_lines.len(), mine_lines.len(), server_lines.len()));
+        if (nb, nm, ns) != (b, m, s) {
+            let (base_chunk, mine_chunk, server_chunk) =
+                (&base_lines[b..nb], &mine_lines[m..nm], &server_lines[s..ns]);
+            if mine_chunk == base_chunk || mine_chunk == server_chunk {
+                merged.extend(server_chunk.iter().copied());
+            } else if server_chunk == base_chunk {
+                merged.extend(mine_chunk.iter().copied());
+            } else {
+                conflicts += 1;
+                push_block(&mut merged, "<<<<<<< mine\n", mine_chunk);
+                push_block(&mut merged, "=======\n", server_chunk);
+                merged.push_str(">>>>>>> server\n");
+            }
+        }
+        if sync.is_none() {
+            break;
+        }
+        merged.push_str(base_lines[nb]);
+        (b, m, s) = (nb + 1, nm + 1, ns + 1);
+    }
+    (merged, conflicts)
+}
+
+// 冲突块里每一段都以换行结尾，标记才能独占一行
+fn push_block(merged: &mut String, marker: &str, lines: &[&str]) {
+    merged.push_str(marker);
+    for line in lines {
+        merged.push_str(line);
+    }
+    if !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+}
+
+// base 每一行在另一版本中对应的行号，被修改或删除的行为 None
+fn matched_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matched = vec![None; base.len()];
+    for op in capture_diff_slices(Algorithm::Myers, base, other) {
+        if let DiffOp::Equal { old_index, new_index, len } = op {
+            for i in 0..len {
+                matched[old_index + i] = Some(new_index + i);
+            }
+        }
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_non_overlapping() {
+        let base = "a: 1\nb: 2\nc: 3\n";
+        let mine = "a: 10\nb: 2\nc: 3\n";
+        let server = "a: 1\nb: 2\nc: 30\nd: 4\n";
+        assert_eq!(merge3(base, mine, server), ("a: 10\nb: 2\nc: 30\nd: 4\n".to_string(), 0));
+
+        // 两边改得一样不算冲突
+        assert_eq!(merge3(base, mine, mine), (mine.to_string(), 0));
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let base = "a: 1\nb: 2\n";
+        let mine = "a: 10\nb: 2\n";
+        let server = "a: 11\nb: 2\n";
+        let (merged, conflicts) = merge3(base, mine, server);
+        assert_eq!(conflicts, 1);
+        assert_eq!(merged, "<<<<<<< mine\na: 10\n=======\na: 11\n>>>>>>> server\nb: 2\n");
+    }
+
+    #[test]
+    fn test_merge_view_marks_changes() {
+        let view = MergeView::new("a\nb\nc\n", "a\nB\nc\n", "a\nb\nc\nd\n");
+        let tags = |lines: &[(String, DiffTag)]| lines.iter().map(|(_, tag)| *tag).collect::<Vec<_>>();
+        assert_eq!(tags(&view.base_lines), vec![DiffTag::Equal, DiffTag::Delete, DiffTag::Equal]);
+        assert_eq!(tags(&view.mine_lines), vec![DiffTag::Equal, DiffTag::Insert, DiffTag::Equal]);
+        assert_eq!(
+            tags(&view.server_lines),
+            vec![DiffTag::Equal, DiffTag::Equal, DiffTag::Equal, DiffTag::Insert]
+        );
+        assert_eq!(view.merged, "a\nB\nc\nd\n");
+        assert_eq!(view.conflicts, 0);
+    }
+}
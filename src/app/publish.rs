@@ -0,0 +1,33 @@
+use crate::api::config::ConfigMeta;
+use crate::app::diff::DiffView;
+
+// 编辑完成、等待用户看过差异后确认的发布
+pub struct PendingPublish {
+    pub ns_id: String,
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub base: String,    // 开始编辑时服务端的内容，发布时用它的 md5 做 cas
+    pub content: String, // 将要发布的内容
+    pub diff: DiffView,
+    pub create: bool,         // 服务端没有这个配置，发布时不带 casMd5
+    pub meta: Option<ConfigMeta>, // 新建配置时的 appName/标签/描述
+    pub request: Option<u64>, // 正在发布的请求
+}
+
+impl PendingPublish {
+    pub fn new(ns_id: &str, data_id: &str, group: &str, type_: &str, original: &str, content: &str) -> PendingPublish {
+        PendingPublish {
+            ns_id: ns_id.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            type_: type_.to_string(),
+            base: original.to_string(),
+            content: content.to_string(),
+            diff: DiffView::new("server", original, "pending", content),
+            create: false,
+            meta: None,
+            request: None,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::api::error::NacosError;
+use crate::app::instance_edit::{parse_inline_metadata, parse_weight};
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::instance_list_resp::Instance;
+
+// 注册表单的输入框，顺序与 InstanceRegisterForm::fields 一致
+pub const REGISTER_FIELDS: [&str; 8] = [
+    "service_name",
+    "group",
+    "ip",
+    "port",
+    "cluster",
+    "weight",
+    "ephemeral (true/false, ephemeral instances expire without heartbeats)",
+    "metadata (key=value, comma separated)",
+];
+
+// 手动注册实例的表单，与新建命名空间的弹窗一样 tab 切换输入框、enter 提交
+pub struct InstanceRegisterForm {
+    pub ns_id: String,
+    pub fields: Vec<TextArea<'static>>,
+    pub focus: usize,
+    pub request: Option<u64>,
+}
+
+impl InstanceRegisterForm {
+    fn new(ns_id: &str, service: &str, group: &str) -> InstanceRegisterForm {
+        // 冒烟测试注册的实例默认为持久实例，不会因为没有心跳被剔除
+        let values = [service, group, "", "", "DEFAULT", "1.0", "false", ""];
+        let fields = values
+            .iter()
+            .map(|value| {
+                let mut textarea = TextArea::new(vec![value.to_string()]);
+                textarea.move_cursor(CursorMove::End);
+                textarea
+            })
+            .collect();
+        InstanceRegisterForm {
+            ns_id: ns_id.to_string(),
+            fields,
+            focus: if service.is_empty() { 0 } else { 2 },
+            request: None,
+        }
+    }
+
+    fn value(&self, index: usize) -> String {
+        self.fields[index].lines().join("").trim().to_string()
+    }
+
+    // 校验后返回 (服务名, 分组, 要注册的实例)
+    pub fn parse(&self) -> Result<(String, String, Instance), String> {
+        let service = self.value(0);
+        if service.is_empty() {
+            return Err("service_name is required".to_string());
+        }
+        let group = Some(self.value(1)).filter(|g| !g.is_empty()).unwrap_or_else(|| "DEFAULT_GROUP".to_string());
+        let ip = self.value(2);
+        if ip.is_empty() {
+            return Err("ip is required".to_string());
+        }
+        let port: u16 = self.value(3).parse().map_err(|_| format!("Invalid port: {}", self.value(3)))?;
+        let cluster = Some(self.value(4)).filter(|c| !c.is_empty()).unwrap_or_else(|| "DEFAULT".to_string());
+        let weight = parse_weight(&self.value(5))?;
+        let ephemeral = match self.value(6).as_str() {
+            "true" => true,
+            "false" => false,
+            other => return Err(format!("ephemeral must be true or false, got: {}", other)),
+        };
+        let metadata = parse_inline_metadata(&self.value(7))?;
+        let instance = Instance {
+            ip,
+            port,
+            weight,
+            healthy: true,
+            enabled: true,
+            ephemeral,
+            clusterName: cluster,
+            metadata,
+        };
+        Ok((service, group, instance))
+    }
+}
+
+impl App<'_> {
+    // 在实例列表里打开时填好当前服务，在服务列表里打开时填选中的服务
+    pub fn instance_register_open(&mut self) {
+        let (ns_id, service, group) = match self.service_instances.as_ref() {
+            Some(browser) => (browser.ns_id.clone(), browser.service.clone(), browser.group.clone()),
+            None => {
+                let Some(ns_id) = self.service_ns_id.clone() else {
+                    return;
+                };
+                let (service, group) = self
+                    .selected_service()
+                    .map_or((String::new(), "DEFAULT_GROUP".to_string()), |s| (s.name.clone(), s.group.clone()));
+                (ns_id, service, group)
+            }
+        };
+        self.instance_register = Some(InstanceRegisterForm::new(&ns_id, &service, &group));
+        self.current_screen = CurrentScreen::InstanceRegister;
+    }
+
+    pub fn instance_register_close(&mut self) {
+        self.instance_register = None;
+        self.current_screen = if self.service_instances.is_some() {
+            CurrentScreen::ServiceInstances
+        } else {
+            CurrentScreen::Main
+        };
+    }
+
+    pub fn instance_register_focus(&mut self, forward: bool) {
+        if let Some(form) = self.instance_register.as_mut() {
+            let len = form.fields.len();
+            form.focus = if forward { (form.focus + 1) % len } else { (form.focus + len - 1) % len };
+        }
+    }
+
+    pub fn instance_register_input(&mut self, input: Input) {
+        if let Some(form) = self.instance_register.as_mut() {
+            form.fields[form.focus].input(input);
+        }
+    }
+
+    pub fn instance_register_submit(&mut self) {
+        let Some(form) = self.instance_register.as_ref().filter(|f| f.request.is_none()) else {
+            return;
+        };
+        let (service, group, instance) = match form.parse() {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                self.error_message = Some(message);
+                return;
+            }
+        };
+        let client = self.client.clone();
+        let ns_id = form.ns_id.clone();
+        let id = self.spawn("Registering instance", async move {
+            ApiEvent::InstanceRegistered(client.register_instance(&ns_id, &service, &group, &instance).await)
+        });
+        if let Some(form) = self.instance_register.as_mut() {
+            form.request = Some(id);
+        }
+    }
+
+    // 成功后关闭表单并刷新当前的实例列表或服务列表
+    pub(super) fn on_instance_registered(&mut self, id: u64, result: Result<(), NacosError>) {
+        let Some(form) = self.instance_register.as_mut().filter(|f| f.request == Some(id)) else {
+            return;
+        };
+        form.request = None;
+        match result {
+            Ok(()) => {
+                self.info_message = Some(format!("Instance {}:{} registered", form.value(2), form.value(3)));
+                self.instance_register_close();
+                if self.service_instances.is_some() {
+                    self.instance_refresh();
+                } else {
+                    self.service_reload();
+                }
+            }
+            Err(err) => self.show_error("Register instance", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_register_form() {
+        let mut form = InstanceRegisterForm::new("dev", "order-service", "");
+        assert!(form.parse().is_err());
+
+        form.fields[2] = TextArea::new(vec!["10.0.0.1".to_string()]);
+        form.fields[3] = TextArea::new(vec!["8080".to_string()]);
+        form.fields[7] = TextArea::new(vec!["version=1.0, tags=a,b, zone=a,".to_string()]);
+        let (service, group, instance) = form.parse().unwrap();
+        assert_eq!((service.as_str(), group.as_str()), ("order-service", "DEFAULT_GROUP"));
+        assert_eq!((instance.port, instance.weight, instance.ephemeral), (8080, 1.0, false));
+        assert_eq!(instance.metadata.get("zone").map(String::as_str), Some("a"));
+        assert_eq!(instance.metadata.get("tags").map(String::as_str), Some("a,b"));
+
+        form.fields[3] = TextArea::new(vec!["70000".to_string()]);
+        assert!(form.parse().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use ratatui::widgets::ListState;
+use regex::{Regex, RegexBuilder};
+use tui_textarea::{Input, TextArea};
+
+use crate::api::error::NacosError;
+use crate::app::viewer::ConfigViewer;
+use crate::app::{App, ConfigItem, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::config_page_resp::ConfigInfo;
+
+// 打开查看器时匹配行上方保留的行数
+const SCROLL_CONTEXT: usize = 5;
+
+// 匹配的一行，text 与查看器里显示的一样把 tab 替换成了空格
+pub struct SearchLine {
+    pub line_no: usize,
+    pub text: String,
+    pub ranges: Vec<(usize, usize)>, // 匹配部分的字节区间
+}
+
+pub struct SearchHit {
+    pub config: usize, // App::config_contents 中的下标
+    pub lines: Vec<SearchLine>,
+}
+
+// 在命名空间所有配置的内容里搜索，内容取一次后缓存，r 重新获取
+pub struct ContentSearch {
+    pub ns_id: String,
+    pub input: TextArea<'static>,
+    pub regex: bool,
+    pub case_sensitive: bool,
+    pub editing: bool, // 焦点在输入框，否则在结果列表
+    pub hits: Vec<SearchHit>,
+    pub rows: Vec<(usize, Option<usize>)>, // 列表的每一行：(hits 下标, 匹配行下标)，None 为配置标题行
+    pub list_state: ListState,
+    pub searched: Option<String>, // 上次搜索的词，没有搜索过为 None
+    pub request: Option<u64>,
+}
+
+impl ContentSearch {
+    fn new(ns_id: &str) -> ContentSearch {
+        ContentSearch {
+            ns_id: ns_id.to_string(),
+            input: TextArea::default(),
+            regex: false,
+            case_sensitive: false,
+            editing: true,
+            hits: vec![],
+            rows: vec![],
+            list_state: ListState::default(),
+            searched: None,
+            request: None,
+        }
+    }
+
+    pub fn query(&self) -> String {
+        self.input.lines().join("")
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.hits.iter().map(|h| h.lines.len()).sum()
+    }
+
+    fn matcher(&self) -> Result<Regex, String> {
+        build_matcher(&self.query(), self.regex, self.case_sensitive)
+    }
+
+    // 在匹配行之间移动，跳过配置标题行
+    fn step(&mut self, forward: bool) {
+        let current = self.list_state.selected();
+        let next = if forward {
+            let from = current.map_or(0, |i| i + 1);
+            (from..self.rows.len()).find(|i| self.rows[*i].1.is_some())
+        } else {
+            current.and_then(|i| (0..i).rev().find(|i| self.rows[*i].1.is_some()))
+        };
+        if next.is_some() {
+            self.list_state.select(next);
+        }
+    }
+}
+
+pub fn build_matcher(query: &str, regex: bool, case_sensitive: bool) -> Result<Regex, String> {
+    let pattern = if regex { query.to_string() } else { regex::escape(query) };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+// 逐行匹配，行号从 0 开始
+pub fn find_lines(matcher: &Regex, content: &str) -> Vec<SearchLine> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(line_no, line)| {
+            let text = line.replace('\t', "    ");
+            let ranges: Vec<(usize, usize)> = matcher
+                .find_iter(&text)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect();
+            (!ranges.is_empty()).then_some(SearchLine { line_no, text, ranges })
+        })
+        .collect()
+}
+
+// 查看器自己的搜索只支持字面匹配，从搜索结果打开时按内容搜索的规则标出匹配
+fn highlight_matches(viewer: &mut ConfigViewer, matcher: &Regex) {
+    viewer.matches = viewer
+        .lines
+        .iter()
+        .enumerate()
+        .flat_map(|(l, text)| matcher.find_iter(text).filter(|m| !m.is_empty()).map(move |m| (l, m.start(), m.end())))
+        .collect();
+}
+
+impl App<'_> {
+    pub fn config_search_open(&mut self) {
+        let Some(ns_id) = self.config_ns_id.clone() else {
+            return;
+        };
+        self.config_search = Some(ContentSearch::new(&ns_id));
+        self.current_screen = CurrentScreen::ConfigSearch;
+        if self.config_contents.as_ref().is_none_or(|(id, _)| *id != ns_id) {
+            self.search_fetch_contents();
+        }
+    }
+
+    // 取到的内容仍然缓存在 App::config_contents 里，下次搜索直接使用
+    pub fn config_search_close(&mut self) {
+        self.config_search = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn search_fetch_contents(&mut self) {
+        let Some(search) = self.config_search.as_ref() else {
+            return;
+        };
+        let client = self.client.clone();
+        let ns_id = search.ns_id.clone();
+        let id = self.spawn("Loading config contents", async move {
+            ApiEvent::ConfigContentsLoaded(client.fetch_all_configs(&ns_id).await)
+        });
+        self.config_contents = None;
+        if let Some(search) = self.config_search.as_mut() {
+            search.request = Some(id);
+        }
+    }
+
+    pub(super) fn on_config_contents_loaded(&mut self, id: u64, result: Result<Vec<ConfigInfo>, NacosError>) {
+        let Some(search) = self.config_search.as_mut().filter(|s| s.request == Some(id)) else {
+            return;
+        };
+        search.request = None;
+        match result {
+            Ok(configs) => {
+                let ns_id = search.ns_id.clone();
+                self.config_contents = Some((ns_id, configs.into_iter().map(ConfigItem::from).collect()));
+                // 内容还没加载完时已经按了搜索
+                if self.config_search.as_ref().is_some_and(|s| s.searched.is_some()) {
+                    self.search_run();
+                }
+            }
+            Err(err) => self.show_error("Load config contents", err),
+        }
+    }
+
+    pub fn search_input(&mut self, input: Input) {
+        if let Some(search) = self.config_search.as_mut() {
+            search.input.input(input);
+        }
+    }
+
+    pub fn search_toggle_regex(&mut self) {
+        if let Some(search) = self.config_search.as_mut() {
+            search.regex = !search.regex;
+            if search.searched.is_some() {
+                self.search_run();
+            }
+        }
+    }
+
+    pub fn search_toggle_case(&mut self) {
+        if let Some(search) = self.config_search.as_mut() {
+            search.case_sensitive = !search.case_sensitive;
+            if search.searched.is_some() {
+                self.search_run();
+            }
+        }
+    }
+
+    pub fn search_toggle_focus(&mut self) {
+        if let Some(search) = self.config_search.as_mut() {
+            search.editing = !search.editing || search.rows.is_empty();
+        }
+    }
+
+    // 用当前的搜索词和开关在缓存的内容里搜索，有结果时焦点移到结果列表
+    pub fn search_run(&mut self) {
+        let Some(search) = self.config_search.as_mut() else {
+            return;
+        };
+        if search.query().is_empty() {
+            return;
+        }
+        let matcher = match search.matcher() {
+            Ok(matcher) => matcher,
+            Err(err) => {
+                self.error_message = Some(format!("Invalid regex: {}", err.lines().last().unwrap_or_default()));
+                return;
+            }
+        };
+        search.searched = Some(search.query());
+        let Some((_, configs)) = self.config_contents.as_ref() else {
+            // 内容加载完成后再搜索
+            return;
+        };
+
+        search.hits = configs
+            .iter()
+            .enumerate()
+            .map(|(config, c)| SearchHit { config, lines: find_lines(&matcher, &c.content) })
+            .filter(|hit| !hit.lines.is_empty())
+            .collect();
+        search.rows = search
+            .hits
+            .iter()
+            .enumerate()
+            .flat_map(|(i, hit)| std::iter::once((i, None)).chain((0..hit.lines.len()).map(move |l| (i, Some(l)))))
+            .collect();
+        search.list_state.select(None);
+        search.step(true);
+        search.editing = search.rows.is_empty();
+    }
+
+    pub fn search_select_next(&mut self) {
+        if let Some(search) = self.config_search.as_mut() {
+            search.step(true);
+        }
+    }
+
+    pub fn search_select_prev(&mut self) {
+        if let Some(search) = self.config_search.as_mut() {
+            search.step(false);
+        }
+    }
+
+    // 在查看器里打开选中的配置，定位到匹配的行；关闭查看器后回到搜索结果
+    pub fn search_open_hit(&mut self) {
+        let (Some(search), Some((_, configs))) = (self.config_search.as_ref(), self.config_contents.as_ref()) else {
+            return;
+        };
+        let Some((hit, Some(line))) = search.list_state.selected().and_then(|i| search.rows.get(i)).copied() else {
+            return;
+        };
+        let Ok(matcher) = search.matcher() else {
+            return;
+        };
+        let hit = &search.hits[hit];
+        let Some(config) = configs.get(hit.config) else {
+            return;
+        };
+
+        let mut viewer = ConfigViewer::new(&search.ns_id, &config.data_id, &config.group, &config.format);
+        viewer.set_content(&config.content);
+        viewer.search = search.query();
+        highlight_matches(&mut viewer, &matcher);
+        let line_no = hit.lines[line].line_no;
+        viewer.current_match = viewer.matches.iter().position(|(l, _, _)| *l == line_no);
+        // 查看器的高度渲染后才知道，先把匹配行放在靠上的位置
+        viewer.scroll = line_no.saturating_sub(SCROLL_CONTEXT);
+
+        // 缓存的内容可能已经过期：先显示缓存，再从服务端重新获取，拿到之后才开始监听变化
+        let client = self.client.clone();
+        let (ns_id, data_id, group) = (viewer.ns_id.clone(), viewer.data_id.clone(), viewer.group.clone());
+        let id = self.spawn("Loading config", async move {
+            let tenant = Some(ns_id.as_str()).filter(|id| !id.is_empty());
+            ApiEvent::ConfigContentLoaded(client.get_config(tenant, &data_id, &group).await)
+        });
+        viewer.request = Some(id);
+        self.config_viewer = Some(viewer);
+        self.current_screen = CurrentScreen::ConfigView;
+    }
+
+    // 查看器重新获取到的内容与缓存不同时，更新缓存并按搜索词重新标出匹配
+    pub(super) fn search_sync_viewer(&mut self) {
+        let (Some(search), Some(viewer), Some((ns_id, configs))) =
+            (self.config_search.as_ref(), self.config_viewer.as_mut(), self.config_contents.as_mut())
+        else {
+            return;
+        };
+        if *ns_id != viewer.ns_id {
+            return;
+        }
+        if let Some(config) = configs.iter_mut().find(|c| c.data_id == viewer.data_id && c.group == viewer.group) {
+            config.content = viewer.content.clone();
+        }
+        if viewer.search == search.query()
+            && let Ok(matcher) = search.matcher()
+        {
+            highlight_matches(viewer, &matcher);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::api::client::NacosClient;
+
+    #[test]
+    fn test_find_lines() {
+        let content = "db:\n  host: DB.internal\n\turl: jdbc://db.internal:3306\nflag: true";
+        let matcher = build_matcher("db.internal", false, false).unwrap();
+        let lines = find_lines(&matcher, content);
+        assert_eq!(lines.iter().map(|l| l.line_no).collect::<Vec<_>>(), vec![1, 2]);
+        // tab 替换成空格后的位置
+        assert_eq!(lines[1].ranges, vec![(16, 27)]);
+
+        let matcher = build_matcher("db.internal", false, true).unwrap();
+        assert_eq!(find_lines(&matcher, content).len(), 1);
+
+        let matcher = build_matcher(r"^\s*host:", true, false).unwrap();
+        assert_eq!(find_lines(&matcher, content)[0].line_no, 1);
+
+        // 不是正则模式时按字面匹配
+        let matcher = build_matcher("d.", false, false).unwrap();
+        assert!(find_lines(&matcher, content).is_empty());
+        assert!(build_matcher("(", true, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_open_hit_refetches_content() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(NacosClient::new("http://127.0.0.1:1", "nacos", "nacos"), tx);
+        let item = ConfigItem {
+            id: "1".to_string(),
+            data_id: "app.yml".to_string(),
+            group: "DEFAULT_GROUP".to_string(),
+            format: "yaml".to_string(),
+            content: "key: 1\n".to_string(),
+            app_name: String::new(),
+            marked: false,
+        };
+        app.config_contents = Some(("dev".to_string(), vec![item]));
+        let mut search = ContentSearch::new("dev");
+        search.input = TextArea::new(vec!["key".to_string()]);
+        app.config_search = Some(search);
+        app.search_run();
+        app.search_select_next();
+        app.search_open_hit();
+
+        // 先显示缓存的内容，服务端的内容回来后更新缓存和匹配
+        let viewer = app.config_viewer.as_ref().unwrap();
+        assert_eq!(viewer.content, "key: 1\n");
+        let id = viewer.request.unwrap();
+        app.on_api_event(id, ApiEvent::ConfigContentLoaded(Ok("a: 0\nkey: 2\n".to_string())));
+        let viewer = app.config_viewer.as_ref().unwrap();
+        assert_eq!(viewer.content, "a: 0\nkey: 2\n");
+        assert_eq!(viewer.matches, vec![(1, 0, 3)]);
+        assert_eq!(app.config_contents.as_ref().unwrap().1[0].content, "a: 0\nkey: 2\n");
+    }
+}
@@ -0,0 +1,279 @@
+use std::cmp::Ordering;
+
+use tokio::task::JoinSet;
+
+use crate::api::error::NacosError;
+use crate::app::App;
+use crate::event::ApiEvent;
+use crate::resp::service_detail_resp::ServiceDetail;
+use crate::resp::service_list_resp::{ServiceListResp, ServiceView};
+
+// 服务列表每页条数
+pub const SERVICE_PAGE_SIZE: u32 = 20;
+
+// 排序只作用于当前页，Nacos 的服务列表接口不支持排序
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ServiceSort {
+    Name,
+    Group,
+    Clusters,
+    Instances,
+    Healthy,
+    Threshold,
+}
+
+impl ServiceSort {
+    const ALL: [ServiceSort; 6] = [
+        ServiceSort::Name,
+        ServiceSort::Group,
+        ServiceSort::Clusters,
+        ServiceSort::Instances,
+        ServiceSort::Healthy,
+        ServiceSort::Threshold,
+    ];
+
+    pub fn next(self) -> ServiceSort {
+        let i = ServiceSort::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        ServiceSort::ALL[(i + 1) % ServiceSort::ALL.len()]
+    }
+
+    fn compare(self, a: &ServiceItem, b: &ServiceItem) -> Ordering {
+        match self {
+            ServiceSort::Name => a.name.cmp(&b.name),
+            ServiceSort::Group => a.group.cmp(&b.group),
+            ServiceSort::Clusters => a.cluster_count.cmp(&b.cluster_count),
+            ServiceSort::Instances => a.ip_count.cmp(&b.ip_count),
+            ServiceSort::Healthy => a.healthy_count.cmp(&b.healthy_count),
+            ServiceSort::Threshold => a.protect_threshold.partial_cmp(&b.protect_threshold).unwrap_or(Ordering::Equal),
+        }
+        // 值相同时按名称排，保证顺序稳定
+        .then_with(|| a.name.cmp(&b.name))
+    }
+}
+
+pub struct ServiceItem {
+    pub name: String,
+    pub group: String,
+    pub cluster_count: i32,
+    pub ip_count: i32,
+    pub healthy_count: i32,
+    pub protect_threshold: Option<f32>, // 服务定义加载失败时为 None
+    pub triggered: bool,                // 健康实例比例低于保护阈值，保护已触发
+}
+
+impl From<ServiceView> for ServiceItem {
+    fn from(service: ServiceView) -> ServiceItem {
+        ServiceItem {
+            name: service.name,
+            group: service.groupName,
+            cluster_count: service.clusterCount,
+            ip_count: service.ipCount,
+            healthy_count: service.healthyInstanceCount,
+            protect_threshold: None,
+            triggered: service.triggerFlag.as_deref() == Some("true"),
+        }
+    }
+}
+
+impl App<'_> {
+    pub fn service_tab_ns_id(&self) -> Option<&str> {
+        self.namespace_list
+            .get(self.service_current_tab)
+            .map(|ns| ns.ns_id.as_str())
+    }
+
+    // 切换到服务菜单时，列表还不是当前 tab 的命名空间就重新加载
+    pub fn service_open(&mut self) {
+        if self.service_ns_id.as_deref() != self.service_tab_ns_id() {
+            self.service_page_no = 1;
+            self.service_reload();
+        }
+    }
+
+    pub fn service_reload(&mut self) {
+        self.service_ns_id = self.service_tab_ns_id().map(|id| id.to_string());
+        self.service_load_page(self.service_page_no.max(1));
+    }
+
+    fn service_load_page(&mut self, page_no: u32) {
+        let Some(ns_id) = self.service_ns_id.clone() else {
+            return;
+        };
+        let client = self.client.clone();
+        let id = self.spawn("Loading services", async move {
+            let page = match client.list_services(&ns_id, "", "", page_no, SERVICE_PAGE_SIZE).await {
+                Ok(page) => page,
+                Err(err) => return ApiEvent::ServicesLoaded(page_no, Err(err)),
+            };
+            // 列表接口没有保护阈值，逐个取服务定义
+            let mut tasks = JoinSet::new();
+            for (i, service) in page.serviceList.iter().enumerate() {
+                let (client, ns_id) = (client.clone(), ns_id.clone());
+                let (name, group) = (service.name.clone(), service.groupName.clone());
+                tasks.spawn(async move { (i, client.get_service(&ns_id, &name, &group).await.ok()) });
+            }
+            let mut details: Vec<Option<ServiceDetail>> = page.serviceList.iter().map(|_| None).collect();
+            while let Some(Ok((i, detail))) = tasks.join_next().await {
+                details[i] = detail;
+            }
+            ApiEvent::ServicesLoaded(page_no, Ok((page, details)))
+        });
+        self.service_request = Some(id);
+    }
+
+    pub(super) fn on_services_loaded(
+        &mut self,
+        id: u64,
+        page_no: u32,
+        result: Result<(ServiceListResp, Vec<Option<ServiceDetail>>), NacosError>,
+    ) {
+        if self.service_request != Some(id) {
+            return;
+        }
+        self.service_request = None;
+        let (page, details) = match result {
+            Ok(result) => result,
+            Err(err) => return self.show_error("Load services", err),
+        };
+        let selected = self.selected_service().map(|s| (s.name.clone(), s.group.clone()));
+        self.service_page_no = page_no;
+        self.service_total = page.count.max(0) as usize;
+        self.service_list = page
+            .serviceList
+            .into_iter()
+            .zip(details)
+            .map(|(service, detail)| {
+                let mut item = ServiceItem::from(service);
+                item.protect_threshold = detail.map(|d| d.protectThreshold);
+                item
+            })
+            .collect();
+        self.service_sort_list();
+        // 刷新后尽量保持选中同一个服务
+        let index = selected
+            .and_then(|(name, group)| self.service_list.iter().position(|s| s.name == name && s.group == group))
+            .or((!self.service_list.is_empty()).then_some(0));
+        self.service_list_state.select(index);
+        self.service_filter_refresh();
+    }
+
+    pub fn service_page_count(&self) -> u32 {
+        (self.service_total as u32).div_ceil(SERVICE_PAGE_SIZE).max(1)
+    }
+
+    pub fn service_next_page(&mut self) {
+        if self.service_page_no < self.service_page_count() {
+            self.service_load_page(self.service_page_no + 1);
+        }
+    }
+
+    pub fn service_prev_page(&mut self) {
+        if self.service_page_no > 1 {
+            self.service_load_page(self.service_page_no - 1);
+        }
+    }
+
+    pub fn service_next_tab(&mut self) {
+        if self.service_current_tab + 1 < self.namespace_list.len() {
+            self.service_current_tab += 1;
+            self.service_page_no = 1;
+            self.service_reload();
+        }
+    }
+
+    pub fn service_prev_tab(&mut self) {
+        if self.service_current_tab > 0 {
+            self.service_current_tab -= 1;
+            self.service_page_no = 1;
+            self.service_reload();
+        }
+    }
+
+    pub fn service_select_next(&mut self) {
+        if self.service_filter.is_some() {
+            self.service_filter_step(true, false);
+            return;
+        }
+        let next = self.service_list_state.selected().map_or(0, |i| i + 1);
+        if next < self.service_list.len() {
+            self.service_list_state.select(Some(next));
+        }
+    }
+
+    pub fn service_select_prev(&mut self) {
+        if self.service_filter.is_some() {
+            self.service_filter_step(false, false);
+            return;
+        }
+        if let Some(i) = self.service_list_state.selected() {
+            self.service_list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    pub fn selected_service(&self) -> Option<&ServiceItem> {
+        self.service_list_state
+            .selected()
+            .and_then(|i| self.service_list.get(i))
+    }
+
+    // o 切换排序列，O 切换升降序
+    pub fn service_cycle_sort(&mut self) {
+        self.service_sort = self.service_sort.next();
+        self.service_resort();
+    }
+
+    pub fn service_reverse_sort(&mut self) {
+        self.service_sort_desc = !self.service_sort_desc;
+        self.service_resort();
+    }
+
+    fn service_resort(&mut self) {
+        let selected = self.service_list_state.selected();
+        let key = selected.and_then(|i| self.service_list.get(i)).map(|s| (s.name.clone(), s.group.clone()));
+        self.service_sort_list();
+        if let Some((name, group)) = key {
+            let index = self.service_list.iter().position(|s| s.name == name && s.group == group);
+            self.service_list_state.select(index);
+        }
+        self.service_filter_refresh();
+    }
+
+    fn service_sort_list(&mut self) {
+        let (sort, desc) = (self.service_sort, self.service_sort_desc);
+        self.service_list.sort_by(|a, b| {
+            let order = sort.compare(a, b);
+            if desc { order.reverse() } else { order }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, ip_count: i32, protect_threshold: Option<f32>) -> ServiceItem {
+        ServiceItem {
+            name: name.to_string(),
+            group: "DEFAULT_GROUP".to_string(),
+            cluster_count: 1,
+            ip_count,
+            healthy_count: 0,
+            protect_threshold,
+            triggered: false,
+        }
+    }
+
+    #[test]
+    fn test_service_sort() {
+        let mut items = [item("b", 2, Some(0.5)), item("a", 2, None), item("c", 1, Some(0.0))];
+        items.sort_by(|a, b| ServiceSort::Instances.compare(a, b));
+        let names: Vec<&str> = items.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["c", "a", "b"]);
+
+        // 没有阈值的排在最前
+        items.sort_by(|a, b| ServiceSort::Threshold.compare(a, b));
+        let names: Vec<&str> = items.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "c", "b"]);
+        assert_eq!(ServiceSort::Threshold.next(), ServiceSort::Name);
+    }
+}
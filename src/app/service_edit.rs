@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::api::error::NacosError;
+use crate::api::service::ServiceSpec;
+use crate::app::instance_edit::{metadata_changes, parse_inline_metadata};
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::service_detail_resp::ServiceDetail;
+
+// 服务定义表单的输入框，顺序与 ServiceEditForm::fields 一致
+pub const SERVICE_FIELDS: [&str; 5] = [
+    "service_name",
+    "group",
+    "protect_threshold (0 to 1)",
+    "metadata (key=value, comma separated)",
+    "selector (label expression, empty for none)",
+];
+
+// 修改时服务名和分组不能改，焦点从保护阈值开始
+const FIRST_EDITABLE_FIELD: usize = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ServiceEditMode {
+    Create,
+    Update,
+    Delete,
+}
+
+// 新建、修改、删除服务定义的弹窗：先填表单，再看修改前后的对比，确认后提交
+pub struct ServiceEditForm {
+    pub ns_id: String,
+    pub mode: ServiceEditMode,
+    pub fields: Vec<TextArea<'static>>,
+    pub focus: usize,
+    pub before: Option<ServiceSpec>, // 修改和删除时从服务端加载的定义
+    pub after: Option<ServiceSpec>,  // 确认页要提交的定义，编辑中为 None
+    pub summary: Vec<String>,
+    pub counts: Option<(i32, i32)>, // 列表里的 (健康实例数, 实例数)，用于提示保护阈值的影响
+    pub request: Option<u64>,
+}
+
+impl ServiceEditForm {
+    fn new(ns_id: &str, mode: ServiceEditMode, counts: Option<(i32, i32)>) -> ServiceEditForm {
+        let mut form = ServiceEditForm {
+            ns_id: ns_id.to_string(),
+            mode,
+            fields: vec![],
+            focus: if mode == ServiceEditMode::Create { 0 } else { FIRST_EDITABLE_FIELD },
+            before: None,
+            after: None,
+            summary: vec![],
+            counts,
+            request: None,
+        };
+        form.fill(&ServiceSpec { group: "DEFAULT_GROUP".to_string(), ..ServiceSpec::default() });
+        form
+    }
+
+    pub fn confirming(&self) -> bool {
+        self.after.is_some()
+    }
+
+    // 修改和删除要等服务定义加载完成
+    pub fn loading(&self) -> bool {
+        self.mode != ServiceEditMode::Create && self.before.is_none()
+    }
+
+    fn fill(&mut self, spec: &ServiceSpec) {
+        let mut metadata: Vec<String> = spec.metadata.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        metadata.sort();
+        let values = [
+            spec.name.clone(),
+            spec.group.clone(),
+            spec.protect_threshold.to_string(),
+            metadata.join(", "),
+            spec.selector.clone().unwrap_or_default(),
+        ];
+        self.fields = values
+            .into_iter()
+            .map(|value| {
+                let mut textarea = TextArea::new(vec![value]);
+                textarea.move_cursor(CursorMove::End);
+                textarea
+            })
+            .collect();
+    }
+
+    pub fn editable(&self, index: usize) -> bool {
+        self.mode == ServiceEditMode::Create || index >= FIRST_EDITABLE_FIELD
+    }
+
+    fn value(&self, index: usize) -> String {
+        self.fields[index].lines().join("").trim().to_string()
+    }
+
+    pub fn parse(&self) -> Result<ServiceSpec, String> {
+        let name = self.value(0);
+        if name.is_empty() {
+            return Err("service_name is required".to_string());
+        }
+        if name.contains("@@") {
+            return Err("service_name must not contain @@".to_string());
+        }
+        let group = Some(self.value(1)).filter(|g| !g.is_empty()).unwrap_or_else(|| "DEFAULT_GROUP".to_string());
+        let protect_threshold = parse_threshold(&self.value(2))?;
+        let metadata = parse_inline_metadata(&self.value(3))?;
+        let selector = Some(self.value(4)).filter(|s| !s.is_empty());
+        Ok(ServiceSpec { name, group, protect_threshold, metadata, selector })
+    }
+}
+
+pub fn parse_threshold(text: &str) -> Result<f32, String> {
+    let threshold: f32 = text.trim().parse().map_err(|_| format!("Invalid protect threshold: {}", text.trim()))?;
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("Protect threshold must be between 0 and 1".to_string());
+    }
+    Ok(threshold)
+}
+
+fn selector_text(selector: &Option<String>) -> String {
+    selector.as_ref().map_or("none".to_string(), |e| format!("label: {}", e))
+}
+
+// 确认页的前后对比；counts 为 (健康实例数, 实例数)
+pub fn describe_service_change(
+    mode: ServiceEditMode,
+    before: Option<&ServiceSpec>,
+    after: &ServiceSpec,
+    counts: Option<(i32, i32)>,
+) -> Vec<String> {
+    let service = format!("{}@@{}", after.group, after.name);
+    let mut lines = vec![];
+    match (mode, before) {
+        (ServiceEditMode::Delete, _) => {
+            lines.push(format!("Delete service {}", service));
+            lines.push(format!("protect threshold: {:.2}", after.protect_threshold));
+            lines.extend(metadata_changes(&after.metadata, &HashMap::new()));
+            lines.push(format!("selector: {}", selector_text(&after.selector)));
+            if let Some((_, total)) = counts.filter(|(_, total)| *total > 0) {
+                lines.push(format!(
+                    "WARNING: {} instance(s) still registered; Nacos refuses to delete a service with instances.",
+                    total
+                ));
+            }
+            return lines;
+        }
+        (ServiceEditMode::Update, Some(before)) => {
+            lines.push(format!("Update service {}", service));
+            if before.protect_threshold == after.protect_threshold {
+                lines.push(format!("protect threshold: {:.2} (unchanged)", after.protect_threshold));
+            } else {
+                lines.push(format!(
+                    "protect threshold: {:.2} -> {:.2}",
+                    before.protect_threshold, after.protect_threshold
+                ));
+            }
+            let changes = metadata_changes(&before.metadata, &after.metadata);
+            if changes.is_empty() {
+                lines.push("metadata: unchanged".to_string());
+            }
+            lines.extend(changes);
+            if before.selector == after.selector {
+                lines.push(format!("selector: {} (unchanged)", selector_text(&after.selector)));
+            } else {
+                lines.push(format!(
+                    "selector: {} -> {}",
+                    selector_text(&before.selector),
+                    selector_text(&after.selector)
+                ));
+            }
+        }
+        _ => {
+            lines.push(format!("Create service {}", service));
+            lines.push(format!("protect threshold: {:.2}", after.protect_threshold));
+            lines.extend(metadata_changes(&HashMap::new(), &after.metadata));
+            lines.push(format!("selector: {}", selector_text(&after.selector)));
+        }
+    }
+    // 健康实例比例低于保护阈值时，Nacos 会把不健康的实例也返回给消费者
+    if let Some((healthy, total)) = counts.filter(|(_, total)| *total > 0) {
+        let ratio = healthy as f32 / total as f32;
+        if ratio < after.protect_threshold {
+            lines.push(format!(
+                "WARNING: healthy ratio {:.2} is below the threshold: protection triggers and consumers also get unhealthy instances.",
+                ratio
+            ));
+        }
+    }
+    lines
+}
+
+impl App<'_> {
+    pub fn service_create_open(&mut self) {
+        let Some(ns_id) = self.service_ns_id.clone() else {
+            return;
+        };
+        self.service_edit = Some(ServiceEditForm::new(&ns_id, ServiceEditMode::Create, None));
+        self.current_screen = CurrentScreen::ServiceEdit;
+    }
+
+    pub fn service_update_open(&mut self) {
+        self.service_edit_load(ServiceEditMode::Update);
+    }
+
+    pub fn service_delete_open(&mut self) {
+        self.service_edit_load(ServiceEditMode::Delete);
+    }
+
+    // 修改和删除先加载服务定义，加载完成前弹窗显示 loading
+    fn service_edit_load(&mut self, mode: ServiceEditMode) {
+        let (Some(ns_id), Some(service)) = (self.service_ns_id.clone(), self.selected_service()) else {
+            return;
+        };
+        let (name, group) = (service.name.clone(), service.group.clone());
+        let counts = Some((service.healthy_count, service.ip_count));
+        let client = self.client.clone();
+        let request_ns_id = ns_id.clone();
+        let id = self.spawn("Loading service", async move {
+            ApiEvent::ServiceDefinitionLoaded(client.get_service(&request_ns_id, &name, &group).await)
+        });
+        let mut form = ServiceEditForm::new(&ns_id, mode, counts);
+        form.request = Some(id);
+        self.service_edit = Some(form);
+        self.current_screen = CurrentScreen::ServiceEdit;
+    }
+
+    pub(super) fn on_service_definition_loaded(&mut self, id: u64, result: Result<ServiceDetail, NacosError>) {
+        let Some(form) = self.service_edit.as_mut().filter(|f| f.request == Some(id)) else {
+            return;
+        };
+        form.request = None;
+        match result {
+            Ok(detail) => {
+                let spec = ServiceSpec::from(detail);
+                form.fill(&spec);
+                if form.mode == ServiceEditMode::Delete {
+                    form.summary = describe_service_change(form.mode, None, &spec, form.counts);
+                    form.after = Some(spec.clone());
+                }
+                form.before = Some(spec);
+            }
+            Err(err) => {
+                self.service_edit_close();
+                self.show_error("Load service", err);
+            }
+        }
+    }
+
+    pub fn service_edit_close(&mut self) {
+        self.service_edit = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    pub fn service_edit_focus(&mut self, forward: bool) {
+        let Some(form) = self.service_edit.as_mut() else {
+            return;
+        };
+        let len = form.fields.len();
+        loop {
+            form.focus = if forward { (form.focus + 1) % len } else { (form.focus + len - 1) % len };
+            if form.editable(form.focus) {
+                break;
+            }
+        }
+    }
+
+    pub fn service_edit_input(&mut self, input: Input) {
+        if let Some(form) = self.service_edit.as_mut().filter(|f| !f.confirming() && !f.loading()) {
+            form.fields[form.focus].input(input);
+        }
+    }
+
+    // 校验表单并进入确认页
+    pub fn service_edit_review(&mut self) {
+        let Some(form) = self.service_edit.as_mut().filter(|f| !f.confirming() && !f.loading()) else {
+            return;
+        };
+        let spec = match form.parse() {
+            Ok(spec) => spec,
+            Err(message) => {
+                self.error_message = Some(message);
+                return;
+            }
+        };
+        if form.before.as_ref() == Some(&spec) {
+            self.info_message = Some("Nothing changed".to_string());
+            return;
+        }
+        form.summary = describe_service_change(form.mode, form.before.as_ref(), &spec, form.counts);
+        form.after = Some(spec);
+    }
+
+    // 确认页返回表单，删除没有表单直接关闭
+    pub fn service_edit_back(&mut self) {
+        let Some(form) = self.service_edit.as_mut().filter(|f| f.request.is_none()) else {
+            return;
+        };
+        if form.confirming() && form.mode != ServiceEditMode::Delete {
+            form.after = None;
+            return;
+        }
+        self.service_edit_close();
+    }
+
+    pub fn service_edit_confirm(&mut self) {
+        let Some(form) = self.service_edit.as_ref().filter(|f| f.request.is_none()) else {
+            return;
+        };
+        let Some(spec) = form.after.clone() else {
+            return;
+        };
+        let client = self.client.clone();
+        let ns_id = form.ns_id.clone();
+        let mode = form.mode;
+        let id = self.spawn("Saving service", async move {
+            ApiEvent::ServiceSaved(match mode {
+                ServiceEditMode::Create => client.create_service(&ns_id, &spec).await,
+                ServiceEditMode::Update => client.update_service(&ns_id, &spec).await,
+                ServiceEditMode::Delete => client.delete_service(&ns_id, &spec.name, &spec.group).await,
+            })
+        });
+        if let Some(form) = self.service_edit.as_mut() {
+            form.request = Some(id);
+        }
+    }
+
+    // 成功后关闭弹窗并刷新服务列表，失败时保留弹窗
+    pub(super) fn on_service_saved(&mut self, id: u64, result: Result<(), NacosError>) {
+        let Some(form) = self.service_edit.as_mut().filter(|f| f.request == Some(id)) else {
+            return;
+        };
+        form.request = None;
+        let action = match form.mode {
+            ServiceEditMode::Create => "created",
+            ServiceEditMode::Update => "updated",
+            ServiceEditMode::Delete => "deleted",
+        };
+        let name = form.after.as_ref().map(|s| format!("{}@@{}", s.group, s.name)).unwrap_or_default();
+        match result {
+            Ok(()) => {
+                self.info_message = Some(format!("Service {} {}", name, action));
+                self.service_edit_close();
+                self.service_reload();
+            }
+            Err(err) => self.show_error("Save service", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::instance_edit::parse_metadata;
+
+    fn spec(threshold: f32, metadata: &str, selector: Option<&str>) -> ServiceSpec {
+        ServiceSpec {
+            name: "order-service".to_string(),
+            group: "DEFAULT_GROUP".to_string(),
+            protect_threshold: threshold,
+            metadata: parse_metadata(metadata).unwrap(),
+            selector: selector.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_parse_service_form() {
+        let mut form = ServiceEditForm::new("dev", ServiceEditMode::Create, None);
+        assert!(form.parse().is_err());
+        form.fields[0] = TextArea::new(vec!["order-service".to_string()]);
+        form.fields[3] = TextArea::new(vec!["owner=order, tier=1".to_string()]);
+        assert_eq!(form.parse().unwrap(), spec(0.0, "owner=order\ntier=1", None));
+
+        form.fields[2] = TextArea::new(vec!["1.5".to_string()]);
+        assert!(form.parse().is_err());
+        assert!(parse_threshold("abc").is_err());
+        assert_eq!(parse_threshold(" 1 "), Ok(1.0));
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut service = spec(0.5, "tags=a,b\nowner=order", None);
+        service.metadata.insert("empty".to_string(), String::new());
+        let mut form = ServiceEditForm::new("dev", ServiceEditMode::Update, None);
+        form.fill(&service);
+        assert_eq!(form.value(3), "empty=, owner=order, tags=a,b");
+        assert_eq!(form.parse().unwrap(), service);
+    }
+
+    #[test]
+    fn test_describe_service_change() {
+        let before = spec(0.0, "owner=order", None);
+        let after = spec(0.8, "owner=pay", Some("CONSUMER.label.zone = PROVIDER.label.zone"));
+        let lines = describe_service_change(ServiceEditMode::Update, Some(&before), &after, Some((1, 2)));
+        assert_eq!(lines[1], "protect threshold: 0.00 -> 0.80");
+        assert_eq!(lines[2], "~ owner: order -> pay");
+        assert_eq!(lines[3], "selector: none -> label: CONSUMER.label.zone = PROVIDER.label.zone");
+        assert!(lines[4].starts_with("WARNING: healthy ratio 0.50"));
+
+        let lines = describe_service_change(ServiceEditMode::Update, Some(&before), &before, Some((2, 2)));
+        assert_eq!(lines.len(), 4);
+
+        let lines = describe_service_change(ServiceEditMode::Delete, None, &before, Some((0, 3)));
+        assert!(lines.last().unwrap().contains("3 instance(s) still registered"));
+    }
+}
@@ -0,0 +1,280 @@
+use ratatui::widgets::ListState;
+use tui_textarea::{CursorMove, Input};
+
+use crate::api::error::NacosError;
+use crate::app::filter::ListFilter;
+use crate::app::instance::InstanceBrowser;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::subscriber_list_resp::{Subscriber, SubscriberListResp};
+
+pub const SUBSCRIBER_PAGE_SIZE: u32 = 20;
+
+// 服务的订阅者，与实例列表是同一个弹窗的两个标签页；服务端分页，过滤只作用于当前页
+pub struct SubscriberBrowser {
+    pub ns_id: String,
+    pub service: String,
+    pub group: String,
+    pub subscribers: Vec<Subscriber>,
+    pub list_state: ListState,
+    pub total: i32,
+    pub page_no: u32,
+    pub filter: Option<ListFilter>,
+    pub request: Option<u64>,
+}
+
+impl SubscriberBrowser {
+    fn new(ns_id: &str, service: &str, group: &str) -> SubscriberBrowser {
+        SubscriberBrowser {
+            ns_id: ns_id.to_string(),
+            service: service.to_string(),
+            group: group.to_string(),
+            subscribers: vec![],
+            list_state: ListState::default(),
+            total: 0,
+            page_no: 1,
+            filter: None,
+            request: None,
+        }
+    }
+
+    pub fn page_count(&self) -> u32 {
+        (self.total.max(0) as u32).div_ceil(SUBSCRIBER_PAGE_SIZE).max(1)
+    }
+
+    fn is_target(&self, ns_id: &str, service: &str, group: &str) -> bool {
+        self.ns_id == ns_id && self.service == service && self.group == group
+    }
+
+    // 过滤 ip:port、客户端版本、应用名和命名空间
+    fn filter_refresh(&mut self) {
+        let Some(filter) = self.filter.as_mut() else {
+            return;
+        };
+        filter.update(
+            self.subscribers
+                .iter()
+                .map(|s| [s.addrStr.as_str(), s.agent.as_str(), s.app.as_str(), s.namespaceId.as_str()]),
+        );
+        let selected = filter.sync(self.list_state.selected());
+        self.list_state.select(selected);
+    }
+}
+
+impl App<'_> {
+    // 实例列表已打开时看同一个服务，否则看服务列表里选中的服务
+    pub fn service_open_subscribers(&mut self) {
+        let (ns_id, service, group) = match self.service_instances.as_ref() {
+            Some(browser) => (browser.ns_id.clone(), browser.service.clone(), browser.group.clone()),
+            None => {
+                let (Some(ns_id), Some(service)) = (self.service_ns_id.clone(), self.selected_service()) else {
+                    return;
+                };
+                (ns_id, service.name.clone(), service.group.clone())
+            }
+        };
+        self.current_screen = CurrentScreen::ServiceSubscribers;
+        // 切回同一个服务的订阅者标签页时保留页码和过滤
+        if self.service_subscribers.as_ref().is_some_and(|b| b.is_target(&ns_id, &service, &group)) {
+            return;
+        }
+        self.service_subscribers = Some(SubscriberBrowser::new(&ns_id, &service, &group));
+        self.subscriber_load_page(1);
+    }
+
+    // 切到实例标签页，实例列表没打开过时按订阅者的服务打开
+    pub fn subscriber_show_instances(&mut self) {
+        let Some(browser) = self.service_subscribers.as_ref() else {
+            return;
+        };
+        if self.service_instances.is_none() {
+            self.service_instances = Some(InstanceBrowser::new(&browser.ns_id, &browser.service, &browser.group));
+            self.instance_refresh();
+        }
+        self.current_screen = CurrentScreen::ServiceInstances;
+    }
+
+    pub fn subscriber_reload(&mut self) {
+        if let Some(page_no) = self.service_subscribers.as_ref().map(|b| b.page_no) {
+            self.subscriber_load_page(page_no);
+        }
+    }
+
+    fn subscriber_load_page(&mut self, page_no: u32) {
+        let Some(browser) = self.service_subscribers.as_ref() else {
+            return;
+        };
+        let client = self.client.clone();
+        let (ns_id, service, group) = (browser.ns_id.clone(), browser.service.clone(), browser.group.clone());
+        let id = self.spawn("Loading subscribers", async move {
+            let result = client
+                .list_subscribers(&ns_id, &service, &group, page_no, SUBSCRIBER_PAGE_SIZE)
+                .await;
+            ApiEvent::SubscribersLoaded(page_no, result)
+        });
+        if let Some(browser) = self.service_subscribers.as_mut() {
+            browser.request = Some(id);
+        }
+    }
+
+    pub(super) fn on_subscribers_loaded(&mut self, id: u64, page_no: u32, result: Result<SubscriberListResp, NacosError>) {
+        let Some(browser) = self.service_subscribers.as_mut().filter(|b| b.request == Some(id)) else {
+            return;
+        };
+        browser.request = None;
+        match result {
+            Ok(resp) => {
+                // 翻页时回到第一行，刷新同一页时保持选中行
+                let selected = browser
+                    .list_state
+                    .selected()
+                    .filter(|_| browser.page_no == page_no)
+                    .map_or(0, |i| i.min(resp.subscribers.len().saturating_sub(1)));
+                browser.page_no = page_no;
+                browser.total = resp.count;
+                browser.subscribers = resp.subscribers;
+                browser.list_state.select((!browser.subscribers.is_empty()).then_some(selected));
+                browser.filter_refresh();
+            }
+            Err(err) => self.show_error("Load subscribers", err),
+        }
+    }
+
+    pub fn subscriber_next_page(&mut self) {
+        if let Some(browser) = self.service_subscribers.as_ref()
+            && browser.page_no < browser.page_count()
+        {
+            let page_no = browser.page_no + 1;
+            self.subscriber_load_page(page_no);
+        }
+    }
+
+    pub fn subscriber_prev_page(&mut self) {
+        if let Some(browser) = self.service_subscribers.as_ref()
+            && browser.page_no > 1
+        {
+            let page_no = browser.page_no - 1;
+            self.subscriber_load_page(page_no);
+        }
+    }
+
+    pub fn subscriber_select_next(&mut self) {
+        let Some(browser) = self.service_subscribers.as_mut() else {
+            return;
+        };
+        if let Some(filter) = browser.filter.as_mut() {
+            if let Some(selected) = filter.step(true, false) {
+                browser.list_state.select(Some(selected));
+            }
+            return;
+        }
+        let next = browser.list_state.selected().map_or(0, |i| i + 1);
+        if next < browser.subscribers.len() {
+            browser.list_state.select(Some(next));
+        }
+    }
+
+    pub fn subscriber_select_prev(&mut self) {
+        let Some(browser) = self.service_subscribers.as_mut() else {
+            return;
+        };
+        if let Some(filter) = browser.filter.as_mut() {
+            if let Some(selected) = filter.step(false, false) {
+                browser.list_state.select(Some(selected));
+            }
+            return;
+        }
+        if let Some(i) = browser.list_state.selected() {
+            browser.list_state.select(Some(i.saturating_sub(1)));
+        }
+    }
+
+    // 与列表上的过滤一样：/ 输入过滤词，enter 保留结果，n/N 在匹配的行之间循环
+    pub fn subscriber_filter_open(&mut self) {
+        let Some(browser) = self.service_subscribers.as_mut() else {
+            return;
+        };
+        match browser.filter.as_mut() {
+            Some(filter) => {
+                filter.editing = true;
+                filter.input.move_cursor(CursorMove::End);
+            }
+            None => {
+                browser.filter = Some(ListFilter::new());
+                browser.filter_refresh();
+            }
+        }
+    }
+
+    pub fn subscriber_filter_input(&mut self, input: Input) {
+        if let Some(browser) = self.service_subscribers.as_mut()
+            && let Some(filter) = browser.filter.as_mut()
+        {
+            filter.input.input(input);
+            browser.filter_refresh();
+        }
+    }
+
+    pub fn subscriber_filter_submit(&mut self) {
+        let Some(browser) = self.service_subscribers.as_mut() else {
+            return;
+        };
+        if let Some(filter) = browser.filter.as_mut() {
+            filter.editing = false;
+            if filter.query().is_empty() {
+                browser.filter = None;
+            }
+        }
+    }
+
+    pub fn subscriber_filter_clear(&mut self) {
+        if let Some(browser) = self.service_subscribers.as_mut() {
+            browser.filter = None;
+        }
+    }
+
+    pub fn subscriber_filter_step(&mut self, forward: bool) {
+        if let Some(browser) = self.service_subscribers.as_mut()
+            && let Some(selected) = browser.filter.as_mut().and_then(|f| f.step(forward, true))
+        {
+            browser.list_state.select(Some(selected));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tui_textarea::TextArea;
+
+    use super::*;
+
+    fn subscriber(ip: &str, agent: &str, app: &str) -> Subscriber {
+        Subscriber {
+            addrStr: format!("{}:51000", ip),
+            agent: agent.to_string(),
+            app: app.to_string(),
+            namespaceId: "dev".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_subscriber_filter() {
+        let mut browser = SubscriberBrowser::new("dev", "order-service", "DEFAULT_GROUP");
+        browser.total = 41;
+        assert_eq!(browser.page_count(), 3);
+        browser.subscribers = vec![
+            subscriber("10.0.1.1", "Nacos-Java-Client:v2.2.0", "cart"),
+            subscriber("10.0.1.2", "Nacos-Go-Client:v2.1.0", "web"),
+            subscriber("10.0.2.1", "Nacos-Java-Client:v1.4.1", "cart"),
+        ];
+        browser.list_state.select(Some(1));
+
+        let mut filter = ListFilter::new();
+        filter.input = TextArea::new(vec!["java".to_string()]);
+        browser.filter = Some(filter);
+        browser.filter_refresh();
+        assert_eq!(browser.filter.as_ref().unwrap().matches, vec![0, 2]);
+        // 选中行不匹配时跳到第一个匹配
+        assert_eq!(browser.list_state.selected(), Some(0));
+    }
+}
@@ -0,0 +1,234 @@
+use std::path::PathBuf;
+
+use chrono::Local;
+use tui_textarea::{CursorMove, Input, TextArea};
+
+use crate::api::config::ConflictPolicy;
+use crate::api::config_zip::{read_zip, ZipConfig};
+use crate::api::error::NacosError;
+use crate::app::{App, CurrentScreen};
+use crate::event::ApiEvent;
+use crate::resp::config_import_resp::ConfigImportResult;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TransferKind {
+    Export,
+    Import,
+}
+
+// 导出/导入 Nacos 格式的 zip 包
+// 导出：填写保存路径后直接导出；导入：填写路径和冲突策略，先看包里的配置再确认上传
+pub struct ConfigTransfer {
+    pub kind: TransferKind,
+    pub ns_id: String,
+    pub ids: Vec<String>, // 导出标记的配置，为空时导出整个命名空间
+    pub path: TextArea<'static>,
+    pub policy: ConflictPolicy,
+    pub policy_focused: bool,
+    pub entries: Option<Vec<ZipConfig>>, // 导入前读出的包内配置
+    zip: Vec<u8>,
+    pub request: Option<u64>,
+    pub result: Option<ConfigImportResult>,
+}
+
+impl ConfigTransfer {
+    fn new(kind: TransferKind, ns_id: &str, path: &str) -> ConfigTransfer {
+        let mut textarea = TextArea::new(vec![path.to_string()]);
+        textarea.move_cursor(CursorMove::End);
+        ConfigTransfer {
+            kind,
+            ns_id: ns_id.to_string(),
+            ids: vec![],
+            path: textarea,
+            policy: ConflictPolicy::Abort,
+            policy_focused: false,
+            entries: None,
+            zip: vec![],
+            request: None,
+            result: None,
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        expand_home(self.path.lines()[0].trim())
+    }
+}
+
+// 支持 ~/ 开头的路径
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// 与 Nacos 控制台导出的文件名一致
+fn export_file_name(ns_id: &str) -> String {
+    let ns = if ns_id.is_empty() { "public" } else { ns_id };
+    format!("nacos_config_export_{}_{}.zip", ns, Local::now().format("%Y%m%d%H%M%S"))
+}
+
+impl App<'_> {
+    // 有标记的行时只导出标记的配置
+    pub fn config_export_open(&mut self) {
+        let Some(ns_id) = self.config_ns_id.clone() else {
+            return;
+        };
+        let mut transfer = ConfigTransfer::new(TransferKind::Export, &ns_id, &export_file_name(&ns_id));
+        transfer.ids = self.config_list.iter().filter(|c| c.marked).map(|c| c.id.clone()).collect();
+        self.config_transfer = Some(transfer);
+        self.current_screen = CurrentScreen::ConfigTransfer;
+    }
+
+    pub fn config_import_open(&mut self) {
+        let Some(ns_id) = self.config_ns_id.clone() else {
+            return;
+        };
+        self.config_transfer = Some(ConfigTransfer::new(TransferKind::Import, &ns_id, ""));
+        self.current_screen = CurrentScreen::ConfigTransfer;
+    }
+
+    pub fn config_transfer_close(&mut self) {
+        if self.config_transfer.as_ref().is_some_and(|t| t.request.is_some()) {
+            return;
+        }
+        self.config_transfer = None;
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    // 导入预览时返回上一步修改路径或策略
+    pub fn transfer_back(&mut self) {
+        if let Some(transfer) = self.config_transfer.as_mut().filter(|t| t.request.is_none()) {
+            transfer.entries = None;
+            transfer.zip.clear();
+        }
+    }
+
+    pub fn transfer_toggle_focus(&mut self) {
+        if let Some(transfer) = self.config_transfer.as_mut().filter(|t| t.kind == TransferKind::Import) {
+            transfer.policy_focused = !transfer.policy_focused;
+        }
+    }
+
+    pub fn transfer_cycle_policy(&mut self) {
+        if let Some(transfer) = self.config_transfer.as_mut() {
+            transfer.policy = transfer.policy.next();
+        }
+    }
+
+    pub fn transfer_input(&mut self, input: Input) {
+        if let Some(transfer) = self.config_transfer.as_mut()
+            && !transfer.policy_focused
+        {
+            transfer.path.input(input);
+        }
+    }
+
+    pub fn transfer_submit(&mut self) {
+        let Some(transfer) = self.config_transfer.as_mut().filter(|t| t.request.is_none()) else {
+            return;
+        };
+        let path = transfer.path();
+        if path.as_os_str().is_empty() {
+            self.error_message = Some("File path is required".to_string());
+            return;
+        }
+
+        match transfer.kind {
+            TransferKind::Export => {
+                let client = self.client.clone();
+                let (ns_id, ids) = (transfer.ns_id.clone(), transfer.ids.clone());
+                let id = self.spawn("Exporting configs", async move {
+                    let result = match client.export_configs(&ns_id, &ids).await {
+                        Ok(zip) => match read_zip(&zip) {
+                            Ok(configs) => tokio::fs::write(&path, &zip)
+                                .await
+                                .map(|_| (configs.len(), path.display().to_string()))
+                                .map_err(|e| format!("write {}: {}", path.display(), e)),
+                            Err(err) => Err(format!("unexpected export response: {err}")),
+                        },
+                        Err(err) => Err(err.to_string()),
+                    };
+                    ApiEvent::ConfigsExported(result)
+                });
+                if let Some(transfer) = self.config_transfer.as_mut() {
+                    transfer.request = Some(id);
+                }
+            }
+            // 先在本地读出包里的配置给用户确认
+            TransferKind::Import if transfer.entries.is_none() => {
+                let read = std::fs::read(&path)
+                    .map_err(|e| format!("read {}: {}", path.display(), e))
+                    .and_then(|zip| read_zip(&zip).map(|entries| (zip, entries)));
+                match read {
+                    Ok((_, entries)) if entries.is_empty() => {
+                        self.error_message = Some("No configs found in the archive".to_string());
+                    }
+                    Ok((zip, entries)) => {
+                        transfer.zip = zip;
+                        transfer.entries = Some(entries);
+                    }
+                    Err(err) => self.error_message = Some(err),
+                }
+            }
+            TransferKind::Import => {
+                if transfer.result.is_some() {
+                    return;
+                }
+                let client = self.client.clone();
+                let (ns_id, zip, policy) = (transfer.ns_id.clone(), transfer.zip.clone(), transfer.policy);
+                let id = self.spawn("Importing configs", async move {
+                    ApiEvent::ConfigsImported(client.import_configs(&ns_id, &zip, policy).await)
+                });
+                if let Some(transfer) = self.config_transfer.as_mut() {
+                    transfer.request = Some(id);
+                }
+            }
+        }
+    }
+
+    pub(super) fn on_configs_exported(&mut self, id: u64, result: Result<(usize, String), String>) {
+        let Some(transfer) = self.config_transfer.as_mut().filter(|t| t.request == Some(id)) else {
+            return;
+        };
+        transfer.request = None;
+        match result {
+            Ok((count, path)) => {
+                self.info_message = Some(format!("Exported {count} config(s) to {path}"));
+                self.config_transfer_close();
+            }
+            Err(err) => self.error_message = Some(format!("Export configs: {err}")),
+        }
+    }
+
+    pub(super) fn on_configs_imported(&mut self, id: u64, result: Result<ConfigImportResult, NacosError>) {
+        let Some(transfer) = self.config_transfer.as_mut().filter(|t| t.request == Some(id)) else {
+            return;
+        };
+        transfer.request = None;
+        match result {
+            Ok(result) => {
+                let imported = result.succCount;
+                transfer.result = Some(result);
+                // 导入到的就是当前 tab 的命名空间，刷新列表
+                if imported > 0 {
+                    self.config_reload();
+                }
+            }
+            Err(err) => self.show_error("Import configs", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(expand_home("~/a.zip"), PathBuf::from(home).join("a.zip"));
+        assert_eq!(expand_home("./a.zip"), PathBuf::from("./a.zip"));
+        assert!(export_file_name("").starts_with("nacos_config_export_public_"));
+    }
+}
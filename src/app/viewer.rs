@@ -0,0 +1,185 @@
+use chrono::{DateTime, Local};
+use tui_textarea::TextArea;
+
+use crate::app::diff::DiffView;
+use crate::highlight::Format;
+
+// 查看期间别人发布或删除了配置，previous 为变化前查看器里的内容
+pub struct RemoteChange {
+    pub previous: String,
+    pub deleted: bool,
+    pub at: DateTime<Local>,
+}
+
+// 配置内容查看器的状态：滚动位置、换行开关和内容内搜索
+pub struct ConfigViewer {
+    pub ns_id: String,
+    pub data_id: String,
+    pub group: String,
+    pub type_: String,
+    pub format: Format,
+    pub revision: Option<String>, // 查看历史版本时显示在标题里
+    pub content: String, // 服务端的原始内容
+    pub lines: Vec<String>,
+    pub loaded: bool,
+    pub request: Option<u64>, // 正在加载内容的请求
+    pub edit_on_load: bool,   // 内容加载完成后直接打开编辑器
+
+    pub scroll: usize,  // 顶部显示的行
+    pub hscroll: usize, // 不换行时水平滚动的列数
+    pub wrap: bool,
+    pub height: usize, // 上次渲染时的可视行数，翻页和定位搜索结果用
+
+    pub search: String,
+    pub search_input: Option<TextArea<'static>>,
+    pub matches: Vec<(usize, usize, usize)>, // (行, 起始字节, 结束字节)
+    pub current_match: Option<usize>,
+
+    pub remote_change: Option<RemoteChange>,
+    pub remote_diff: Option<DiffView>, // 变化前后的对比
+}
+
+impl ConfigViewer {
+    pub fn new(ns_id: &str, data_id: &str, group: &str, type_: &str) -> ConfigViewer {
+        ConfigViewer {
+            ns_id: ns_id.to_string(),
+            data_id: data_id.to_string(),
+            group: group.to_string(),
+            type_: type_.to_string(),
+            format: Format::detect(type_, data_id),
+            revision: None,
+            content: String::new(),
+            lines: vec![],
+            loaded: false,
+            request: None,
+            edit_on_load: false,
+            scroll: 0,
+            hscroll: 0,
+            wrap: false,
+            height: 1,
+            search: String::new(),
+            search_input: None,
+            matches: vec![],
+            current_match: None,
+            remote_change: None,
+            remote_diff: None,
+        }
+    }
+
+    pub fn set_content(&mut self, content: &str) {
+        self.content = content.to_string();
+        // tab 在终端里宽度不确定，显示时统一替换为空格
+        self.lines = content.lines().map(|line| line.replace('\t', "    ")).collect();
+        self.loaded = true;
+        self.scroll = self.scroll.min(self.max_scroll());
+        let search = std::mem::take(&mut self.search);
+        self.set_search(&search);
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(1)
+    }
+
+    pub fn scroll_down(&mut self, n: usize) {
+        self.scroll = (self.scroll + n).min(self.max_scroll());
+    }
+
+    pub fn scroll_up(&mut self, n: usize) {
+        self.scroll = self.scroll.saturating_sub(n);
+    }
+
+    pub fn scroll_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn scroll_bottom(&mut self) {
+        self.scroll = self.lines.len().saturating_sub(self.height);
+    }
+
+    pub fn scroll_right(&mut self) {
+        if !self.wrap {
+            self.hscroll += 4;
+        }
+    }
+
+    pub fn scroll_left(&mut self) {
+        self.hscroll = self.hscroll.saturating_sub(4);
+    }
+
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.hscroll = 0;
+    }
+
+    // 把指定行滚动到可视区域内，已经可见时不动
+    pub fn scroll_to_line(&mut self, line: usize) {
+        if line < self.scroll || line >= self.scroll + self.height {
+            self.scroll = line.saturating_sub(self.height / 2).min(self.max_scroll());
+        }
+    }
+
+    pub fn start_search(&mut self) {
+        let mut textarea = TextArea::new(vec![self.search.clone()]);
+        textarea.move_cursor(tui_textarea::CursorMove::End);
+        self.search_input = Some(textarea);
+    }
+
+    pub fn submit_search(&mut self) {
+        if let Some(textarea) = self.search_input.take() {
+            let query = textarea.lines().join("");
+            self.set_search(&query);
+            self.next_match();
+        }
+    }
+
+    // 搜索词全小写时忽略大小写（smart case）
+    pub fn set_search(&mut self, query: &str) {
+        self.search = query.to_string();
+        self.matches.clear();
+        self.current_match = None;
+        if query.is_empty() {
+            return;
+        }
+
+        let ignore_case = !query.chars().any(|c| c.is_uppercase());
+        let needle = if ignore_case { query.to_ascii_lowercase() } else { query.to_string() };
+        for (line_no, line) in self.lines.iter().enumerate() {
+            let haystack = if ignore_case { line.to_ascii_lowercase() } else { line.clone() };
+            let mut from = 0;
+            while let Some(pos) = haystack[from..].find(&needle) {
+                let start = from + pos;
+                self.matches.push((line_no, start, start + needle.len()));
+                from = start + needle.len();
+            }
+        }
+    }
+
+    // 从当前位置往后找下一个匹配，到末尾后回到开头
+    pub fn next_match(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let next = match self.current_match {
+            Some(i) => (i + 1) % self.matches.len(),
+            None => self
+                .matches
+                .iter()
+                .position(|(line, _, _)| *line >= self.scroll)
+                .unwrap_or(0),
+        };
+        self.current_match = Some(next);
+        self.scroll_to_line(self.matches[next].0);
+    }
+
+    pub fn prev_match(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let prev = match self.current_match {
+            Some(0) | None => self.matches.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.current_match = Some(prev);
+        self.scroll_to_line(self.matches[prev].0);
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::Local;
+
+use crate::api::watch::{ConfigChange, ConfigWatcher, WatchKey};
+use crate::app::diff::DiffView;
+use crate::app::viewer::{ConfigViewer, RemoteChange};
+use crate::app::App;
+use crate::event::Event;
+
+fn watch_key(viewer: &ConfigViewer) -> WatchKey {
+    WatchKey {
+        tenant: viewer.ns_id.clone(),
+        data_id: viewer.data_id.clone(),
+        group: viewer.group.clone(),
+    }
+}
+
+impl App<'_> {
+    // 查看器里的内容加载或发布后，用它的 md5 监听服务端的变化
+    pub(super) fn config_watch_viewer(&mut self) {
+        let Some(viewer) = self.config_viewer.as_ref().filter(|v| v.loaded && v.revision.is_none()) else {
+            return;
+        };
+        let key = watch_key(viewer);
+        let watcher = self.config_watcher.get_or_insert_with(|| {
+            let tx = self.event_tx.clone();
+            ConfigWatcher::spawn(self.client.clone(), move |change| {
+                let _ = tx.send(Event::ConfigChanged(change));
+            })
+        });
+        watcher.watch(key, &viewer.content);
+    }
+
+    pub(super) fn config_unwatch_viewer(&mut self) {
+        if let (Some(viewer), Some(watcher)) = (self.config_viewer.as_ref(), self.config_watcher.as_ref()) {
+            watcher.unwatch(watch_key(viewer));
+        }
+    }
+
+    // 别人发布后原地更新查看器，保留变化前的内容用于对比；连续多次变化时对比的是最早看到的内容
+    pub fn on_config_changed(&mut self, change: ConfigChange) {
+        let Some(viewer) = self.config_viewer.as_mut().filter(|v| watch_key(v) == change.key) else {
+            return;
+        };
+        let content = change.content.as_deref();
+        if content == Some(viewer.content.as_str()) {
+            return;
+        }
+        // 自己的发布先于发布结果到达时不提示
+        let own_publish = self.config_publish.as_ref().is_some_and(|p| {
+            p.request.is_some()
+                && p.ns_id == viewer.ns_id
+                && p.data_id == viewer.data_id
+                && p.group == viewer.group
+                && Some(p.content.as_str()) == content
+        });
+        if own_publish {
+            return;
+        }
+        let previous = match viewer.remote_change.take() {
+            Some(earlier) => earlier.previous,
+            None => viewer.content.clone(),
+        };
+        if let Some(content) = content {
+            viewer.set_content(content);
+        }
+        viewer.remote_change = Some(RemoteChange {
+            previous,
+            deleted: content.is_none(),
+            at: Local::now(),
+        });
+        self.search_sync_viewer();
+    }
+
+    pub fn viewer_open_remote_diff(&mut self) {
+        let Some(viewer) = self.config_viewer.as_mut() else {
+            return;
+        };
+        let Some(change) = viewer.remote_change.as_ref().filter(|c| !c.deleted) else {
+            return;
+        };
+        let server_label = format!("server ({})", change.at.format("%H:%M:%S"));
+        viewer.remote_diff = Some(DiffView::new("you were viewing", &change.previous, &server_label, &viewer.content));
+    }
+
+    pub fn viewer_dismiss_remote_change(&mut self) {
+        if let Some(viewer) = self.config_viewer.as_mut() {
+            viewer.remote_change = None;
+            viewer.remote_diff = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::api::client::NacosClient;
+
+    fn change(data_id: &str, content: Option<&str>) -> ConfigChange {
+        ConfigChange {
+            key: WatchKey {
+                tenant: "dev".to_string(),
+                data_id: data_id.to_string(),
+                group: "DEFAULT_GROUP".to_string(),
+            },
+            content: content.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_config_changed() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut app = App::new(NacosClient::new("http://127.0.0.1:8848", "nacos", "nacos"), tx);
+        let mut viewer = ConfigViewer::new("dev", "app.yml", "DEFAULT_GROUP", "yaml");
+        viewer.set_content("a: 1\n");
+        app.config_viewer = Some(viewer);
+
+        // 其他配置和相同内容的变化都忽略
+        app.on_config_changed(change("other.yml", Some("b: 1\n")));
+        app.on_config_changed(change("app.yml", Some("a: 1\n")));
+        assert!(app.config_viewer.as_ref().unwrap().remote_change.is_none());
+
+        // 连续两次变化，对比的是最早看到的内容
+        app.on_config_changed(change("app.yml", Some("a: 2\n")));
+        app.on_config_changed(change("app.yml", Some("a: 3\n")));
+        let viewer = app.config_viewer.as_ref().unwrap();
+        assert_eq!(viewer.content, "a: 3\n");
+        assert_eq!(viewer.remote_change.as_ref().unwrap().previous, "a: 1\n");
+
+        // 删除后保留最后的内容
+        app.on_config_changed(change("app.yml", None));
+        let viewer = app.config_viewer.as_ref().unwrap();
+        assert_eq!(viewer.content, "a: 3\n");
+        assert!(viewer.remote_change.as_ref().unwrap().deleted);
+        app.viewer_dismiss_remote_change();
+        assert!(app.config_viewer.as_ref().unwrap().remote_change.is_none());
+    }
+}
@@ -1,3 +1,5 @@
+use std::{fs, path::Path};
+
 use serde::Deserialize;
 use config::Config;
 
@@ -24,4 +26,62 @@ pub fn load_config() -> AppConfig {
         .unwrap();
 
     settings.try_deserialize().unwrap()
-}
\ No newline at end of file
+}
+
+// 新建配置时用来预填内容的本地模板
+// 放在 config/templates 下，文件名（不含扩展名）作为模板名，扩展名作为配置类型
+#[derive(Debug, Clone)]
+pub struct ConfigTemplate {
+    pub name: String,
+    pub type_: String,
+    pub content: String,
+}
+
+pub fn load_templates() -> Vec<ConfigTemplate> {
+    read_templates(Path::new("config/templates"))
+}
+
+// 目录不存在时没有模板，读不了的文件跳过
+fn read_templates(dir: &Path) -> Vec<ConfigTemplate> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut templates: Vec<ConfigTemplate> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let content = fs::read_to_string(&path).ok()?;
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            let type_ = match path.extension().map(|ext| ext.to_string_lossy().to_ascii_lowercase()) {
+                Some(ext) if ext == "yml" => "yaml".to_string(),
+                Some(ext) if ext == "txt" => "text".to_string(),
+                Some(ext) => ext,
+                None => "text".to_string(),
+            };
+            Some(ConfigTemplate { name, type_, content })
+        })
+        .collect();
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_templates() {
+        let dir = std::env::temp_dir().join(format!("lazynacos-templates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("spring.yml"), "server:\n  port: 8080\n").unwrap();
+        std::fs::write(dir.join("logging.properties"), "level=INFO\n").unwrap();
+        std::fs::write(dir.join("notes"), "hello").unwrap();
+
+        let templates = read_templates(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let summary: Vec<(&str, &str)> = templates.iter().map(|t| (t.name.as_str(), t.type_.as_str())).collect();
+        assert_eq!(summary, vec![("logging", "properties"), ("notes", "text"), ("spring", "yaml")]);
+        assert_eq!(templates[2].content, "server:\n  port: 8080\n");
+
+        assert!(read_templates(&dir).is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self as crossterm_event, Event as CrosstermEvent, KeyEvent, KeyEventKind};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::api::config::ConfigListener;
+use crate::api::error::NacosError;
+use crate::api::watch::ConfigChange;
+use crate::resp::config_history_resp::ConfigHistoryPage;
+use crate::resp::config_import_resp::ConfigImportResult;
+use crate::resp::config_page_resp::{ConfigInfo, ConfigPageResp};
+use crate::resp::instance_list_resp::Instance;
+use crate::resp::namespace_list_resp::NamespaceListResp;
+use crate::resp::service_detail_resp::ServiceDetail;
+use crate::resp::service_list_resp::ServiceListResp;
+use crate::resp::subscriber_list_resp::SubscriberListResp;
+
+// 按键输入的轮询间隔，输入线程每隔这段时间检查一次通道是否已关闭
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// 事件循环里所有事件都从同一个通道进来：按键、定时 tick 和异步请求的结果
+pub enum Event {
+    Key(KeyEvent),
+    Tick,
+    // 请求 id 与 App::spawn 返回的 id 对应
+    Api(u64, ApiEvent),
+    // 长轮询监听到的配置变化
+    ConfigChanged(ConfigChange),
+}
+
+// 异步请求完成后回传给 App 的结果
+pub enum ApiEvent {
+    NamespacesLoaded(Result<NamespaceListResp, NacosError>),
+    NamespaceCreated(Result<bool, NacosError>),
+    NamespaceUpdated(Result<bool, NacosError>),
+    NamespaceDeleted(Result<bool, NacosError>),
+    ConfigsLoaded(Result<ConfigPageResp, NacosError>),
+    ConfigContentsLoaded(Result<Vec<ConfigInfo>, NacosError>),
+    ConfigContentLoaded(Result<String, NacosError>),
+    ConfigPublished(Result<bool, NacosError>),
+    ExternalContentLoaded(Result<String, NacosError>),
+    // 发布冲突后拉取的服务端最新内容
+    ConflictContentLoaded(Result<String, NacosError>),
+    HistoryLoaded(Result<ConfigHistoryPage, NacosError>),
+    HistoryCurrentLoaded(Result<String, NacosError>),
+    HistoryDetailLoaded(Result<String, NacosError>),
+    // 监听配置的客户端和对应配置在服务端的当前 md5
+    ListenersLoaded(Result<Vec<(ConfigListener, Option<String>)>, NacosError>),
+    // 与删除弹窗中的配置一一对应
+    ConfigsDeleted(Vec<Result<(), String>>),
+    // 新建前检查配置是否已存在，NotFound 表示可以新建
+    NewConfigChecked(Result<String, NacosError>),
+    // 每个待复制配置的源内容和目标中现有的内容（不存在为 None）
+    ClonePreviewLoaded(Result<Vec<(String, Option<String>)>, NacosError>),
+    ConfigsCloned(Vec<Result<(), String>>),
+    // 导出的配置数和保存的路径
+    ConfigsExported(Result<(usize, String), String>),
+    ConfigsImported(Result<ConfigImportResult, NacosError>),
+    // 页码、服务列表和每个服务的定义（获取失败为 None）
+    ServicesLoaded(u32, Result<(ServiceListResp, Vec<Option<ServiceDetail>>), NacosError>),
+    InstancesLoaded(Result<Vec<Instance>, NacosError>),
+    SubscribersLoaded(u32, Result<SubscriberListResp, NacosError>),
+    // 修改或注销实例
+    InstanceUpdated(Result<(), NacosError>),
+    InstanceRegistered(Result<(), NacosError>),
+    // 修改或删除前加载的服务定义
+    ServiceDefinitionLoaded(Result<ServiceDetail, NacosError>),
+    ServiceSaved(Result<(), NacosError>),
+}
+
+pub struct EventHandler {
+    tx: UnboundedSender<Event>,
+    rx: UnboundedReceiver<Event>,
+    // 外部编辑器运行时暂停读取按键，避免和编辑器抢终端输入
+    input_paused: Arc<AtomicBool>,
+}
+
+impl EventHandler {
+    // 启动读取按键的线程和发送 tick 的定时任务
+    pub fn new(tick_rate: Duration) -> EventHandler {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        // crossterm 的 read 是阻塞的，放在单独的线程里
+        let input_tx = tx.clone();
+        let input_paused = Arc::new(AtomicBool::new(false));
+        let paused = input_paused.clone();
+        std::thread::spawn(move || {
+            while !input_tx.is_closed() {
+                if paused.load(Ordering::Acquire) {
+                    std::thread::sleep(INPUT_POLL_INTERVAL);
+                    continue;
+                }
+                match crossterm_event::poll(INPUT_POLL_INTERVAL) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(_) => break,
+                }
+                if paused.load(Ordering::Acquire) {
+                    continue;
+                }
+                if let Ok(CrosstermEvent::Key(key)) = crossterm_event::read() {
+                    // Skip events that are not KeyEventKind::Press
+                    if key.kind == KeyEventKind::Press && input_tx.send(Event::Key(key)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        let tick_tx = tx.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tick_rate);
+            loop {
+                interval.tick().await;
+                if tick_tx.send(Event::Tick).is_err() {
+                    break;
+                }
+            }
+        });
+
+        EventHandler { tx, rx, input_paused }
+    }
+
+    // 暂停后等待输入线程当前的 poll 结束，之后不会再读取终端输入
+    pub fn pause_input(&self) {
+        self.input_paused.store(true, Ordering::Release);
+        std::thread::sleep(INPUT_POLL_INTERVAL * 2);
+    }
+
+    pub fn resume_input(&self) {
+        self.input_paused.store(false, Ordering::Release);
+    }
+
+    pub fn sender(&self) -> UnboundedSender<Event> {
+        self.tx.clone()
+    }
+
+    pub async fn next(&mut self) -> Option<Event> {
+        self.rx.recv().await
+    }
+}
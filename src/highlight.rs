@@ -0,0 +1,441 @@
+// 按 Nacos 的配置类型对内容做简单的逐行语法高亮，
+// 只区分注释、键、字符串、数字/布尔值和标签，足够在终端里阅读
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Span,
+};
+use unicode_width::UnicodeWidthChar;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Yaml,
+    Json,
+    Properties,
+    Xml,
+    Toml,
+    Html,
+    Text,
+}
+
+impl Format {
+    // type 为空或未知时按 dataId 的扩展名推断
+    pub fn detect(type_: &str, data_id: &str) -> Format {
+        match Format::from_name(type_) {
+            Format::Text => {
+                let ext = data_id.rsplit_once('.').map(|(_, ext)| ext).unwrap_or_default();
+                Format::from_name(ext)
+            }
+            format => format,
+        }
+    }
+
+    fn from_name(name: &str) -> Format {
+        match name.to_ascii_lowercase().as_str() {
+            "yaml" | "yml" => Format::Yaml,
+            "json" => Format::Json,
+            "properties" => Format::Properties,
+            "xml" => Format::Xml,
+            "toml" => Format::Toml,
+            "html" | "htm" => Format::Html,
+            _ => Format::Text,
+        }
+    }
+}
+
+fn comment() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+
+fn key() -> Style {
+    Style::default().fg(Color::Cyan)
+}
+
+fn string() -> Style {
+    Style::default().fg(Color::Green)
+}
+
+fn literal() -> Style {
+    Style::default().fg(Color::Magenta)
+}
+
+fn tag() -> Style {
+    Style::default().fg(Color::Blue)
+}
+
+fn section() -> Style {
+    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+}
+
+pub fn highlight_line(format: Format, line: &str) -> Vec<Span<'static>> {
+    match format {
+        Format::Yaml => yaml_line(line),
+        Format::Json => json_line(line),
+        Format::Properties => properties_line(line),
+        Format::Xml | Format::Html => xml_line(line),
+        Format::Toml => toml_line(line),
+        Format::Text => vec![Span::raw(line.to_string())],
+    }
+}
+
+// 找到不在引号内的注释起始位置，# 前面必须是行首或空白
+fn find_comment(line: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && prev.is_whitespace() => return Some(i),
+            None => {}
+        }
+        prev = c;
+    }
+    None
+}
+
+fn split_comment(line: &str) -> (&str, Option<&str>) {
+    match find_comment(line) {
+        Some(i) => (&line[..i], Some(&line[i..])),
+        None => (line, None),
+    }
+}
+
+fn scalar_style(value: &str) -> Style {
+    let v = value.trim();
+    if v.starts_with('"') || v.starts_with('\'') {
+        string()
+    } else if v.parse::<f64>().is_ok()
+        || matches!(v, "true" | "false" | "null" | "~" | "yes" | "no" | "on" | "off")
+    {
+        literal()
+    } else {
+        Style::default()
+    }
+}
+
+fn yaml_line(line: &str) -> Vec<Span<'static>> {
+    let (code, comment_part) = split_comment(line);
+    let mut spans = vec![];
+
+    let trimmed = code.trim_start();
+    if trimmed == "---" || trimmed == "..." {
+        spans.push(Span::styled(code.to_string(), section()));
+    } else {
+        let indent = &code[..code.len() - trimmed.len()];
+        spans.push(Span::raw(indent.to_string()));
+        let mut rest = trimmed;
+        if let Some(item) = rest.strip_prefix("- ").or_else(|| (rest == "-").then_some("")) {
+            spans.push(Span::styled("- ", tag()));
+            rest = item;
+        }
+        match yaml_key_end(rest) {
+            Some(end) => {
+                spans.push(Span::styled(rest[..end].to_string(), key()));
+                spans.push(Span::raw(":".to_string()));
+                let value = &rest[end + 1..];
+                spans.push(Span::styled(value.to_string(), scalar_style(value)));
+            }
+            None => spans.push(Span::styled(rest.to_string(), scalar_style(rest))),
+        }
+    }
+
+    if let Some(comment_part) = comment_part {
+        spans.push(Span::styled(comment_part.to_string(), comment()));
+    }
+    spans
+}
+
+// yaml 的 key 以不在引号内、后面紧跟空白或行尾的冒号结束
+fn yaml_key_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    let bytes = s.as_bytes();
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if (c == '"' || c == '\'') && i == 0 => quote = Some(c),
+            None if c == ':' && (i + 1 == s.len() || bytes[i + 1] == b' ') => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+fn properties_line(line: &str) -> Vec<Span<'static>> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with('!') {
+        return vec![Span::styled(line.to_string(), comment())];
+    }
+    match line.find(['=', ':']) {
+        Some(i) => vec![
+            Span::styled(line[..i].to_string(), key()),
+            Span::raw(line[i..i + 1].to_string()),
+            Span::styled(line[i + 1..].to_string(), string()),
+        ],
+        None => vec![Span::styled(line.to_string(), key())],
+    }
+}
+
+fn toml_line(line: &str) -> Vec<Span<'static>> {
+    let (code, comment_part) = split_comment(line);
+    let mut spans = vec![];
+
+    let trimmed = code.trim();
+    if trimmed.starts_with('[') {
+        spans.push(Span::styled(code.to_string(), section()));
+    } else if let Some(i) = code.find('=') {
+        spans.push(Span::styled(code[..i].to_string(), key()));
+        spans.push(Span::raw("=".to_string()));
+        let value = &code[i + 1..];
+        spans.push(Span::styled(value.to_string(), scalar_style(value)));
+    } else {
+        spans.push(Span::raw(code.to_string()));
+    }
+
+    if let Some(comment_part) = comment_part {
+        spans.push(Span::styled(comment_part.to_string(), comment()));
+    }
+    spans
+}
+
+fn json_line(line: &str) -> Vec<Span<'static>> {
+    let mut spans = vec![];
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let mut i = 0;
+    let mut plain_start = 0;
+
+    let flush = |spans: &mut Vec<Span<'static>>, from: usize, to: usize| {
+        if from < to {
+            spans.push(Span::raw(line[from..to].to_string()));
+        }
+    };
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        if c == '"' {
+            // 字符串，处理转义
+            let mut j = i + 1;
+            while j < chars.len() && chars[j].1 != '"' {
+                if chars[j].1 == '\\' {
+                    j += 1;
+                }
+                j += 1;
+            }
+            let end = chars.get(j + 1).map_or(line.len(), |(pos, _)| *pos);
+            // 后面紧跟冒号的是 key
+            let is_key = line[end..].trim_start().starts_with(':');
+            flush(&mut spans, plain_start, start);
+            spans.push(Span::styled(
+                line[start..end].to_string(),
+                if is_key { key() } else { string() },
+            ));
+            i = j + 1;
+            plain_start = end;
+        } else if c == '-' || c.is_ascii_digit() || c.is_ascii_alphabetic() {
+            let mut j = i;
+            while j < chars.len() && (chars[j].1.is_ascii_alphanumeric() || "+-.".contains(chars[j].1)) {
+                j += 1;
+            }
+            let end = chars.get(j).map_or(line.len(), |(pos, _)| *pos);
+            flush(&mut spans, plain_start, start);
+            spans.push(Span::styled(line[start..end].to_string(), literal()));
+            i = j;
+            plain_start = end;
+        } else {
+            i += 1;
+        }
+    }
+    flush(&mut spans, plain_start, line.len());
+    spans
+}
+
+fn xml_line(line: &str) -> Vec<Span<'static>> {
+    let mut spans = vec![];
+    let mut rest = line;
+    while !rest.is_empty() {
+        if let Some(body) = rest.strip_prefix("<!--") {
+            let end = body.find("-->").map_or(rest.len(), |i| i + 4 + 3);
+            spans.push(Span::styled(rest[..end].to_string(), comment()));
+            rest = &rest[end..];
+        } else if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            spans.extend(xml_tag(&rest[..end]));
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            spans.push(Span::raw(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    spans
+}
+
+// 标签名、属性名、属性值分别着色
+fn xml_tag(tag_text: &str) -> Vec<Span<'static>> {
+    let mut spans = vec![];
+    let name_end = tag_text
+        .find(|c: char| c.is_whitespace() || c == '>')
+        .unwrap_or(tag_text.len());
+    spans.push(Span::styled(tag_text[..name_end].to_string(), tag()));
+
+    let mut rest = &tag_text[name_end..];
+    while !rest.is_empty() {
+        if let Some(quote) = rest.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let end = rest[1..].find(quote).map_or(rest.len(), |i| i + 2);
+            spans.push(Span::styled(rest[..end].to_string(), string()));
+            rest = &rest[end..];
+        } else if rest.starts_with('>') || rest.starts_with("/>") || rest.starts_with("?>") {
+            spans.push(Span::styled(rest.to_string(), tag()));
+            break;
+        } else {
+            let end = rest.find(['"', '\'', '>', '/', '?']).unwrap_or(rest.len()).max(1);
+            spans.push(Span::styled(rest[..end].to_string(), key()));
+            rest = &rest[end..];
+        }
+    }
+    spans
+}
+
+// 在已着色的 spans 上叠加样式，ranges 为该行内的字节区间，用来标出搜索结果
+pub fn overlay(spans: Vec<Span<'static>>, ranges: &[(usize, usize, Style)]) -> Vec<Span<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+    let mut result = vec![];
+    let mut offset = 0;
+    for span in spans {
+        let text = span.content.to_string();
+        let span_end = offset + text.len();
+
+        // 在区间边界处切开 span
+        let mut cuts = vec![0, text.len()];
+        for (start, end, _) in ranges {
+            for pos in [*start, *end] {
+                if pos > offset && pos < span_end && text.is_char_boundary(pos - offset) {
+                    cuts.push(pos - offset);
+                }
+            }
+        }
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        for piece in cuts.windows(2) {
+            let (from, to) = (piece[0], piece[1]);
+            let pos = offset + from;
+            let style = ranges
+                .iter()
+                .filter(|(start, end, _)| pos >= *start && pos < *end)
+                .fold(span.style, |style, (_, _, patch)| style.patch(*patch));
+            result.push(Span::styled(text[from..to].to_string(), style));
+        }
+        offset = span_end;
+    }
+    result
+}
+
+// 跳过前 skip 列，最多保留 width 列，用于不换行时的水平滚动
+pub fn cut_spans(spans: &[Span<'static>], skip: usize, width: usize) -> Vec<Span<'static>> {
+    let mut result = vec![];
+    let mut col = 0;
+    for span in spans {
+        let mut text = String::new();
+        for c in span.content.chars() {
+            let w = c.width().unwrap_or(0);
+            if col >= skip && col + w <= skip + width {
+                text.push(c);
+            }
+            col += w;
+        }
+        if !text.is_empty() {
+            result.push(Span::styled(text, span.style));
+        }
+        if col >= skip + width {
+            break;
+        }
+    }
+    result
+}
+
+// 按显示宽度把一行 spans 折成多行
+pub fn wrap_spans(spans: &[Span<'static>], width: usize) -> Vec<Vec<Span<'static>>> {
+    let width = width.max(1);
+    let mut rows = vec![vec![]];
+    let mut col = 0;
+    for span in spans {
+        let mut text = String::new();
+        for c in span.content.chars() {
+            let w = c.width().unwrap_or(0);
+            if col + w > width && col > 0 {
+                if !text.is_empty() {
+                    rows.last_mut().unwrap().push(Span::styled(std::mem::take(&mut text), span.style));
+                }
+                rows.push(vec![]);
+                col = 0;
+            }
+            text.push(c);
+            col += w;
+        }
+        if !text.is_empty() {
+            rows.last_mut().unwrap().push(Span::styled(text, span.style));
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled(spans: &[Span]) -> Vec<(String, Style)> {
+        spans.iter().map(|s| (s.content.to_string(), s.style)).collect()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Format::detect("yaml", "a.txt"), Format::Yaml);
+        assert_eq!(Format::detect("text", "application.yml"), Format::Yaml);
+        assert_eq!(Format::detect("", "a"), Format::Text);
+    }
+
+    #[test]
+    fn test_yaml_comment_inside_quotes() {
+        let spans = yaml_line("  url: \"jdbc:mysql://db#1\" # primary");
+        let spans = styled(&spans);
+        assert_eq!(spans[1], ("url".to_string(), key()));
+        assert_eq!(spans[3], (" \"jdbc:mysql://db#1\" ".to_string(), string()));
+        assert_eq!(spans[4], ("# primary".to_string(), comment()));
+    }
+
+    #[test]
+    fn test_json_key_and_value() {
+        let spans = styled(&json_line(r#"  "a\"b": "c", "n": -1.5, "ok": true"#));
+        assert!(spans.contains(&(r#""a\"b""#.to_string(), key())));
+        assert!(spans.contains(&(r#""c""#.to_string(), string())));
+        assert!(spans.contains(&("-1.5".to_string(), literal())));
+        assert!(spans.contains(&("true".to_string(), literal())));
+    }
+
+    #[test]
+    fn test_overlay_and_wrap() {
+        let mark = Style::default().bg(Color::Yellow);
+        let spans = overlay(yaml_line("port: 8080"), &[(3, 8, mark)]);
+        let text: Vec<String> = spans.iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(text, vec!["por", "t", ":", " 80", "80"]);
+        assert_eq!(spans[1].style, key().patch(mark));
+
+        let rows = wrap_spans(&spans, 4);
+        let rows: Vec<String> = rows.iter().map(|r| r.iter().map(|s| s.content.to_string()).collect()).collect();
+        assert_eq!(rows, vec!["port", ": 80", "80"]);
+
+        let cut: String = cut_spans(&spans, 2, 5).iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(cut, "rt: 8");
+    }
+
+    #[test]
+    fn test_spans_keep_line_text() {
+        let line = r#"<bean id="a" class='b'/> text <!-- note --> <x>"#;
+        let text: String = xml_line(line).iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(text, line);
+    }
+}
@@ -1,28 +1,47 @@
-use std::{error::Error, io};
+use std::{error::Error, io, time::Duration};
 
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     crossterm::{
-        event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+        event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent, KeyModifiers},
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
     Terminal,
 };
-use tui_textarea::{Input, TextArea};
+use tui_textarea::Input;
 
 mod app;
 mod ui;
 mod resp;
 mod config;
 mod api;
+mod event;
+mod highlight;
+mod validate;
 use crate::{
-    app::{App, AppState,},
+    api::client::NacosClient,
+    app::{
+        diff::DiffView,
+        external::{editor_command, ExternalEdit},
+        history::HistoryAction,
+        instance_edit::InstanceEditKind,
+        viewer::ConfigViewer,
+        App, AppState,
+    },
+    config::{load_config, load_templates},
+    event::{Event, EventHandler},
     ui::ui,
 };
 
+// tick 间隔，用于刷新loading动画
+const TICK_RATE: Duration = Duration::from_millis(100);
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let app_config = load_config();
+    let client = NacosClient::from_config(&app_config.nacos);
+
     // setup terminal
     enable_raw_mode()?;
     let mut stderr = io::stderr(); // This is a special case. Normally using stdout is fine
@@ -31,8 +50,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut terminal = Terminal::new(backend)?;
 
     // create app and run it
-    let mut app = App::new();
-    let res = run_app(&mut terminal, &mut app);
+    let mut events = EventHandler::new(TICK_RATE);
+    let mut app = App::new(client, events.sender());
+    app.config_templates = load_templates();
+    app.ns_refresh();
+    let res = run_app(&mut terminal, &mut app, &mut events).await;
 
     // restore terminal
     disable_raw_mode()?;
@@ -43,122 +65,743 @@ async fn main() -> Result<(), Box<dyn Error>> {
     )?;
     terminal.show_cursor()?;
 
+    if let Err(err) = res {
+        println!("{err:?}");
+    }
+
     Ok(())
 }
 
-fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<bool> {
+async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App<'_>,
+    events: &mut EventHandler,
+) -> io::Result<bool> {
     loop {
         terminal.draw(|f| ui(f, app))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == event::KeyEventKind::Release {
-                // Skip events that are not KeyEventKind::Press
-                continue;
+        match events.next().await {
+            Some(Event::Key(key)) => handle_key(app, key),
+            Some(Event::Tick) => app.on_tick(),
+            Some(Event::Api(id, event)) => app.on_api_event(id, event),
+            Some(Event::ConfigChanged(change)) => app.on_config_changed(change),
+            None => return Ok(true),
+        }
+        if app.state == AppState::Quitting {
+            return Ok(true);
+        }
+        if let Some(edit) = app.take_external_edit() {
+            let result = run_external_editor(terminal, events, &edit)?;
+            app.on_external_edit_done(edit, result);
+        }
+    }
+}
+
+// 挂起 TUI 运行外部编辑器，退出后恢复终端并整屏重绘
+fn run_external_editor<B: Backend>(
+    terminal: &mut Terminal<B>,
+    events: &EventHandler,
+    edit: &ExternalEdit,
+) -> io::Result<io::Result<String>> {
+    events.pause_input();
+    disable_raw_mode()?;
+    execute!(io::stderr(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    let result = edit.run(&editor_command());
+
+    enable_raw_mode()?;
+    execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    events.resume_input();
+    Ok(result)
+}
+
+fn handle_key(app: &mut App, key: KeyEvent) {
+    app.error_message = None;
+    app.info_message = None;
+    match app.state{
+        AppState::Quitting => {}
+        AppState::Running => match app.current_screen {
+            app::CurrentScreen::Main => {
+                if handle_filter_key(app, key) {
+                    return;
+                }
+                match key.code {
+                    KeyCode::Char('q') =>{
+                        app.state = AppState::Quitting;
+                    }
+                    KeyCode::Char('1') => {
+                        app.current_menu = app::CurrentMenu::Config;
+                    }
+                    KeyCode::Char('2') => {
+                        app.current_menu = app::CurrentMenu::Service;
+                        app.service_open();
+                    }
+                    KeyCode::Char('3') => {
+                        app.current_menu = app::CurrentMenu::Namespace;
+                    }
+                    //config
+                    KeyCode::Left | KeyCode::Char('h') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_prev_tab();
+                    }
+                    KeyCode::Right | KeyCode::Char('l') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_next_tab();
+                    }
+                    KeyCode::Up | KeyCode::Char('k') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_select_prev();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_select_next();
+                    }
+                    KeyCode::Char('r') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_reload();
+                    }
+                    KeyCode::Enter if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_open_viewer();
+                    }
+                    KeyCode::Char('e') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_edit_selected();
+                    }
+                    KeyCode::Char('E') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_edit_external();
+                    }
+                    KeyCode::Char('H') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_open_history();
+                    }
+                    KeyCode::Char('L') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_open_listeners();
+                    }
+                    KeyCode::Char(' ') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_toggle_mark();
+                    }
+                    KeyCode::Char('d') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_delete_start();
+                    }
+                    KeyCode::Char('n') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_new_open();
+                    }
+                    KeyCode::Char('c') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_clone_start();
+                    }
+                    KeyCode::Char('x') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_export_open();
+                    }
+                    KeyCode::Char('i') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_import_open();
+                    }
+                    KeyCode::Char('s') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_query_open();
+                    }
+                    KeyCode::Char('f') if app.current_menu == app::CurrentMenu::Config => {
+                        app.config_search_open();
+                    }
+                    // 没有过滤时 esc 清除服务端查询条件
+                    KeyCode::Esc
+                        if app.current_menu == app::CurrentMenu::Config
+                            && app.config_query != (String::new(), String::new()) => {
+                        app.set_config_query(String::new(), String::new());
+                    }
+                    //service
+                    KeyCode::Left | KeyCode::Char('h') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_prev_tab();
+                    }
+                    KeyCode::Right | KeyCode::Char('l') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_next_tab();
+                    }
+                    KeyCode::Up | KeyCode::Char('k') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_select_prev();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_select_next();
+                    }
+                    KeyCode::Char(']') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_next_page();
+                    }
+                    KeyCode::Char('[') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_prev_page();
+                    }
+                    KeyCode::Char('o') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_cycle_sort();
+                    }
+                    KeyCode::Char('O') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_reverse_sort();
+                    }
+                    KeyCode::Char('r') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_reload();
+                    }
+                    KeyCode::Enter if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_open_instances();
+                    }
+                    KeyCode::Char('s') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_open_subscribers();
+                    }
+                    KeyCode::Char('i') if app.current_menu == app::CurrentMenu::Service => {
+                        app.instance_register_open();
+                    }
+                    KeyCode::Char('n') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_create_open();
+                    }
+                    KeyCode::Char('e') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_update_open();
+                    }
+                    KeyCode::Char('d') if app.current_menu == app::CurrentMenu::Service => {
+                        app.service_delete_open();
+                    }
+                    //namespace
+                    KeyCode::Up | KeyCode::Char('k') if app.current_menu == app::CurrentMenu::Namespace => {
+                        app.namespace_select_prev();
+                    }
+                    KeyCode::Down | KeyCode::Char('j') if app.current_menu == app::CurrentMenu::Namespace => {
+                        app.namespace_select_next();
+                    }
+                    KeyCode::Char('d') if app.current_menu == app::CurrentMenu::Namespace => {
+                        app.move_screen_main_to_ns_delete();
+                    }
+                    KeyCode::Char('a') if app.current_menu == app::CurrentMenu::Namespace => {
+                        app.move_screen_main_to_ns_add();
+                    }
+                    KeyCode::Char('e') if app.current_menu == app::CurrentMenu::Namespace => {
+                        app.move_screen_main_to_ns_edit();
+                    }
+                    KeyCode::Char('r') if app.current_menu == app::CurrentMenu::Namespace => {
+                        app.ns_refresh();
+                    }
+                    _ => {}
+                }
+            }
+            app::CurrentScreen::ConfigView => handle_viewer_key(app, key),
+            app::CurrentScreen::ConfigEdit => handle_editor_key(app, key),
+            app::CurrentScreen::ConfigMerge => handle_merge_key(app, key),
+            app::CurrentScreen::ConfigHistory => handle_history_key(app, key),
+            app::CurrentScreen::ConfigListeners => handle_listener_key(app, key),
+            app::CurrentScreen::ConfigDelete => {
+                let finished = app.config_delete.as_ref().is_some_and(|d| d.results.is_some());
+                match key.code {
+                    // 显示结果后按任意键关闭
+                    _ if finished => app.config_delete_close(),
+                    KeyCode::Char('y') => app.config_delete_confirm(),
+                    KeyCode::Esc | KeyCode::Char('n') => app.config_delete_close(),
+                    _ => {}
+                }
+            }
+            app::CurrentScreen::ConfigNew => {
+                let on_picker = app.config_new.as_ref().is_some_and(|f| f.focus.is_picker());
+                match key.code {
+                    KeyCode::Esc => app.config_new_close(),
+                    KeyCode::Tab | KeyCode::Down => app.config_new_focus(true),
+                    KeyCode::BackTab | KeyCode::Up => app.config_new_focus(false),
+                    KeyCode::Left | KeyCode::Char('h') if on_picker => app.config_new_cycle(false),
+                    KeyCode::Right | KeyCode::Char('l') if on_picker => app.config_new_cycle(true),
+                    KeyCode::Enter => app.config_new_submit(),
+                    _ => app.config_new_input(Input::from(key)),
+                }
             }
-            match app.state{
-                AppState::Quitting => {
-                    return Ok(true);
+            app::CurrentScreen::ConfigQuery => match key.code {
+                KeyCode::Esc => app.config_query_close(),
+                KeyCode::Enter => app.config_query_submit(),
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => app.config_query_toggle_focus(),
+                _ => app.config_query_input(Input::from(key)),
+            },
+            app::CurrentScreen::ConfigSearch => handle_search_key(app, key),
+            app::CurrentScreen::ServiceInstances => handle_instance_key(app, key),
+            app::CurrentScreen::ServiceSubscribers => handle_subscriber_key(app, key),
+            app::CurrentScreen::InstanceEdit => handle_instance_edit_key(app, key),
+            app::CurrentScreen::ServiceEdit => handle_service_edit_key(app, key),
+            app::CurrentScreen::InstanceRegister => match key.code {
+                KeyCode::Esc => app.instance_register_close(),
+                KeyCode::Tab | KeyCode::Down => app.instance_register_focus(true),
+                KeyCode::BackTab | KeyCode::Up => app.instance_register_focus(false),
+                KeyCode::Enter => app.instance_register_submit(),
+                _ => app.instance_register_input(Input::from(key)),
+            },
+            app::CurrentScreen::ConfigClone => handle_clone_key(app, key),
+            app::CurrentScreen::ConfigTransfer => handle_transfer_key(app, key),
+            app::CurrentScreen::ConfigPublishConfirm => match key.code {
+                KeyCode::Char('y') => app.config_confirm_publish(),
+                KeyCode::Char('n') | KeyCode::Esc => app.config_cancel_publish(),
+                _ => {
+                    if let Some(publish) = app.config_publish.as_mut() {
+                        handle_diff_key(&mut publish.diff, key);
+                    }
+                }
+            },
+            app::CurrentScreen::NamespaceDelete => {
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('n') => {
+                        app.current_screen = app::CurrentScreen::Main;
+                    }
+                    KeyCode::Char('y') => {
+                        app.ns_delete();
+                    }
+                    _ => {
+                        // Handle other keys if necessary
+                    }
+                }
+            }
+            app::CurrentScreen::NamespaceAdd => {
+                match key.code {
+                    KeyCode::Esc => {
+                        app.move_screen_ns_add_to_main();
+                    }
+                    KeyCode::Tab => {
+                        // 切换输入焦点
+                        app.namespace_current_edit_index = (app.namespace_current_edit_index + 1) % 3;
+                    }
+                    KeyCode::Enter => {
+                        app.ns_add_submit();
+                    }
+                    _ => {
+                        let input = Input::from(key);
+                        app.handle_input(input);
+                    }
                 }
-                AppState::Running => match app.current_screen {
-                    app::CurrentScreen::Main => {
-                        match key.code {
-                            KeyCode::Char('q') =>{
-                                app.state = AppState::Quitting;
-                                return Ok(true);
-                            }
-                            KeyCode::Char('1') => {
-                                app.current_menu = app::CurrentMenu::Config;
-                            }
-                            KeyCode::Char('2') => {
-                                app.current_menu = app::CurrentMenu::Service;
-                            }
-                            KeyCode::Char('3') => {
-                                app.current_menu = app::CurrentMenu::Namespace;
-                            }
-                            //namespace
-                            KeyCode::Up | KeyCode::Char('k') => {
-                                if app.current_menu == app::CurrentMenu::Namespace {
-                                    if app.namespace_current_line > 0 {
-                                        app.namespace_current_line -= 1;
-                                    }
-                                }
-                            }
-                            KeyCode::Down | KeyCode::Char('j') => {
-                                if app.current_menu == app::CurrentMenu::Namespace {
-                                    if app.namespace_current_line < (app.namespace_list.len() as u8 - 1) {
-                                        app.namespace_current_line += 1;
-                                    }
-                                }
-                            }
-                            KeyCode::Char('d') => {
-                                if app.current_menu == app::CurrentMenu::Namespace {
-                                    app.move_screen_main_to_ns_delete();
-                                }
-                            }
-                            KeyCode::Char('a') => {
-                                if app.current_menu == app::CurrentMenu::Namespace {
-                                    app.move_screen_main_to_ns_add();
-                                }
-                            }
-                            KeyCode::Char('e') => {
-                                if app.current_menu == app::CurrentMenu::Namespace {
-                                    app.move_screen_main_to_ns_edit();
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                    app::CurrentScreen::NamespaceDelete => {
-                        match key.code {
-                            KeyCode::Esc | KeyCode::Char('n') => {
-                                app.current_screen = app::CurrentScreen::Main;
-                            }
-                            KeyCode::Char('y') => {
-                                app.ns_delete();
-                            }
-                            _ => {
-                                // Handle other keys if necessary
-                            }
-                        }
-                    }
-                    app::CurrentScreen::NamespaceAdd => {
-                        match key.code {
-                            KeyCode::Esc => {
-                                app.move_screen_ns_add_to_main();
-                            }
-                            KeyCode::Tab => {
-                                // 切换输入焦点
-                                app.namespace_current_edit_index = (app.namespace_current_edit_index + 1) % 3;
-                            }
-                            KeyCode::Enter => {
-                                app.ns_add_submit();
-                            }
-                            _ => {
-                                let input = Input::from(key);
-                                app.handle_input(input);
-                            }
-                        }
-                    }
-                    app::CurrentScreen::NamespaceEdit => {
-                        match key.code {
-                            KeyCode::Esc => {
-                                app.move_screen_ns_add_to_main();
-                            }
-                            KeyCode::Tab => {
-                                // 切换输入焦点
-                                app.namespace_current_edit_index = (app.namespace_current_edit_index + 1) % 2;
-                            }
-                            KeyCode::Enter => {
-                                app.ns_edit_submit();
-                            }
-                            _ => {
-                                let input = Input::from(key);
-                                app.handle_input(input);
-                            }
-                        }
+            }
+            app::CurrentScreen::NamespaceEdit => {
+                match key.code {
+                    KeyCode::Esc => {
+                        app.move_screen_ns_add_to_main();
+                    }
+                    KeyCode::Tab => {
+                        // 切换输入焦点
+                        app.namespace_current_edit_index = (app.namespace_current_edit_index + 1) % 2;
+                    }
+                    KeyCode::Enter => {
+                        app.ns_edit_submit();
+                    }
+                    _ => {
+                        let input = Input::from(key);
+                        app.handle_input(input);
                     }
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// 列表上的 / 过滤：输入时按键都交给过滤框，输入完成后 n/N 在匹配之间跳转，esc 清除过滤。已处理返回 true
+fn handle_filter_key(app: &mut App, key: KeyEvent) -> bool {
+    if key.code == KeyCode::Char('/') && app.current_filter().is_none_or(|f| !f.editing) {
+        app.filter_open();
+        return true;
+    }
+    let Some(filter) = app.current_filter() else {
+        return false;
+    };
+    if filter.editing {
+        match key.code {
+            KeyCode::Esc => app.filter_clear(),
+            KeyCode::Enter => app.filter_submit(),
+            KeyCode::Down => select_next(app),
+            KeyCode::Up => select_prev(app),
+            _ => app.filter_input(Input::from(key)),
+        }
+        return true;
+    }
+    match key.code {
+        KeyCode::Esc => app.filter_clear(),
+        KeyCode::Char('n') => app.filter_step(true),
+        KeyCode::Char('N') => app.filter_step(false),
+        _ => return false,
+    }
+    true
+}
+
+fn select_next(app: &mut App) {
+    match app.current_menu {
+        app::CurrentMenu::Config => app.config_select_next(),
+        app::CurrentMenu::Namespace => app.namespace_select_next(),
+        app::CurrentMenu::Service => app.service_select_next(),
+    }
+}
+
+fn select_prev(app: &mut App) {
+    match app.current_menu {
+        app::CurrentMenu::Config => app.config_select_prev(),
+        app::CurrentMenu::Namespace => app.namespace_select_prev(),
+        app::CurrentMenu::Service => app.service_select_prev(),
+    }
+}
+
+// 复制配置分三步：选择目标、预览（可看差异）、查看结果
+fn handle_clone_key(app: &mut App, key: KeyEvent) {
+    let Some(clone) = app.config_clone.as_mut() else {
+        return;
+    };
+    if let Some(diff) = clone.diff.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.clone_close_diff(),
+            _ => handle_diff_key(diff, key),
+        }
+        return;
+    }
+    if clone.finished {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => app.clone_select_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.clone_select_prev(),
+            KeyCode::Enter | KeyCode::Char('d') => app.clone_show_diff(),
+            _ => app.config_clone_close(),
+        }
+        return;
+    }
+    if clone.previewed {
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => app.clone_select_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.clone_select_prev(),
+            KeyCode::Enter | KeyCode::Char('d') => app.clone_show_diff(),
+            KeyCode::Char('p') => app.clone_cycle_policy(),
+            KeyCode::Char('y') => app.clone_confirm(),
+            KeyCode::Esc | KeyCode::Char('n') => app.clone_back(),
+            _ => {}
+        }
+        return;
+    }
+    let on_picker = clone.focus != app::clone::CloneField::Group;
+    match key.code {
+        KeyCode::Esc => app.config_clone_close(),
+        KeyCode::Tab | KeyCode::Down => app.clone_focus(true),
+        KeyCode::BackTab | KeyCode::Up => app.clone_focus(false),
+        KeyCode::Left | KeyCode::Char('h') if on_picker => app.clone_cycle(false),
+        KeyCode::Right | KeyCode::Char('l') if on_picker => app.clone_cycle(true),
+        KeyCode::Enter => app.clone_preview(),
+        _ => app.clone_input(Input::from(key)),
+    }
+}
+
+// 内容搜索：输入框里 enter 搜索，结果列表里 enter 在查看器中打开；alt-r/alt-c 切换正则和大小写
+fn handle_search_key(app: &mut App, key: KeyEvent) {
+    let Some(search) = app.config_search.as_ref() else {
+        return;
+    };
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        match key.code {
+            KeyCode::Char('r') => app.search_toggle_regex(),
+            KeyCode::Char('c') => app.search_toggle_case(),
+            _ => {}
+        }
+        return;
+    }
+    if search.editing {
+        match key.code {
+            KeyCode::Esc => app.config_search_close(),
+            KeyCode::Enter => app.search_run(),
+            KeyCode::Tab | KeyCode::Down => app.search_toggle_focus(),
+            _ => app.search_input(Input::from(key)),
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.config_search_close(),
+        KeyCode::Down | KeyCode::Char('j') => app.search_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.search_select_prev(),
+        KeyCode::Enter => app.search_open_hit(),
+        KeyCode::Tab | KeyCode::Char('/') => app.search_toggle_focus(),
+        KeyCode::Char('r') => app.search_fetch_contents(),
+        _ => {}
+    }
+}
+
+fn handle_instance_key(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.instance_close(),
+        KeyCode::Down | KeyCode::Char('j') => app.instance_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.instance_select_prev(),
+        KeyCode::Char('r') => app.instance_refresh(),
+        KeyCode::Char('a') => app.instance_toggle_auto_refresh(),
+        KeyCode::Char('e') => app.instance_toggle_enabled(),
+        KeyCode::Char('w') => app.instance_edit_weight(),
+        KeyCode::Char('m') => app.instance_edit_metadata(),
+        KeyCode::Char('n') => app.instance_register_open(),
+        KeyCode::Char('d') => app.instance_deregister(),
+        KeyCode::Tab => app.service_open_subscribers(),
+        _ => {}
+    }
+}
+
+// 过滤词输入中的按键交给过滤框，esc 先清除过滤再关闭
+fn handle_subscriber_key(app: &mut App, key: KeyEvent) {
+    let Some(browser) = app.service_subscribers.as_ref() else {
+        return;
+    };
+    if let Some(filter) = browser.filter.as_ref() {
+        if filter.editing {
+            match key.code {
+                KeyCode::Esc => app.subscriber_filter_clear(),
+                KeyCode::Enter => app.subscriber_filter_submit(),
+                KeyCode::Down => app.subscriber_select_next(),
+                KeyCode::Up => app.subscriber_select_prev(),
+                _ => app.subscriber_filter_input(Input::from(key)),
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => return app.subscriber_filter_clear(),
+            KeyCode::Char('n') => return app.subscriber_filter_step(true),
+            KeyCode::Char('N') => return app.subscriber_filter_step(false),
+            _ => {}
+        }
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.instance_close(),
+        KeyCode::Down | KeyCode::Char('j') => app.subscriber_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.subscriber_select_prev(),
+        KeyCode::Char(']') => app.subscriber_next_page(),
+        KeyCode::Char('[') => app.subscriber_prev_page(),
+        KeyCode::Char('/') => app.subscriber_filter_open(),
+        KeyCode::Char('r') => app.subscriber_reload(),
+        KeyCode::Tab => app.subscriber_show_instances(),
+        _ => {}
+    }
+}
+
+// 表单里 enter 查看修改前后的对比，确认页 y 提交
+fn handle_service_edit_key(app: &mut App, key: KeyEvent) {
+    let Some(form) = app.service_edit.as_ref() else {
+        return;
+    };
+    if form.confirming() {
+        match key.code {
+            KeyCode::Char('y') => app.service_edit_confirm(),
+            KeyCode::Esc | KeyCode::Char('n') => app.service_edit_back(),
+            _ => {}
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc => app.service_edit_close(),
+        KeyCode::Tab | KeyCode::Down => app.service_edit_focus(true),
+        KeyCode::BackTab | KeyCode::Up => app.service_edit_focus(false),
+        KeyCode::Enter => app.service_edit_review(),
+        _ => app.service_edit_input(Input::from(key)),
+    }
+}
+
+// 权重单行输入 enter 提交，元数据多行输入 ctrl-s 提交；确认页 y 提交到服务端
+fn handle_instance_edit_key(app: &mut App, key: KeyEvent) {
+    let Some(edit) = app.instance_edit.as_ref() else {
+        return;
+    };
+    if edit.confirming() {
+        match key.code {
+            KeyCode::Char('y') => app.instance_edit_confirm(),
+            KeyCode::Esc | KeyCode::Char('n') => app.instance_edit_back(),
+            _ => {}
+        }
+        return;
+    }
+    let multiline = edit.kind == InstanceEditKind::Metadata;
+    match key.code {
+        KeyCode::Esc => app.instance_edit_back(),
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => app.instance_edit_submit(),
+        KeyCode::Enter if !multiline => app.instance_edit_submit(),
+        _ => app.instance_edit_input(Input::from(key)),
+    }
+}
+
+// 导出只有路径一个输入框；导入先填路径和策略，再确认包里的配置，最后查看结果
+fn handle_transfer_key(app: &mut App, key: KeyEvent) {
+    let Some(transfer) = app.config_transfer.as_ref() else {
+        return;
+    };
+    if transfer.result.is_some() {
+        app.config_transfer_close();
+        return;
+    }
+    if transfer.entries.is_some() {
+        match key.code {
+            KeyCode::Char('y') => app.transfer_submit(),
+            KeyCode::Char('p') => app.transfer_cycle_policy(),
+            KeyCode::Esc | KeyCode::Char('n') => app.transfer_back(),
+            _ => {}
+        }
+        return;
+    }
+    let policy_focused = transfer.policy_focused;
+    match key.code {
+        KeyCode::Esc => app.config_transfer_close(),
+        KeyCode::Enter => app.transfer_submit(),
+        KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => app.transfer_toggle_focus(),
+        KeyCode::Left | KeyCode::Right | KeyCode::Char('h') | KeyCode::Char('l') if policy_focused => {
+            app.transfer_cycle_policy()
+        }
+        _ => app.transfer_input(Input::from(key)),
+    }
+}
+
+fn handle_viewer_key(app: &mut App, key: KeyEvent) {
+    let Some(viewer) = app.config_viewer.as_mut() else {
+        return;
+    };
+    if let Some(diff) = viewer.remote_diff.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => viewer.remote_diff = None,
+            _ => handle_diff_key(diff, key),
+        }
+        return;
+    }
+    if handle_viewer_nav(viewer, key) {
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.config_close_viewer(),
+        KeyCode::Char('e') => app.config_open_editor(),
+        KeyCode::Char('E') => app.config_edit_external(),
+        KeyCode::Char('H') => app.config_open_history(),
+        KeyCode::Char('L') => app.config_open_listeners(),
+        KeyCode::Char('d') => app.viewer_open_remote_diff(),
+        KeyCode::Char('x') => app.viewer_dismiss_remote_change(),
+        _ => {}
+    }
+}
+
+// 查看器通用的滚动和搜索按键，已处理返回 true
+fn handle_viewer_nav(viewer: &mut ConfigViewer, key: KeyEvent) -> bool {
+    let page = viewer.height.max(1);
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+    // 正在输入搜索词
+    if let Some(textarea) = viewer.search_input.as_mut() {
+        match key.code {
+            KeyCode::Esc => viewer.search_input = None,
+            KeyCode::Enter => viewer.submit_search(),
+            _ => {
+                textarea.input(Input::from(key));
+            }
+        }
+        return true;
+    }
+
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => viewer.scroll_down(1),
+        KeyCode::Up | KeyCode::Char('k') => viewer.scroll_up(1),
+        KeyCode::Char('d') if ctrl => viewer.scroll_down(page / 2),
+        KeyCode::Char('u') if ctrl => viewer.scroll_up(page / 2),
+        KeyCode::PageDown => viewer.scroll_down(page),
+        KeyCode::PageUp => viewer.scroll_up(page),
+        KeyCode::Char('g') | KeyCode::Home => viewer.scroll_top(),
+        KeyCode::Char('G') | KeyCode::End => viewer.scroll_bottom(),
+        KeyCode::Right | KeyCode::Char('l') => viewer.scroll_right(),
+        KeyCode::Left | KeyCode::Char('h') => viewer.scroll_left(),
+        KeyCode::Char('w') => viewer.toggle_wrap(),
+        KeyCode::Char('/') => viewer.start_search(),
+        KeyCode::Char('n') => viewer.next_match(),
+        KeyCode::Char('N') => viewer.prev_match(),
+        _ => return false,
+    }
+    true
+}
+
+// 历史列表上依次叠着版本查看器和对比视图，按键交给最上层
+fn handle_history_key(app: &mut App, key: KeyEvent) {
+    let Some(history) = app.config_history.as_mut() else {
+        return;
+    };
+    if let Some(diff) = history.diff.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => history.diff = None,
+            _ => handle_diff_key(diff, key),
+        }
+        return;
+    }
+    if let Some(viewer) = history.viewer.as_mut() {
+        if !handle_viewer_nav(viewer, key) && matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+            history.viewer = None;
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => app.config_close_history(),
+        KeyCode::Down | KeyCode::Char('j') => app.history_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.history_select_prev(),
+        KeyCode::Char(' ') => app.history_toggle_mark(),
+        KeyCode::Enter => app.history_run(HistoryAction::View),
+        KeyCode::Char('d') => app.history_run(HistoryAction::DiffCurrent),
+        KeyCode::Char('c') => app.history_run(HistoryAction::DiffMarked),
+        KeyCode::Char('R') => app.history_run(HistoryAction::Rollback),
+        KeyCode::Char('r') => app.history_reload(),
+        _ => {}
+    }
+}
+
+// 正在输入 ip 时按键交给输入框
+fn handle_listener_key(app: &mut App, key: KeyEvent) {
+    let Some(browser) = app.config_listeners.as_ref() else {
+        return;
+    };
+    if browser.ip_input.is_some() {
+        match key.code {
+            KeyCode::Esc => app.listener_ip_close(),
+            KeyCode::Enter => app.listener_ip_submit(),
+            _ => app.listener_ip_input(Input::from(key)),
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc => app.listener_back(),
+        KeyCode::Char('q') => app.listener_close(),
+        KeyCode::Down | KeyCode::Char('j') => app.listener_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.listener_select_prev(),
+        KeyCode::Enter => app.listener_drill(),
+        KeyCode::Char('i') => app.listener_ip_open(),
+        KeyCode::Char('r') => app.listener_reload(),
+        _ => {}
+    }
+}
+
+// 对比视图通用的滚动和切换按键
+fn handle_diff_key(diff: &mut DiffView, key: KeyEvent) {
+    let half_page = (diff.height / 2).max(1);
+    match key.code {
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => diff.scroll_down(half_page),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => diff.scroll_up(half_page),
+        KeyCode::Char('j') | KeyCode::Down => diff.scroll_down(1),
+        KeyCode::Char('k') | KeyCode::Up => diff.scroll_up(1),
+        KeyCode::PageDown => diff.scroll_down(diff.height.max(1)),
+        KeyCode::PageUp => diff.scroll_up(diff.height.max(1)),
+        KeyCode::Char('g') | KeyCode::Home => diff.scroll_top(),
+        KeyCode::Char('G') | KeyCode::End => diff.scroll_bottom(),
+        KeyCode::Char(']') => diff.next_change(),
+        KeyCode::Char('[') => diff.prev_change(),
+        KeyCode::Char('s') => diff.toggle_side_by_side(),
+        _ => {}
+    }
+}
+
+fn handle_merge_key(app: &mut App, key: KeyEvent) {
+    let Some(merge) = app.config_merge.as_mut() else {
+        return;
+    };
+    let half_page = (merge.height / 2).max(1);
+    match key.code {
+        KeyCode::Char('m') => app.config_merge_edit(),
+        KeyCode::Char('o') => app.config_merge_keep_mine(),
+        KeyCode::Esc | KeyCode::Char('q') => app.config_merge_abort(),
+        KeyCode::Char('j') | KeyCode::Down => merge.scroll_down(1),
+        KeyCode::Char('k') | KeyCode::Up => merge.scroll_up(1),
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => merge.scroll_down(half_page),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => merge.scroll_up(half_page),
+        _ => {}
+    }
+}
+
+fn handle_editor_key(app: &mut App, key: KeyEvent) {
+    let Some(editor) = app.config_editor.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.config_editor_save();
+        }
+        KeyCode::Esc => {
+            if editor.is_modified() && !editor.confirm_discard {
+                editor.confirm_discard = true;
+                app.info_message = Some("Unsaved changes, press esc again to discard".to_string());
+            } else {
+                app.config_close_editor();
+            }
+        }
+        _ => {
+            editor.confirm_discard = false;
+            editor.textarea.input(Input::from(key));
+        }
+    }
+}
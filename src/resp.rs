@@ -1,3 +1,14 @@
+// 字段名与 Nacos 返回的 json 保持一致
+#![allow(non_snake_case)]
+
 pub mod auth_login_resp;
 pub mod namespace_list_resp;
-pub mod config_list_resp;
\ No newline at end of file
+pub mod config_page_resp;
+pub mod config_history_resp;
+pub mod config_import_resp;
+pub mod service_list_resp;
+pub mod service_detail_resp;
+pub mod instance_list_resp;
+pub mod subscriber_list_resp;
+pub mod config_listener_resp;
+pub mod rest_result;
@@ -4,6 +4,9 @@ use serde::Deserialize;
 pub struct AuthLoginResp {
     pub accessToken: String,
     pub tokenTtl: i64,
+    // 登录账号的信息，客户端目前只用到 token
+    #[allow(dead_code)]
     pub globalAdmin: bool,
+    #[allow(dead_code)]
     pub username: String,
 }
\ No newline at end of file
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Deserializer};
+
+// /nacos/v2/cs/history/list 的分页结果
+#[derive(Debug, Deserialize)]
+pub struct ConfigHistoryPageResp {
+    pub data: ConfigHistoryPage,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigHistoryPage {
+    pub totalCount: i32,
+    pub pageItems: Vec<ConfigHistory>,
+}
+
+// /nacos/v2/cs/history 的单条历史，列表里 content 为空
+#[derive(Debug, Deserialize)]
+pub struct ConfigHistoryResp {
+    pub data: ConfigHistory,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigHistory {
+    // 历史记录 id，查询详情时作为 nid 传回去
+    #[serde(deserialize_with = "string_or_number")]
+    pub id: String,
+    pub content: Option<String>,
+    pub srcIp: Option<String>,
+    pub srcUser: Option<String>,
+    // I/U/D，后面带空格补齐
+    pub opType: String,
+    // 不同版本的 Nacos 返回时间戳或 "2010-05-04T16:00:00.000+0000"
+    pub lastModifiedTime: Option<serde_json::Value>,
+}
+
+// 不同版本的 Nacos 把 id 序列化为字符串或数字
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+// 导入配置的结果，放在 RestResult 的 data 中；没有跳过/失败时对应字段可能为 null
+#[derive(Debug, Deserialize)]
+pub struct ConfigImportResult {
+    pub succCount: i32,
+    #[serde(default)]
+    pub skipCount: i32,
+    #[serde(default)]
+    pub skipData: Option<Vec<ConfigKey>>,
+    #[serde(default)]
+    pub failData: Option<Vec<ConfigKey>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigKey {
+    pub dataId: String,
+    pub group: String,
+}
@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+// /nacos/v1/cs/configs/listener 和 /nacos/v1/cs/listener 的结果，字段名的拼写与 Nacos 一致
+#[derive(Debug, Deserialize)]
+pub struct ListenerStatusResp {
+    // 按配置查询时为 客户端 ip -> md5，按 ip 查询时为 groupKey -> md5
+    #[serde(default)]
+    pub lisentersGroupkeyStatus: HashMap<String, String>,
+}
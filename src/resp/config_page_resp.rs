@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+// /nacos/v1/cs/configs?search=blur 的分页结果
+#[derive(Debug, Deserialize)]
+pub struct ConfigPageResp {
+    pub totalCount: i32,
+    pub pageItems: Vec<ConfigInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConfigInfo {
+    pub id: String,
+    pub dataId: String,
+    pub group: String,
+    pub content: Option<String>,
+    pub appName: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+}
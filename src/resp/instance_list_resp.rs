@@ -0,0 +1,23 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+// /nacos/v1/ns/catalog/instances 的分页结果，与 /instance/list 不同，包含已下线的实例
+#[derive(Debug, Deserialize)]
+pub struct InstanceListResp {
+    pub count: i32,
+    pub list: Vec<Instance>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Instance {
+    pub ip: String,
+    pub port: u16,
+    pub weight: f64,
+    pub healthy: bool,
+    pub enabled: bool,
+    pub ephemeral: bool,
+    pub clusterName: String,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
@@ -0,0 +1,9 @@
+use serde::Deserialize;
+
+// Nacos 通用的 {code, message, data} 响应
+#[derive(Debug, Deserialize)]
+pub struct RestResult<T> {
+    pub code: i32,
+    pub message: Option<String>,
+    pub data: T,
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+// /nacos/v1/ns/service 返回的服务定义
+#[derive(Debug, Deserialize)]
+pub struct ServiceDetail {
+    pub groupName: String,
+    pub name: String,
+    pub protectThreshold: f32,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    pub selector: Option<Selector>,
+    #[serde(default)]
+    pub clusters: Vec<ClusterInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Selector {
+    #[serde(rename = "type")]
+    pub type_: String, // none 或 label
+    #[serde(default)]
+    pub expression: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterInfo {
+    pub name: String,
+}
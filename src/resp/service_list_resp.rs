@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+// /nacos/v1/ns/catalog/services 的分页结果
+#[derive(Debug, Deserialize)]
+pub struct ServiceListResp {
+    pub count: i32,
+    pub serviceList: Vec<ServiceView>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServiceView {
+    pub name: String,
+    pub groupName: String,
+    pub clusterCount: i32,
+    pub ipCount: i32,
+    pub healthyInstanceCount: i32,
+    #[serde(default)]
+    pub triggerFlag: Option<String>, // 健康实例比例低于保护阈值时为 "true"
+}
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+// /nacos/v1/ns/service/subscribers 的分页结果
+#[derive(Debug, Deserialize)]
+pub struct SubscriberListResp {
+    pub count: i32,
+    pub subscribers: Vec<Subscriber>,
+}
+
+// 订阅服务的客户端，agent 为客户端类型和版本，如 Nacos-Java-Client:v2.2.0
+#[derive(Clone, Debug, Deserialize)]
+pub struct Subscriber {
+    pub addrStr: String,
+    #[serde(default)]
+    pub agent: String,
+    #[serde(default)]
+    pub app: String,
+    #[serde(default)]
+    pub namespaceId: String,
+}
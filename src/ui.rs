@@ -1,13 +1,31 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Position, Rect}, 
+    layout::{Alignment, Constraint, Direction, Layout, Rect}, 
     style::{Color, Modifier, Style, Stylize}, 
-    text::{self, Line, Span, Text}, 
-    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Tabs, Widget, Wrap}, 
+    text::{Line, Span, Text}, 
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs}, 
     Frame
 };
-use tui_textarea::TextArea;
 
-use crate::{app::{self, App}, main};
+use crate::app::{self, App};
+
+mod clone;
+mod create;
+mod delete;
+mod diff;
+mod editor;
+mod filter;
+mod history;
+mod instance;
+mod instance_edit;
+mod listener;
+mod merge;
+mod register;
+mod search;
+mod service;
+mod service_edit;
+mod subscriber;
+mod transfer;
+mod viewer;
 
 pub fn ui(frame: &mut Frame, app: &mut App) {
     // Create the layout sections. 
@@ -18,6 +36,20 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             Constraint::Length(1)])
         .areas(frame.area());
 
+    // 右下角显示进行中的请求
+    let [hint_rect, loading_rect] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(32)])
+        .areas(hint_rect);
+    if let Some((_, label)) = app.in_flight.last() {
+        let loading = Paragraph::new(format!("{} {}...", app.spinner(), label))
+            .style(Style::default().fg(Color::Cyan))
+            .alignment(Alignment::Right);
+        frame.render_widget(loading, loading_rect);
+    }
+
     let [menu_rect, body_rect] = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -63,7 +95,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         });
     let service_text = Paragraph::new(
         Text::styled(
-            "Todo", 
+            "Service List", 
             Style::default().fg(Color::Reset)))
         .block(service_block);
 
@@ -85,12 +117,6 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         .block(namespace_block);
 
     frame.render_widget(namespace_text, namespace_rect);
-
-    // content section
-    let content_block = Block::default()
-        .borders(Borders::ALL)
-        .title("content")
-        .style(Style::default());
         
     // 根据menu选择渲染不同的内容
     if app.current_menu == app::CurrentMenu::Config {
@@ -119,36 +145,93 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
 
         frame.render_widget(tabs, tab_rect);
 
-        // 命名空间下的配置列表
-        let header = ListItem::new(Text::styled(
-            format!("{:<20} {:<20} {:<20}", "data_id", "group", "type"),
+        // 命名空间下的配置列表，有服务端查询条件时显示在标题上
+        let (query_data_id, query_group) = &app.config_query;
+        let query = match (query_data_id.is_empty(), query_group.is_empty()) {
+            (true, true) => String::new(),
+            (false, true) => format!(" data_id={}", query_data_id),
+            (true, false) => format!(" group={}", query_group),
+            (false, false) => format!(" data_id={} group={}", query_data_id, query_group),
+        };
+        let config_block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Configs{} ({}/{})", query, app.config_list.len(), app.config_total));
+        let [header_rect, list_rect, filter_rect] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // header row
+                Constraint::Min(1),    // config rows
+                Constraint::Length(if app.config_filter.is_some() { 1 } else { 0 }), // filter row
+            ])
+            .areas(config_block.inner(content_rect));
+        frame.render_widget(config_block, content_rect);
+
+        let header = Paragraph::new(Text::styled(
+            format!("  {:<40} {:<20} {:<12} {:<20}", "data_id", "group", "type", "app_name"),
             Style::default().fg(Color::Yellow),
         ));
-        let config_items: Vec<ListItem> = app.config_list
+        frame.render_widget(header, header_rect);
+
+        // 过滤时只显示匹配的行，并标出匹配的字符
+        let query = app.config_filter.as_ref().map(|f| f.query()).unwrap_or_default();
+        let rows: Vec<usize> = match app.config_filter.as_ref() {
+            Some(filter) => filter.matches.clone(),
+            None => (0..app.config_list.len()).collect(),
+        };
+        let config_items: Vec<ListItem> = rows
             .iter()
-            .map(|config| {
-                ListItem::new(Text::styled(
-                    format!("{:<20} {:<20} {:<20}", 
-                    config.data_id, config.group, config.format),
-                    Style::default(),
-                ))
+            .map(|i| {
+                let config = &app.config_list[*i];
+                let mark = if config.marked { "* " } else { "  " };
+                let mut line = vec![Span::raw(mark)];
+                line.extend(filter::filter_cell(&config.data_id, 40, &query));
+                line.extend(filter::filter_cell(&config.group, 20, &query));
+                line.push(Span::raw(format!("{:<12} ", config.format)));
+                line.extend(filter::filter_cell(&config.app_name, 20, &query));
+                let item = ListItem::new(Line::from(line));
+                if config.marked { item.fg(Color::Yellow) } else { item }
             })
             .collect();
-        let mut items = vec![header];
-        items.extend(config_items);
 
-        let config_list = List::new(items)
-            .block(Block::default()
-                .borders(Borders::ALL)
-                .title("Configs"))
-            .style(Style::default());
-        frame.render_widget(config_list, content_rect);
+        let config_list = List::new(config_items)
+            .style(Style::default())
+            .highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+        match app.config_filter.as_mut() {
+            Some(filter) => {
+                frame.render_stateful_widget(config_list, list_rect, &mut filter.list_state);
+                filter::render_filter_input(frame, filter_rect, filter, app.config_list.len());
+            }
+            None => frame.render_stateful_widget(config_list, list_rect, &mut app.config_list_state),
+        }
+
+        if let Some(config_search) = app.config_search.as_mut() {
+            let contents = app.config_contents.as_ref().map(|(_, configs)| configs.as_slice());
+            search::render_search(frame, body_rect, config_search, contents);
+        }
+        if let Some(config_viewer) = app.config_viewer.as_mut() {
+            viewer::render_config_viewer(frame, body_rect, config_viewer);
+            viewer::render_remote_diff(frame, config_viewer);
+        }
+        if let Some(config_history) = app.config_history.as_mut() {
+            history::render_history(frame, body_rect, config_history);
+        }
+        if let Some(config_listeners) = app.config_listeners.as_mut() {
+            listener::render_listeners(frame, body_rect, config_listeners);
+        }
+        if let Some(config_editor) = app.config_editor.as_mut() {
+            editor::render_config_editor(frame, main_rect, config_editor);
+        }
     }
     else if app.current_menu == app::CurrentMenu::Service {
-        let content_text = Paragraph::new(Text::styled(
-            "Todo", Style::default()))
-            .block(content_block);
-        frame.render_widget(content_text, body_rect);
+        service::render_services(frame, body_rect, app);
+        if let Some(browser) = app.service_instances.as_mut() {
+            instance::render_instances(frame, body_rect, browser);
+        }
+        if app.current_screen == app::CurrentScreen::ServiceSubscribers
+            && let Some(browser) = app.service_subscribers.as_mut()
+        {
+            subscriber::render_subscribers(frame, body_rect, browser);
+        }
     }
     else if app.current_menu == app::CurrentMenu::Namespace {
         let header = ListItem::new(Text::styled(
@@ -157,9 +240,11 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             Style::default().fg(Color::Yellow),
         ));
 
+        let query = app.namespace_filter.as_ref().map(|f| f.query()).unwrap_or_default();
         let namespace_items: Vec<ListItem> = app.namespace_list
             .iter()
             .enumerate()
+            .filter(|(index, _)| app.namespace_filter.as_ref().is_none_or(|f| f.matches.contains(index)))
             .map(|(index, namespace)| {
                 let is_selected = index as u8 == app.namespace_current_line;
                 let item_style = if is_selected {
@@ -168,15 +253,15 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                     Style::default()
                 };
 
-                let content = format!(
-                    "{:<20} {:<36} {:<20} {:<15}",
-                    namespace.ns_name,
-                    namespace.ns_id,
-                    namespace.ns_desc.clone().unwrap_or_else(|| "N/A".to_string()),
-                    format!("{}/{}", namespace.config_count, namespace.quota)
-                );
+                let mut content = filter::filter_cell(&namespace.ns_name, 20, &query);
+                content.extend(filter::filter_cell(&namespace.ns_id, 36, &query));
+                match namespace.ns_desc.as_deref() {
+                    Some(desc) => content.extend(filter::filter_cell(desc, 20, &query)),
+                    None => content.push(Span::raw(format!("{:<20} ", "N/A"))),
+                }
+                content.push(Span::raw(format!("{:<15}", format!("{}/{}", namespace.config_count, namespace.quota))));
 
-                ListItem::new(Text::raw(content))
+                ListItem::new(Line::from(content))
                     .style(item_style)
             })
             .collect();
@@ -184,23 +269,220 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         // Combine header and namespace rows
         let mut items = vec![header];
         items.extend(namespace_items);
-        
+
+        let namespace_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Namespaces");
+        let [list_rect, filter_rect] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(if app.namespace_filter.is_some() { 1 } else { 0 }), // filter row
+            ])
+            .areas(namespace_block.inner(body_rect));
+        frame.render_widget(namespace_block, body_rect);
+
         let namespace_list = List::new(items)
-            .block(Block::default()
-                .borders(Borders::ALL)
-                .title("Namespaces"))
             .style(Style::default());
+        frame.render_widget(namespace_list, list_rect);
 
-        frame.render_widget(namespace_list, body_rect);
+        if let Some(namespace_filter) = app.namespace_filter.as_mut() {
+            filter::render_filter_input(frame, filter_rect, namespace_filter, app.namespace_list.len());
+        }
     }
 
-    // 根据menu选择渲染不同的hint
-    if app.current_menu == app::CurrentMenu::Config {
-        let hint = Paragraph::new("press q to exit");
+    // 根据menu选择渲染不同的hint，有错误时优先显示错误
+    if let Some(message) = &app.error_message {
+        let hint = Paragraph::new(Span::styled(message.as_str(), Style::default().fg(Color::Red)));
+        frame.render_widget(hint, hint_rect);
+    }
+    else if let Some(message) = &app.info_message {
+        let hint = Paragraph::new(Span::styled(message.as_str(), Style::default().fg(Color::Green)));
+        frame.render_widget(hint, hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::Main
+        && let Some(list_filter) = app.current_filter()
+    {
+        let hint = if list_filter.editing {
+            Line::from(vec![
+                Span::raw("type to filter, "),
+                Span::raw("↑/↓: move, "),
+                Span::raw("enter: done, "),
+                Span::raw("esc: clear filter"),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("n/N: next/prev match, "),
+                Span::raw("j/k: move, "),
+                Span::raw("/: edit filter, "),
+                Span::raw("esc: clear filter"),
+            ])
+        };
+        frame.render_widget(Paragraph::new(hint), hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigEdit {
+        let hint = Paragraph::new(
+            Line::from(vec![
+                Span::raw("ctrl-s: validate & review diff, "),
+                Span::raw("esc: close"),
+            ])
+        );
+        frame.render_widget(hint, hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ServiceInstances {
+        let hint = Paragraph::new(
+            Line::from(vec![
+                Span::raw("j/k: move, "),
+                Span::raw("r: refresh, "),
+                Span::raw("e: online/offline, "),
+                Span::raw("w: weight, "),
+                Span::raw("m: metadata, "),
+                Span::raw("n: register, "),
+                Span::raw("d: deregister, "),
+                Span::raw("a: toggle auto-refresh, "),
+                Span::raw("tab: subscribers, "),
+                Span::raw("esc: close"),
+            ])
+        );
+        frame.render_widget(hint, hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ServiceSubscribers {
+        let filter = app.service_subscribers.as_ref().and_then(|b| b.filter.as_ref());
+        let hint = match filter {
+            Some(filter) if filter.editing => Line::from(vec![
+                Span::raw("type to filter, "),
+                Span::raw("↑/↓: move, "),
+                Span::raw("enter: done, "),
+                Span::raw("esc: clear filter"),
+            ]),
+            Some(_) => Line::from(vec![
+                Span::raw("n/N: next/prev match, "),
+                Span::raw("j/k: move, "),
+                Span::raw("/: edit filter, "),
+                Span::raw("esc: clear filter"),
+            ]),
+            None => Line::from(vec![
+                Span::raw("j/k: move, "),
+                Span::raw("[/]: prev/next page, "),
+                Span::raw("/: filter, "),
+                Span::raw("r: refresh, "),
+                Span::raw("tab: instances, "),
+                Span::raw("esc: close"),
+            ]),
+        };
+        frame.render_widget(Paragraph::new(hint), hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigSearch {
+        let editing = app.config_search.as_ref().is_some_and(|s| s.editing);
+        let hint = if editing {
+            Line::from(vec![
+                Span::raw("enter: search, "),
+                Span::raw("tab: results, "),
+                Span::raw("alt-r: regex, "),
+                Span::raw("alt-c: case sensitive, "),
+                Span::raw("esc: close"),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("j/k: move, "),
+                Span::raw("enter: open in viewer, "),
+                Span::raw("/: edit search, "),
+                Span::raw("alt-r: regex, "),
+                Span::raw("alt-c: case sensitive, "),
+                Span::raw("r: reload contents, "),
+                Span::raw("esc: close"),
+            ])
+        };
+        frame.render_widget(Paragraph::new(hint), hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigHistory {
+        let hint = Paragraph::new(
+            Line::from(vec![
+                Span::raw("esc: close, "),
+                Span::raw("enter: view, "),
+                Span::raw("d: diff with current, "),
+                Span::raw("space: mark, "),
+                Span::raw("c: diff with marked, "),
+                Span::raw("R: rollback, "),
+                Span::raw("r: refresh"),
+            ])
+        );
+        frame.render_widget(hint, hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigListeners {
+        let typing = app.config_listeners.as_ref().is_some_and(|b| b.ip_input.is_some());
+        let hint = if typing {
+            Line::from(vec![Span::raw("enter: look up ip, "), Span::raw("esc: cancel")])
+        } else {
+            Line::from(vec![
+                Span::raw("esc: back, "),
+                Span::raw("q: close, "),
+                Span::raw("j/k: move, "),
+                Span::raw("enter: drill into client/config, "),
+                Span::raw("i: look up ip, "),
+                Span::raw("r: refresh"),
+            ])
+        };
+        frame.render_widget(Paragraph::new(hint), hint_rect);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigView {
+        let hint = Paragraph::new(
+            Line::from(vec![
+                Span::raw("esc: close, "),
+                Span::raw("e/E: edit/$EDITOR, "),
+                Span::raw("H: history, "),
+                Span::raw("L: listeners, "),
+                Span::raw("d/x: diff/dismiss server change, "),
+                Span::raw("j/k: scroll, "),
+                Span::raw("g/G: top/bottom, "),
+                Span::raw("h/l: scroll horizontally, "),
+                Span::raw("w: wrap, "),
+                Span::raw("/: search, "),
+                Span::raw("n/N: next/prev match"),
+            ])
+        );
+        frame.render_widget(hint, hint_rect);
+    }
+    else if app.current_menu == app::CurrentMenu::Config {
+        let hint = Paragraph::new(
+            Line::from(vec![
+                Span::raw("h/l: switch namespace, "),
+                Span::raw("j/k: move, "),
+                Span::raw("/: filter, "),
+                Span::raw("s: search server, "),
+                Span::raw("f: find in contents, "),
+                Span::raw("enter: view, "),
+                Span::raw("e/E: edit/$EDITOR, "),
+                Span::raw("H: history, "),
+                Span::raw("L: listeners, "),
+                Span::raw("space: mark, "),
+                Span::raw("d: delete, "),
+                Span::raw("n: new, "),
+                Span::raw("c: clone, "),
+                Span::raw("x/i: export/import, "),
+
+                Span::raw("r: refresh, "),
+                Span::raw("q: exit"),
+            ])
+        );
         frame.render_widget(hint, hint_rect);
     }
     else if app.current_menu == app::CurrentMenu::Service {
-        let hint = Paragraph::new("press q to exit");
+        let hint = Paragraph::new(
+            Line::from(vec![
+                Span::raw("h/l: switch namespace, "),
+                Span::raw("j/k: move, "),
+                Span::raw("[/]: prev/next page, "),
+                Span::raw("o/O: sort page by column/order, "),
+                Span::raw("enter: instances, "),
+                Span::raw("s: subscribers, "),
+                Span::raw("i: register instance, "),
+                Span::raw("n/e/d: new/edit/delete service, "),
+                Span::raw("/: filter, "),
+                Span::raw("r: refresh, "),
+                Span::raw("q: exit"),
+            ])
+        );
         frame.render_widget(hint, hint_rect);
     }
     else if app.current_menu == app::CurrentMenu::Namespace {
@@ -210,15 +492,17 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                 Span::raw("a: add, "),
                 Span::raw("r: refresh, "),
                 Span::raw("d: delete, "),
-                Span::raw("e: edit"),
-                Span::raw("/: search")
+                Span::raw("e: edit, "),
+                Span::raw("/: filter")
             ])
         );
         frame.render_widget(hint, hint_rect);
     }
 
     // 添加 Namespace 的弹出窗口
-    if app.current_screen == app::CurrentScreen::NamespaceDelete {
+    if app.current_screen == app::CurrentScreen::NamespaceDelete
+        && let Some(ns_item) = app.selected_namespace()
+    {
         let area = centered_rect(60, 50, frame.area());
         frame.render_widget(Clear, area); //清空背景内容
 
@@ -239,7 +523,6 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         let title_text = Paragraph::new("Delete Namespace");
         frame.render_widget(title_text, title_rect);
 
-        let ns_item= &app.namespace_list[app.namespace_current_line as usize];
         let content_text = Paragraph::new(vec![
                 Line::from(Span::raw("")), //empty line for spacing
                 Line::from(Span::raw("Are you sure you want to delete this namespace?")),
@@ -249,7 +532,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                 ]),
                 Line::from(vec![
                     Span::raw("ns_id: "),
-                    Span::styled(&ns_item.ns_name, Style::default().fg(Color::Red)),
+                    Span::styled(&ns_item.ns_id, Style::default().fg(Color::Red)),
                 ]),
             ]).block( 
                 Block::default().borders(Borders::BOTTOM)
@@ -416,9 +699,96 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             ]));
         frame.render_widget(hint_text, hint_rect);
     }
+    else if app.current_screen == app::CurrentScreen::ConfigQuery
+        && let Some(query_form) = app.config_query_form.as_mut()
+    {
+        let area = centered_rect(50, 40, frame.area());
+        filter::render_config_query(frame, area, query_form);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigTransfer
+        && let Some(transfer_view) = app.config_transfer.as_mut()
+    {
+        let area = centered_rect(70, 60, frame.area());
+        transfer::render_transfer(frame, area, transfer_view);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigClone
+        && let Some(clone_view) = app.config_clone.as_mut()
+    {
+        let area = centered_rect(85, 85, frame.area());
+        clone::render_clone(frame, area, clone_view, &app.namespace_list);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigNew
+        && let Some(form) = app.config_new.as_mut()
+    {
+        let area = centered_rect(60, 70, frame.area());
+        create::render_new_config(frame, area, form, &app.config_templates);
+    }
+    else if app.current_screen == app::CurrentScreen::InstanceRegister
+        && let Some(form) = app.instance_register.as_mut()
+    {
+        let area = centered_rect(60, 80, frame.area());
+        register::render_register(frame, area, form);
+    }
+    else if app.current_screen == app::CurrentScreen::ServiceEdit
+        && let Some(form) = app.service_edit.as_mut()
+    {
+        let area = centered_rect(60, 60, frame.area());
+        service_edit::render_service_edit(frame, area, form);
+    }
+    else if app.current_screen == app::CurrentScreen::InstanceEdit
+        && let Some(edit) = app.instance_edit.as_mut()
+    {
+        let area = centered_rect(60, 50, frame.area());
+        instance_edit::render_instance_edit(frame, area, edit);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigDelete
+        && let Some(delete) = app.config_delete.as_ref()
+    {
+        delete::render_delete(frame, centered_rect(60, 50, frame.area()), delete);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigMerge
+        && let (Some(merge_view), Some(publish)) = (app.config_merge.as_mut(), app.config_publish.as_ref())
+    {
+        let area = centered_rect(95, 90, frame.area());
+        let title = format!(" Conflict: {} | {} ", publish.data_id, publish.group);
+        merge::render_merge(frame, area, &title, merge_view);
+    }
+    else if app.current_screen == app::CurrentScreen::ConfigPublishConfirm
+        && let Some(publish) = app.config_publish.as_mut()
+    {
+        let area = centered_rect(90, 85, frame.area());
+        frame.render_widget(Clear, area); //清空背景内容
+
+        let namespace = if publish.ns_id.is_empty() { "public" } else { &publish.ns_id };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Publish {} | {} | {} ", publish.data_id, publish.group, namespace))
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [diff_rect, hint_rect] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1), // diff
+                Constraint::Length(1), // hint row
+            ])
+            .areas(inner);
+        diff::render_diff(frame, diff_rect, &mut publish.diff);
+
+        let hint_text = Paragraph::new(
+            Line::from(vec![
+                Span::styled("y: publish (takes effect immediately), ", Style::default().fg(Color::Yellow)),
+                Span::raw("n/esc: cancel, "),
+                Span::raw("j/k: scroll, "),
+                Span::raw("]/[: next/prev change, "),
+                Span::raw("s: side-by-side"),
+            ]));
+        frame.render_widget(hint_text, hint_rect);
+    }
 }
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+pub(crate) fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     // Cut the given rectangle into three vertical pieces
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -0,0 +1,175 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::clone::{CloneAction, CloneField, ConfigClone};
+use crate::app::NamespaceItem;
+
+use super::diff::render_diff;
+
+fn action_label(action: CloneAction) -> (&'static str, Color) {
+    match action {
+        CloneAction::Create => ("create", Color::Green),
+        CloneAction::Overwrite => ("overwrite", Color::Yellow),
+        CloneAction::Unchanged => ("unchanged", Color::DarkGray),
+        CloneAction::Skip => ("skip", Color::DarkGray),
+        CloneAction::Conflict => ("exists", Color::Red),
+    }
+}
+
+pub fn render_clone(frame: &mut Frame, area: Rect, clone: &mut ConfigClone, namespaces: &[NamespaceItem]) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let src = namespaces
+        .iter()
+        .find(|ns| ns.ns_id == clone.src_ns)
+        .map_or(clone.src_ns.as_str(), |ns| ns.ns_name.as_str());
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Clone {} config(s) from {} ", clone.items.len(), src))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let target = namespaces
+        .iter()
+        .find(|ns| ns.ns_id == clone.target_ns)
+        .map_or("-".to_string(), |ns| format!("{} ({})", ns.ns_name, if ns.ns_id.is_empty() { "public" } else { &ns.ns_id }));
+
+    if !clone.previewed {
+        render_setup(frame, inner, clone, &target);
+        return;
+    }
+
+    let [summary_rect, list_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1), Constraint::Length(1)])
+        .areas(inner);
+
+    let mut summary = vec![
+        Span::raw(format!("to {} | policy: ", target)),
+        Span::styled(clone.policy.name(), Style::default().fg(Color::Cyan)),
+        Span::raw(" |"),
+    ];
+    for action in [CloneAction::Create, CloneAction::Overwrite, CloneAction::Unchanged, CloneAction::Skip, CloneAction::Conflict] {
+        let count = clone.count(action);
+        if count > 0 {
+            let (label, color) = action_label(action);
+            summary.push(Span::styled(format!(" {} {}", count, label), Style::default().fg(color)));
+        }
+    }
+    frame.render_widget(Paragraph::new(Line::from(summary)), summary_rect);
+
+    if let Some(diff) = clone.diff.as_mut() {
+        render_diff(frame, list_rect, diff);
+    } else {
+        let items: Vec<ListItem> = clone
+            .items
+            .iter()
+            .map(|item| {
+                let action = item.action(clone.policy);
+                let (label, color) = action_label(action);
+                let mut line = vec![
+                    Span::styled(format!("{:<10} ", label), Style::default().fg(color)),
+                    Span::raw(format!("{:<40} {} -> {}", item.data_id, item.group, clone.target_group(item))),
+                ];
+                // 跳过和内容相同的配置没有发布，不显示结果
+                let result = item.result.as_ref().filter(|_| matches!(action, CloneAction::Create | CloneAction::Overwrite));
+                match result {
+                    Some(Ok(())) => line.insert(0, Span::styled("✓ ", Style::default().fg(Color::Green))),
+                    Some(Err(err)) => {
+                        line.insert(0, Span::styled("✗ ", Style::default().fg(Color::Red)));
+                        line.push(Span::styled(format!("  {}", err), Style::default().fg(Color::Red)));
+                    }
+                    None => {}
+                }
+                ListItem::new(Line::from(line))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+        frame.render_stateful_widget(list, list_rect, &mut clone.list_state);
+    }
+
+    let hint = if clone.diff.is_some() {
+        Line::from(vec![
+            Span::raw("esc: back, "),
+            Span::raw("j/k: scroll, "),
+            Span::raw("]/[: next/prev change, "),
+            Span::raw("s: side-by-side"),
+        ])
+    } else if clone.finished {
+        Line::from(vec![Span::raw("j/k: move, "), Span::raw("enter: diff, "), Span::raw("any other key: close")])
+    } else {
+        Line::from(vec![
+            Span::styled("y: clone (takes effect immediately), ", Style::default().fg(Color::Yellow)),
+            Span::raw("enter: diff, "),
+            Span::raw("p: change policy, "),
+            Span::raw("n/esc: back"),
+        ])
+    };
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
+
+// 第一步：选择目标命名空间、group 和冲突策略
+fn render_setup(frame: &mut Frame, area: Rect, clone: &mut ConfigClone, target: &str) {
+    let [ns_rect, group_rect, policy_rect, list_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // target namespace
+            Constraint::Length(3), // target group
+            Constraint::Length(3), // conflict policy
+            Constraint::Min(1),    // configs to clone
+            Constraint::Length(1), // hint row
+        ])
+        .areas(area);
+
+    let field_block = |field: CloneField, title: &'static str| {
+        let block = Block::default().borders(Borders::ALL).title(title);
+        if clone.focus == field {
+            block.style(Style::default().fg(Color::Green))
+        } else {
+            block
+        }
+    };
+    let picker = |value: String| Line::from(vec![Span::raw("< "), Span::styled(value, Style::default().fg(Color::Reset)), Span::raw(" >")]);
+
+    frame.render_widget(
+        Paragraph::new(picker(target.to_string())).block(field_block(CloneField::Namespace, "target namespace")),
+        ns_rect,
+    );
+    frame.render_widget(
+        Paragraph::new(picker(clone.policy.name().to_string()))
+            .block(field_block(CloneField::Policy, "if it already exists with different content")),
+        policy_rect,
+    );
+
+    let group_block = field_block(CloneField::Group, "target group(empty to keep the source group)");
+    let focused = clone.focus == CloneField::Group;
+    clone.group.set_block(group_block);
+    clone.group.set_style(Style::default().fg(Color::Reset));
+    clone.group.set_cursor_line_style(Style::default());
+    clone.group.set_cursor_style(if focused {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+    });
+    frame.render_widget(&clone.group, group_rect);
+
+    let items: Vec<ListItem> = clone
+        .items
+        .iter()
+        .map(|item| ListItem::new(format!("{:<40} {}", item.data_id, item.group)))
+        .collect();
+    frame.render_widget(List::new(items).block(Block::default().borders(Borders::TOP).title("configs")), list_rect);
+
+    let hint = Line::from(vec![
+        Span::raw("esc: cancel, "),
+        Span::raw("enter: preview, "),
+        Span::raw("tab/↓ shift-tab/↑: switch focus, "),
+        Span::raw("←/→: choose"),
+    ]);
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
@@ -0,0 +1,87 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::create::{NewConfigField, NewConfigForm, CONFIG_TYPES};
+use crate::config::ConfigTemplate;
+
+// 新建配置弹窗：文本框和左右切换的选择框，当前焦点绿色高亮
+pub fn render_new_config(frame: &mut Frame, area: Rect, form: &mut NewConfigForm, templates: &[ConfigTemplate]) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let popup_block = Block::default()
+        .borders(Borders::NONE)
+        .style(Style::default().bg(Color::DarkGray));
+    frame.render_widget(popup_block, area);
+
+    let [title_rect, content_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // title row
+            Constraint::Min(3), // content row
+            Constraint::Length(1), // hint row
+        ])
+        .areas(area);
+
+    let namespace = if form.ns_id.is_empty() { "public" } else { &form.ns_id };
+    let title = if form.is_checking() {
+        format!("New Config in {} (checking...)", namespace)
+    } else {
+        format!("New Config in {}", namespace)
+    };
+    frame.render_widget(Paragraph::new(title), title_rect);
+
+    let rects = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(NewConfigField::ALL.map(|_| Constraint::Length(3)))
+        .split(content_rect);
+
+    for (field, rect) in NewConfigField::ALL.into_iter().zip(rects.iter()) {
+        let focused = form.focus == field;
+        let mut block = Block::default().borders(Borders::ALL).title(field.label());
+        if focused {
+            block = block.style(Style::default().fg(Color::Green));
+        }
+
+        if field.is_picker() {
+            let value = match field {
+                NewConfigField::Format => CONFIG_TYPES[form.format].to_string(),
+                _ => match form.template.map(|t| &templates[t]) {
+                    Some(template) => format!("{} ({}, {} lines)", template.name, template.type_, template.content.lines().count()),
+                    None if templates.is_empty() => "blank (no templates in config/templates)".to_string(),
+                    None => "blank".to_string(),
+                },
+            };
+            let arrow = Style::default().fg(if focused { Color::Green } else { Color::DarkGray });
+            let picker = Paragraph::new(Line::from(vec![
+                Span::styled("< ", arrow),
+                Span::styled(value, Style::default().fg(Color::Reset)),
+                Span::styled(" >", arrow),
+            ]))
+            .block(block);
+            frame.render_widget(picker, *rect);
+        } else if let Some(textarea) = form.input(field) {
+            textarea.set_block(block);
+            textarea.set_style(Style::default().fg(Color::Reset));
+            textarea.set_cursor_line_style(Style::default());
+            textarea.set_cursor_style(if focused {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            });
+            frame.render_widget(&*textarea, *rect);
+        }
+    }
+
+    let hint_text = Paragraph::new(
+        Line::from(vec![
+            Span::raw("esc: cancel, "),
+            Span::raw("enter: edit content, "),
+            Span::raw("tab/↓ shift-tab/↑: switch focus, "),
+            Span::raw("←/→: choose format/template"),
+        ]));
+    frame.render_widget(hint_text, hint_rect);
+}
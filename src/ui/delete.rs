@@ -0,0 +1,78 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::delete::ConfigDelete;
+
+// 确认前列出要删除的配置，有失败时列出每个配置的结果
+pub fn render_delete(frame: &mut Frame, area: Rect, delete: &ConfigDelete) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let popup_block = Block::default()
+        .borders(Borders::NONE)
+        .style(Style::default().bg(Color::DarkGray));
+    frame.render_widget(popup_block, area);
+
+    let [title_rect, content_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // title row
+            Constraint::Min(3), // content row
+            Constraint::Length(1), // hint row
+        ])
+        .areas(area);
+
+    let title = match delete.targets.len() {
+        1 => "Delete Config".to_string(),
+        n => format!("Delete {} Configs", n),
+    };
+    frame.render_widget(Paragraph::new(title), title_rect);
+
+    let namespace = if delete.ns_id.is_empty() { "public" } else { &delete.ns_id };
+    let mut lines = vec![
+        Line::from(Span::raw("")), //empty line for spacing
+        match (&delete.results, delete.request) {
+            (Some(_), _) => Line::from(Span::styled("Some configs were not deleted:", Style::default().fg(Color::Yellow))),
+            (None, Some(_)) => Line::from(Span::raw("Deleting...")),
+            (None, None) => Line::from(Span::raw("Are you sure you want to delete these configs? This cannot be undone.")),
+        },
+        Line::from(vec![
+            Span::raw("namespace: "),
+            Span::styled(namespace, Style::default().fg(Color::Red)),
+        ]),
+    ];
+    for (i, target) in delete.targets.iter().enumerate() {
+        let mut line = vec![
+            Span::raw("data_id: "),
+            Span::styled(&target.data_id, Style::default().fg(Color::Red)),
+            Span::raw("  group: "),
+            Span::styled(&target.group, Style::default().fg(Color::Red)),
+        ];
+        match delete.results.as_ref().and_then(|results| results.get(i)) {
+            Some(Ok(())) => line.insert(0, Span::styled("✓ ", Style::default().fg(Color::Green))),
+            Some(Err(err)) => {
+                line.insert(0, Span::styled("✗ ", Style::default().fg(Color::Red)));
+                line.push(Span::styled(format!("  {}", err), Style::default().fg(Color::Yellow)));
+            }
+            None => {}
+        }
+        lines.push(Line::from(line));
+    }
+    let content_text = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::BOTTOM));
+    frame.render_widget(content_text, content_rect);
+
+    let hint_text = if delete.results.is_some() {
+        Paragraph::new("any key: close")
+    } else {
+        Paragraph::new(Line::from(vec![
+            Span::raw("y: confirm, "),
+            Span::raw("n/esc: cancel"),
+        ]))
+    };
+    frame.render_widget(hint_text, hint_rect);
+}
@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::app::diff::{DiffLine, DiffTag, DiffView};
+use crate::highlight::cut_spans;
+
+// 在 area 内渲染对比内容，外框和标题由调用方负责
+pub fn render_diff(frame: &mut Frame, area: Rect, diff: &mut DiffView) {
+    let [header_rect, content_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .areas(area);
+
+    diff.set_height(content_rect.height as usize);
+
+    let (added, removed) = diff.stats();
+    let header = Line::from(vec![
+        Span::styled(format!("--- {}  ", diff.old_label), Style::default().fg(Color::Red)),
+        Span::styled(format!("+++ {}  ", diff.new_label), Style::default().fg(Color::Green)),
+        Span::styled(format!("+{added} -{removed}"), Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(Paragraph::new(header), header_rect);
+
+    if diff.is_empty() {
+        frame.render_widget(Paragraph::new("No differences"), content_rect);
+        return;
+    }
+
+    let gutter = diff.lines.len().max(1).to_string().len();
+    if diff.side_by_side {
+        let [left_rect, right_rect] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .areas(content_rect);
+        let visible = diff.rows.iter().skip(diff.scroll).take(diff.height);
+        let (left, right): (Vec<Line>, Vec<Line>) = visible
+            .map(|(l, r)| {
+                (
+                    diff_row(l.as_ref(), l.as_ref().and_then(|l| l.old_no), gutter, left_rect.width),
+                    diff_row(r.as_ref(), r.as_ref().and_then(|r| r.new_no), gutter, right_rect.width),
+                )
+            })
+            .unzip();
+        frame.render_widget(Paragraph::new(left), left_rect);
+        frame.render_widget(Paragraph::new(right), right_rect);
+    } else {
+        let rows: Vec<Line> = diff
+            .lines
+            .iter()
+            .skip(diff.scroll)
+            .take(diff.height)
+            .map(|line| {
+                let number = line.new_no.or(line.old_no);
+                diff_row(Some(line), number, gutter, content_rect.width)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(rows), content_rect);
+    }
+}
+
+// 一行：行号、+/- 标记和按修改类型着色的内容，超出宽度截断
+fn diff_row(line: Option<&DiffLine>, number: Option<usize>, gutter: usize, width: u16) -> Line<'static> {
+    let Some(line) = line else {
+        return Line::from("");
+    };
+    let (marker, style) = match line.tag {
+        DiffTag::Equal => (" ", Style::default()),
+        DiffTag::Delete => ("-", Style::default().fg(Color::Red)),
+        DiffTag::Insert => ("+", Style::default().fg(Color::Green)),
+    };
+    let number = number.map_or(" ".repeat(gutter), |n| format!("{n:>gutter$}"));
+    let text_width = (width as usize).saturating_sub(gutter + 3);
+    let mut spans = vec![
+        Span::styled(format!("{number} "), Style::default().fg(Color::DarkGray)),
+        Span::styled(format!("{marker} "), style),
+    ];
+    spans.extend(cut_spans(&[Span::styled(line.text.clone(), style)], 0, text_width));
+    Line::from(spans)
+}
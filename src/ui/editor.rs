@@ -0,0 +1,45 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Span,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::editor::ConfigEditor;
+
+pub fn render_config_editor(frame: &mut Frame, area: Rect, editor: &mut ConfigEditor) {
+    frame.render_widget(Clear, area);
+
+    let namespace = if editor.ns_id.is_empty() { "public" } else { &editor.ns_id };
+    let modified = if editor.is_modified() { " [modified]" } else { "" };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " {} {} | {} | {} | {}{} ",
+            if editor.meta.is_some() { "New" } else { "Edit" },
+            editor.data_id, editor.group, editor.type_, namespace, modified
+        ))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [content_rect, status_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .areas(inner);
+
+    editor.textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
+    editor.textarea.set_cursor_line_style(Style::default());
+    frame.render_widget(&editor.textarea, content_rect);
+
+    // 校验失败时显示错误位置，否则显示光标位置
+    let status = match &editor.error {
+        Some(err) => Span::styled(format!("{:?}: {}", editor.format, err), Style::default().fg(Color::Red)),
+        None => {
+            let (row, col) = editor.textarea.cursor();
+            Span::styled(format!("{}:{}", row + 1, col + 1), Style::default().fg(Color::DarkGray))
+        }
+    };
+    frame.render_widget(Paragraph::new(status), status_rect);
+}
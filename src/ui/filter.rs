@@ -0,0 +1,81 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::filter::{fuzzy_match, ConfigQueryForm, ListFilter};
+use crate::highlight::overlay;
+
+// 按列宽补齐单元格，匹配过滤词的字符加粗标红
+pub fn filter_cell(text: &str, width: usize, query: &str) -> Vec<Span<'static>> {
+    let match_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let ranges: Vec<_> = fuzzy_match(query, text)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(start, end)| (start, end, match_style))
+        .collect();
+    overlay(vec![Span::raw(format!("{:<width$} ", text))], &ranges)
+}
+
+// 列表底部的过滤输入框，右侧显示匹配的行数
+pub fn render_filter_input(frame: &mut Frame, area: Rect, filter: &mut ListFilter, total: usize) {
+    let count = format!(" {}/{} ", filter.matches.len(), total);
+    let [prompt_rect, input_rect, count_rect] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(1), Constraint::Min(1), Constraint::Length(count.len() as u16)])
+        .areas(area);
+    frame.render_widget(Paragraph::new("/"), prompt_rect);
+    filter.input.set_cursor_line_style(Style::default());
+    filter.input.set_cursor_style(if filter.editing {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+    });
+    frame.render_widget(&filter.input, input_rect);
+    frame.render_widget(Paragraph::new(count).style(Style::default().fg(Color::Cyan)), count_rect);
+}
+
+pub fn render_config_query(frame: &mut Frame, area: Rect, form: &mut ConfigQueryForm) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Search configs on server ")
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [data_id_rect, group_rect, note_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(1), Constraint::Length(1)])
+        .areas(inner);
+
+    let focused = [!form.group_focused, form.group_focused];
+    for ((textarea, title), (focused, rect)) in [(&mut form.data_id, "data_id"), (&mut form.group, "group")]
+        .into_iter()
+        .zip(focused.into_iter().zip([data_id_rect, group_rect]))
+    {
+        let block = Block::default().borders(Borders::ALL).title(title);
+        textarea.set_block(if focused { block.style(Style::default().fg(Color::Green)) } else { block });
+        textarea.set_style(Style::default().fg(Color::Reset));
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_cursor_style(if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        });
+        frame.render_widget(&*textarea, rect);
+    }
+
+    let note = Paragraph::new(vec![
+        Line::from(" use * as wildcard, e.g. order-*.yaml or *GROUP"),
+        Line::from(" leave both empty to list the whole namespace"),
+    ])
+    .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(note, note_rect);
+
+    let hint = Line::from(vec![Span::raw("esc: cancel, "), Span::raw("enter: search, "), Span::raw("tab: switch focus")]);
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
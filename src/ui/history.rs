@@ -0,0 +1,89 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::history::ConfigHistoryBrowser;
+use crate::ui::{centered_rect, diff, viewer};
+
+pub fn render_history(frame: &mut Frame, area: Rect, history: &mut ConfigHistoryBrowser) {
+    frame.render_widget(Clear, area);
+
+    let namespace = if history.ns_id.is_empty() { "public" } else { &history.ns_id };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " History ({}/{}) | {} | {} | {} ",
+            history.items.len(),
+            history.total,
+            history.data_id,
+            history.group,
+            namespace
+        ))
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [header_rect, list_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .areas(inner);
+
+    let header = Paragraph::new(Text::styled(
+        format!("  {:<20} {:<8} {:<16} {:<40}", "modified", "op", "operator", "source_ip"),
+        Style::default().fg(Color::Yellow),
+    ));
+    frame.render_widget(header, header_rect);
+
+    let items: Vec<ListItem> = history
+        .items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mark = if history.marked == Some(index) { "*" } else { " " };
+            let style = match item.op_type {
+                "delete" => Style::default().fg(Color::Red),
+                "insert" => Style::default().fg(Color::Green),
+                _ => Style::default(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{mark} {:<20} ", item.modified)),
+                Span::styled(format!("{:<8} ", item.op_type), style),
+                Span::raw(format!("{:<16} {:<40}", item.src_user, item.src_ip)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+    frame.render_stateful_widget(list, list_rect, &mut history.list_state);
+
+    if let Some(revision) = history.viewer.as_mut() {
+        viewer::render_config_viewer(frame, area, revision);
+    }
+
+    if let Some(diff_view) = history.diff.as_mut() {
+        let area = centered_rect(90, 85, frame.area());
+        frame.render_widget(Clear, area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Compare {} | {} ", history.data_id, history.group))
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [diff_rect, hint_rect] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .areas(inner);
+        diff::render_diff(frame, diff_rect, diff_view);
+        let hint = Line::from(vec![
+            Span::raw("esc: close, "),
+            Span::raw("j/k: scroll, "),
+            Span::raw("]/[: next/prev change, "),
+            Span::raw("s: side-by-side"),
+        ]);
+        frame.render_widget(Paragraph::new(hint), hint_rect);
+    }
+}
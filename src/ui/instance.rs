@@ -0,0 +1,77 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::instance::{InstanceBrowser, INSTANCE_REFRESH_INTERVAL};
+use crate::resp::instance_list_resp::Instance;
+
+pub fn render_instances(frame: &mut Frame, area: Rect, browser: &mut InstanceBrowser) {
+    frame.render_widget(Clear, area);
+
+    let namespace = if browser.ns_id.is_empty() { "public" } else { &browser.ns_id };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Instances | {}@@{} | {} ", browser.group, browser.service, namespace))
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [status_rect, header_rect, list_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(1)])
+        .areas(inner);
+
+    let refresh = if browser.auto_refresh {
+        Span::styled(
+            format!("auto-refresh every {}s", INSTANCE_REFRESH_INTERVAL.as_secs()),
+            Style::default().fg(Color::Cyan),
+        )
+    } else {
+        Span::styled("auto-refresh off", Style::default().fg(Color::DarkGray))
+    };
+    let updated = browser
+        .updated_at
+        .map_or("loading...".to_string(), |t| format!("updated {}", t.format("%H:%M:%S")));
+    let status = Line::from(vec![
+        Span::raw(format!("{}/{} healthy | ", browser.healthy_count(), browser.instances.len())),
+        refresh,
+        Span::raw(format!(" | {}", updated)),
+    ]);
+    frame.render_widget(Paragraph::new(status), status_rect);
+
+    let header = format!(
+        "{:<22} {:<12} {:<7} {:<8} {:<8} {:<10} {}",
+        "ip:port", "cluster", "weight", "healthy", "enabled", "ephemeral", "metadata"
+    );
+    frame.render_widget(Paragraph::new(header).style(Style::default().fg(Color::Yellow)), header_rect);
+
+    let items: Vec<ListItem> = browser.instances.iter().map(instance_row).collect();
+    let list = List::new(items).highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+    frame.render_stateful_widget(list, list_rect, &mut browser.list_state);
+}
+
+// 已下线的实例为灰色，其余按健康状态显示绿色或红色
+fn instance_row(instance: &Instance) -> ListItem<'static> {
+    let color = match (instance.enabled, instance.healthy) {
+        (false, _) => Color::DarkGray,
+        (true, true) => Color::Green,
+        (true, false) => Color::Red,
+    };
+    let mut metadata: Vec<String> = instance.metadata.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    metadata.sort();
+    let text = format!(
+        "{:<22} {:<12} {:<7} {:<8} {:<8} {:<10} {}",
+        format!("{}:{}", instance.ip, instance.port),
+        instance.clusterName,
+        format!("{:?}", instance.weight),
+        instance.healthy,
+        instance.enabled,
+        instance.ephemeral,
+        metadata.join(", ")
+    );
+    ListItem::new(text).style(Style::default().fg(color))
+}
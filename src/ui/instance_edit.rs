@@ -0,0 +1,84 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::instance_edit::{InstanceEdit, InstanceEditKind};
+
+pub fn render_instance_edit(frame: &mut Frame, area: Rect, edit: &mut InstanceEdit) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let action = match edit.kind {
+        InstanceEditKind::Enabled if edit.before.enabled => "Take offline",
+        InstanceEditKind::Enabled => "Bring online",
+        InstanceEditKind::Weight => "Edit weight",
+        InstanceEditKind::Metadata => "Edit metadata",
+        InstanceEditKind::Deregister => "Deregister",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            " {} | {}:{} | {}@@{} ",
+            action, edit.before.ip, edit.before.port, edit.group, edit.service
+        ))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [target_rect, content_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(3), Constraint::Length(1)])
+        .areas(inner);
+
+    let namespace = if edit.ns_id.is_empty() { "public" } else { &edit.ns_id };
+    let target = Line::from(vec![
+        Span::raw("cluster: "),
+        Span::styled(&edit.before.clusterName, Style::default().fg(Color::Cyan)),
+        Span::raw("  namespace: "),
+        Span::styled(namespace, Style::default().fg(Color::Cyan)),
+        Span::raw(format!(
+            "  weight: {:?}  enabled: {}  healthy: {}",
+            edit.before.weight, edit.before.enabled, edit.before.healthy
+        )),
+    ]);
+    frame.render_widget(Paragraph::new(target), target_rect);
+
+    if edit.confirming() {
+        let lines: Vec<Line> = edit
+            .impact
+            .iter()
+            .map(|line| {
+                let style = match line.chars().next() {
+                    _ if line.starts_with("WARNING") => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    Some('+') => Style::default().fg(Color::Green),
+                    Some('-') => Style::default().fg(Color::Red),
+                    Some('~') => Style::default().fg(Color::Yellow),
+                    _ => Style::default(),
+                };
+                Line::styled(line.clone(), style)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), content_rect);
+    } else {
+        let title = match edit.kind {
+            InstanceEditKind::Metadata => "metadata (one key=value per line)",
+            _ => "weight (0 drains all traffic)",
+        };
+        edit.input.set_block(Block::default().borders(Borders::ALL).title(title).style(Style::default().fg(Color::Green)));
+        edit.input.set_cursor_line_style(Style::default());
+        frame.render_widget(&edit.input, content_rect);
+    }
+
+    let hint = match (edit.confirming(), edit.request, edit.kind) {
+        (_, Some(_), _) => Line::from("updating..."),
+        (true, None, _) => Line::from(vec![
+            Span::styled("y: apply (takes effect immediately), ", Style::default().fg(Color::Yellow)),
+            Span::raw("n/esc: back"),
+        ]),
+        (false, None, InstanceEditKind::Metadata) => Line::from("ctrl-s: review, esc: cancel"),
+        (false, None, _) => Line::from("enter: review, esc: cancel"),
+    };
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
@@ -0,0 +1,98 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::listener::{ListenerBrowser, ListenerQuery, ListenerRow};
+
+pub fn render_listeners(frame: &mut Frame, area: Rect, browser: &mut ListenerBrowser) {
+    frame.render_widget(Clear, area);
+
+    let title = match &browser.query {
+        ListenerQuery::Config { ns_id, data_id, group } => {
+            let namespace = if ns_id.is_empty() { "public" } else { ns_id };
+            format!(" Listeners | {} | {} | {} ", data_id, group, namespace)
+        }
+        ListenerQuery::Ip(ip) => format!(" Configs listened by {} ", ip),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [status_rect, header_rect, list_rect, input_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(if browser.ip_input.is_some() { 3 } else { 0 }),
+        ])
+        .areas(inner);
+
+    // 有客户端没拿到最新内容时标红
+    let status = match (browser.updated_at, browser.stale_count()) {
+        (None, _) => Line::from("loading..."),
+        (Some(t), 0) => Line::from(vec![
+            Span::styled(format!("{} up to date", browser.rows.len()), Style::default().fg(Color::Green)),
+            Span::raw(format!(" | updated {}", t.format("%H:%M:%S"))),
+        ]),
+        (Some(t), stale) => Line::from(vec![
+            Span::styled(
+                format!("{}/{} stale", stale, browser.rows.len()),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(" | updated {}", t.format("%H:%M:%S"))),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(status), status_rect);
+
+    let header = match browser.query {
+        ListenerQuery::Config { .. } => format!("  {:<24} {:<34} {}", "client ip", "client md5", "status"),
+        ListenerQuery::Ip(_) => format!(
+            "  {:<30} {:<16} {:<12} {:<34} {}",
+            "data_id", "group", "namespace", "client md5", "status"
+        ),
+    };
+    frame.render_widget(Paragraph::new(header).style(Style::default().fg(Color::Yellow)), header_rect);
+
+    let by_ip = matches!(browser.query, ListenerQuery::Ip(_));
+    let items: Vec<ListItem> = browser.rows.iter().map(|row| listener_row(row, by_ip)).collect();
+    let list = List::new(items).highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+    frame.render_stateful_widget(list, list_rect, &mut browser.list_state);
+
+    if let Some(input) = browser.ip_input.as_mut() {
+        input.set_block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("client ip")
+                .style(Style::default().fg(Color::Green)),
+        );
+        input.set_cursor_line_style(Style::default());
+        frame.render_widget(&*input, input_rect);
+    }
+}
+
+fn listener_row(row: &ListenerRow, by_ip: bool) -> ListItem<'static> {
+    let listener = &row.listener;
+    let (status, color) = match row.server_md5.as_deref() {
+        None => ("config deleted".to_string(), Color::DarkGray),
+        Some(md5) if md5 == listener.md5 => ("up to date".to_string(), Color::Green),
+        Some(md5) => (format!("stale (server {})", md5), Color::Red),
+    };
+    let prefix = if by_ip {
+        let namespace = if listener.tenant.is_empty() { "public" } else { &listener.tenant };
+        format!("  {:<30} {:<16} {:<12} ", listener.data_id, listener.group, namespace)
+    } else {
+        format!("  {:<24} ", listener.ip)
+    };
+    ListItem::new(Line::from(vec![
+        Span::raw(format!("{}{:<34} ", prefix, listener.md5)),
+        Span::styled(status, Style::default().fg(color)),
+    ]))
+}
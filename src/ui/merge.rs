@@ -0,0 +1,75 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::diff::DiffTag;
+use crate::app::merge::MergeView;
+use crate::highlight::cut_spans;
+
+// 三栏并排显示 base/mine/server，相对 base 的改动着色
+pub fn render_merge(frame: &mut Frame, area: Rect, title: &str, merge: &mut MergeView) {
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title.to_string())
+        .border_style(Style::default().fg(Color::Red));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [summary_rect, columns_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1), Constraint::Length(1)])
+        .areas(inner);
+
+    let summary = if merge.conflicts == 0 {
+        Span::styled("The config was changed on the server, changes merge cleanly", Style::default().fg(Color::Yellow))
+    } else {
+        Span::styled(
+            format!("The config was changed on the server, {} conflicting block(s)", merge.conflicts),
+            Style::default().fg(Color::Red),
+        )
+    };
+    frame.render_widget(Paragraph::new(summary), summary_rect);
+
+    let columns: [Rect; 3] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+        .areas(columns_rect);
+    merge.set_height(columns[0].height.saturating_sub(1) as usize);
+
+    let panes = [
+        ("base", &merge.base_lines, Color::Red),
+        ("mine", &merge.mine_lines, Color::Green),
+        ("server", &merge.server_lines, Color::Cyan),
+    ];
+    for ((label, lines, color), rect) in panes.into_iter().zip(columns) {
+        let block = Block::default().borders(Borders::LEFT).title(label);
+        let inner = block.inner(rect);
+        frame.render_widget(block, rect);
+        let rows: Vec<Line> = lines
+            .iter()
+            .skip(merge.scroll)
+            .take(merge.height)
+            .map(|(text, tag)| {
+                let style = match tag {
+                    DiffTag::Equal => Style::default(),
+                    _ => Style::default().fg(color),
+                };
+                Line::from(cut_spans(&[Span::styled(text.clone(), style)], 0, inner.width as usize))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(rows), inner);
+    }
+
+    let hint = Line::from(vec![
+        Span::raw("m: edit merged result, "),
+        Span::raw("o: overwrite with mine, "),
+        Span::raw("esc: abort, "),
+        Span::raw("j/k: scroll"),
+    ]);
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
@@ -0,0 +1,63 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::register::{InstanceRegisterForm, REGISTER_FIELDS};
+
+// 与新建命名空间的弹窗一样：标题行、逐行的输入框和提示行，当前焦点绿色高亮
+pub fn render_register(frame: &mut Frame, area: Rect, form: &mut InstanceRegisterForm) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let popup_block = Block::default()
+        .borders(Borders::NONE)
+        .style(Style::default().bg(Color::DarkGray));
+    frame.render_widget(popup_block, area);
+
+    let [title_rect, content_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // title row
+            Constraint::Min(3), // content row
+            Constraint::Length(1), // hint row
+        ])
+        .areas(area);
+
+    let namespace = if form.ns_id.is_empty() { "public" } else { &form.ns_id };
+    let title = if form.request.is_some() {
+        format!("Register Instance in {} (registering...)", namespace)
+    } else {
+        format!("Register Instance in {}", namespace)
+    };
+    frame.render_widget(Paragraph::new(title), title_rect);
+
+    let rects = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(REGISTER_FIELDS.map(|_| Constraint::Length(3)))
+        .split(content_rect);
+    for (i, (textarea, rect)) in form.fields.iter_mut().zip(rects.iter()).enumerate() {
+        let focused = form.focus == i;
+        let mut block = Block::default().borders(Borders::ALL).title(REGISTER_FIELDS[i]);
+        if focused {
+            block = block.style(Style::default().fg(Color::Green));
+        }
+        textarea.set_block(block);
+        textarea.set_style(Style::default().fg(Color::Reset));
+        textarea.set_cursor_line_style(Style::default());
+        textarea.set_cursor_style(if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        });
+        frame.render_widget(&*textarea, *rect);
+    }
+
+    let hint_text = Paragraph::new(Line::from(vec![
+        Span::raw("esc: cancel, "),
+        Span::raw("enter: register, "),
+        Span::raw("tab: switch focus"),
+    ]));
+    frame.render_widget(hint_text, hint_rect);
+}
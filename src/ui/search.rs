@@ -0,0 +1,107 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::search::ContentSearch;
+use crate::app::ConfigItem;
+use crate::highlight::{cut_spans, overlay};
+
+// 匹配位置太靠右时，左侧保留的列数
+const MATCH_CONTEXT: usize = 20;
+
+pub fn render_search(frame: &mut Frame, area: Rect, search: &mut ContentSearch, contents: Option<&[ConfigItem]>) {
+    frame.render_widget(Clear, area);
+
+    let namespace = if search.ns_id.is_empty() { "public" } else { &search.ns_id };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Search config contents | {} ", namespace))
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [input_rect, status_rect, list_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(1), Constraint::Min(1)])
+        .areas(inner);
+
+    // 输入框，右上角显示正则和大小写开关
+    let toggle = |label: &'static str, on: bool| {
+        if on {
+            Span::styled(label, Style::default().fg(Color::Black).bg(Color::Cyan))
+        } else {
+            Span::styled(label, Style::default().fg(Color::DarkGray))
+        }
+    };
+    let toggles = Line::from(vec![
+        toggle(" .* regex ", search.regex),
+        Span::raw(" "),
+        toggle(" Aa case ", search.case_sensitive),
+    ])
+    .right_aligned();
+    let input_block = Block::default().borders(Borders::ALL).title("search").title(toggles);
+    search.input.set_block(if search.editing {
+        input_block.style(Style::default().fg(Color::Green))
+    } else {
+        input_block
+    });
+    search.input.set_style(Style::default().fg(Color::Reset));
+    search.input.set_cursor_line_style(Style::default());
+    search.input.set_cursor_style(if search.editing {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+    });
+    frame.render_widget(&search.input, input_rect);
+
+    let status = match (contents, &search.searched) {
+        (None, _) => "loading config contents...".to_string(),
+        (Some(configs), None) => format!("{} configs loaded", configs.len()),
+        (Some(configs), Some(query)) => format!(
+            "{} matching line(s) in {} of {} configs for \"{}\"",
+            search.line_count(),
+            search.hits.len(),
+            configs.len(),
+            query
+        ),
+    };
+    frame.render_widget(Paragraph::new(status).style(Style::default().fg(Color::Cyan)), status_rect);
+
+    let Some(configs) = contents else {
+        return;
+    };
+    let width = list_rect.width as usize;
+    let match_style = Style::default().bg(Color::Yellow).fg(Color::Black);
+    let items: Vec<ListItem> = search
+        .rows
+        .iter()
+        .filter_map(|(hit, line)| {
+            let hit = &search.hits[*hit];
+            let config = configs.get(hit.config)?;
+            let Some(line) = line else {
+                return Some(ListItem::new(Line::from(vec![
+                    Span::styled(config.data_id.clone(), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("  {}", config.group), Style::default().fg(Color::DarkGray)),
+                ])));
+            };
+            let line = &hit.lines[*line];
+            let number = format!("{:>6}: ", line.line_no + 1);
+            let ranges: Vec<_> = line.ranges.iter().map(|(start, end)| (*start, *end, match_style)).collect();
+            let spans = overlay(vec![Span::raw(line.text.clone())], &ranges);
+            // 第一个匹配显示不下时向左滚动
+            let first = line.ranges.first().map_or(0, |(start, _)| line.text[..*start].width());
+            let text_width = width.saturating_sub(number.len());
+            let skip = if first + MATCH_CONTEXT > text_width { first.saturating_sub(MATCH_CONTEXT) } else { 0 };
+            let mut row = vec![Span::styled(number, Style::default().fg(Color::DarkGray))];
+            row.extend(cut_spans(&spans, skip, text_width));
+            Some(ListItem::new(Line::from(row)))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, list_rect, &mut search.list_state);
+}
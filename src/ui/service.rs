@@ -0,0 +1,104 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
+    Frame,
+};
+
+use crate::app::service::{ServiceItem, ServiceSort};
+use crate::app::App;
+use crate::ui::filter;
+
+// 列标题、宽度和对应的排序列
+const COLUMNS: [(&str, usize, ServiceSort); 6] = [
+    ("name", 40, ServiceSort::Name),
+    ("group", 20, ServiceSort::Group),
+    ("clusters", 9, ServiceSort::Clusters),
+    ("instances", 10, ServiceSort::Instances),
+    ("healthy", 8, ServiceSort::Healthy),
+    ("threshold", 10, ServiceSort::Threshold),
+];
+
+pub fn render_services(frame: &mut Frame, area: Rect, app: &mut App) {
+    let [tab_rect, content_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(1)])
+        .areas(area);
+
+    let titles: Vec<Span> = app.namespace_list.iter().map(|ns| Span::raw(ns.ns_name.clone())).collect();
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL).title(" service management "))
+        .highlight_style(Style::default().fg(Color::Green))
+        .select(app.service_current_tab);
+    frame.render_widget(tabs, tab_rect);
+
+    // Nacos 不支持排序，多页时提示排序只作用于当前页
+    let sort_scope = if app.service_page_count() > 1 { ", sorted within page" } else { "" };
+    let block = Block::default().borders(Borders::ALL).title(format!(
+        "Services (page {}/{}, total {}{})",
+        app.service_page_no,
+        app.service_page_count(),
+        app.service_total,
+        sort_scope
+    ));
+    let [header_rect, list_rect, filter_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(if app.service_filter.is_some() { 1 } else { 0 }),
+        ])
+        .areas(block.inner(content_rect));
+    frame.render_widget(block, content_rect);
+
+    // 当前排序列的标题后面显示方向
+    let arrow = if app.service_sort_desc { "▼" } else { "▲" };
+    let header: String = COLUMNS
+        .iter()
+        .map(|(title, width, sort)| {
+            let title = if *sort == app.service_sort { format!("{}{}", title, arrow) } else { title.to_string() };
+            format!("{:<width$} ", title)
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(format!("  {}", header)).style(Style::default().fg(Color::Yellow)),
+        header_rect,
+    );
+
+    let query = app.service_filter.as_ref().map(|f| f.query()).unwrap_or_default();
+    let rows: Vec<usize> = match app.service_filter.as_ref() {
+        Some(filter) => filter.matches.clone(),
+        None => (0..app.service_list.len()).collect(),
+    };
+    let items: Vec<ListItem> = rows.iter().map(|i| service_row(&app.service_list[*i], &query)).collect();
+    let list = List::new(items).highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+    match app.service_filter.as_mut() {
+        Some(filter) => {
+            frame.render_stateful_widget(list, list_rect, &mut filter.list_state);
+            filter::render_filter_input(frame, filter_rect, filter, app.service_list.len());
+        }
+        None => frame.render_stateful_widget(list, list_rect, &mut app.service_list_state),
+    }
+}
+
+fn service_row(service: &ServiceItem, query: &str) -> ListItem<'static> {
+    let mut line = vec![Span::raw("  ")];
+    line.extend(filter::filter_cell(&service.name, COLUMNS[0].1, query));
+    line.extend(filter::filter_cell(&service.group, COLUMNS[1].1, query));
+    line.push(Span::raw(format!("{:<9} ", service.cluster_count)));
+    line.push(Span::raw(format!("{:<10} ", service.ip_count)));
+    // 全部健康为绿色，部分健康为黄色，没有健康实例为红色
+    let healthy_color = match (service.healthy_count, service.ip_count) {
+        (_, 0) => Color::Reset,
+        (healthy, total) if healthy >= total => Color::Green,
+        (0, _) => Color::Red,
+        _ => Color::Yellow,
+    };
+    line.push(Span::styled(format!("{:<8} ", service.healthy_count), Style::default().fg(healthy_color)));
+    let threshold = service.protect_threshold.map_or("-".to_string(), |t| format!("{:.2}", t));
+    // 保护阈值已触发时标红
+    let threshold_style = if service.triggered { Style::default().fg(Color::Red) } else { Style::default() };
+    line.push(Span::styled(threshold, threshold_style));
+    ListItem::new(Line::from(line))
+}
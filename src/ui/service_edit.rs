@@ -0,0 +1,86 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::service_edit::{ServiceEditForm, ServiceEditMode, SERVICE_FIELDS};
+
+// 表单页与注册实例一样逐行输入框，确认页与实例修改一样按行着色显示前后对比
+pub fn render_service_edit(frame: &mut Frame, area: Rect, form: &mut ServiceEditForm) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let action = match form.mode {
+        ServiceEditMode::Create => "New service",
+        ServiceEditMode::Update => "Edit service",
+        ServiceEditMode::Delete => "Delete service",
+    };
+    let namespace = if form.ns_id.is_empty() { "public" } else { &form.ns_id };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} | {} ", action, namespace))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [content_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .areas(inner);
+
+    if form.loading() {
+        frame.render_widget(Paragraph::new("loading..."), content_rect);
+    } else if form.confirming() {
+        let lines: Vec<Line> = form
+            .summary
+            .iter()
+            .map(|line| {
+                let style = match line.chars().next() {
+                    _ if line.starts_with("WARNING") => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    Some('+') => Style::default().fg(Color::Green),
+                    Some('-') => Style::default().fg(Color::Red),
+                    Some('~') => Style::default().fg(Color::Yellow),
+                    _ => Style::default(),
+                };
+                Line::styled(line.clone(), style)
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), content_rect);
+    } else {
+        let rects = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(SERVICE_FIELDS.map(|_| Constraint::Length(3)))
+            .split(content_rect);
+        for (i, rect) in rects.iter().enumerate() {
+            let focused = form.focus == i;
+            let editable = form.editable(i);
+            let mut block = Block::default().borders(Borders::ALL).title(SERVICE_FIELDS[i]);
+            if focused {
+                block = block.style(Style::default().fg(Color::Green));
+            } else if !editable {
+                block = block.style(Style::default().fg(Color::DarkGray));
+            }
+            let textarea = &mut form.fields[i];
+            textarea.set_block(block);
+            textarea.set_cursor_line_style(Style::default());
+            textarea.set_cursor_style(if focused {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            });
+            frame.render_widget(&*textarea, *rect);
+        }
+    }
+
+    let hint = match (form.confirming(), form.request) {
+        (_, Some(_)) if form.loading() => Line::from("esc: cancel"),
+        (_, Some(_)) => Line::from("saving..."),
+        (true, None) => Line::from(vec![
+            Span::styled("y: apply (takes effect immediately), ", Style::default().fg(Color::Yellow)),
+            Span::raw(if form.mode == ServiceEditMode::Delete { "n/esc: cancel" } else { "n/esc: back" }),
+        ]),
+        (false, None) => Line::from("enter: review, tab: switch focus, esc: cancel"),
+    };
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
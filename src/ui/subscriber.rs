@@ -0,0 +1,75 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::subscriber::SubscriberBrowser;
+use crate::resp::subscriber_list_resp::Subscriber;
+use crate::ui::filter;
+
+// 列标题和宽度，最后一列命名空间不补齐
+const COLUMNS: [(&str, usize); 3] = [("ip:port", 22), ("agent", 32), ("app", 20)];
+
+pub fn render_subscribers(frame: &mut Frame, area: Rect, browser: &mut SubscriberBrowser) {
+    frame.render_widget(Clear, area);
+
+    let namespace = if browser.ns_id.is_empty() { "public" } else { &browser.ns_id };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Subscribers | {}@@{} | {} ", browser.group, browser.service, namespace))
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [status_rect, header_rect, list_rect, filter_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+            Constraint::Length(if browser.filter.is_some() { 1 } else { 0 }),
+        ])
+        .areas(inner);
+
+    let status = if browser.request.is_some() {
+        "loading...".to_string()
+    } else {
+        format!("page {}/{}, total {}", browser.page_no, browser.page_count(), browser.total)
+    };
+    frame.render_widget(Paragraph::new(status), status_rect);
+
+    let header: String = COLUMNS.iter().map(|(title, width)| format!("{:<width$} ", title)).collect();
+    frame.render_widget(
+        Paragraph::new(format!("{}namespace", header)).style(Style::default().fg(Color::Yellow)),
+        header_rect,
+    );
+
+    let query = browser.filter.as_ref().map(|f| f.query()).unwrap_or_default();
+    let rows: Vec<usize> = match browser.filter.as_ref() {
+        Some(filter) => filter.matches.clone(),
+        None => (0..browser.subscribers.len()).collect(),
+    };
+    let items: Vec<ListItem> = rows.iter().map(|i| subscriber_row(&browser.subscribers[*i], &query)).collect();
+    let list = List::new(items).highlight_style(Style::default().bg(Color::Gray).fg(Color::Black));
+    let total = browser.subscribers.len();
+    match browser.filter.as_mut() {
+        Some(filter) => {
+            frame.render_stateful_widget(list, list_rect, &mut filter.list_state);
+            filter::render_filter_input(frame, filter_rect, filter, total);
+        }
+        None => frame.render_stateful_widget(list, list_rect, &mut browser.list_state),
+    }
+}
+
+fn subscriber_row(subscriber: &Subscriber, query: &str) -> ListItem<'static> {
+    let namespace = if subscriber.namespaceId.is_empty() { "public" } else { &subscriber.namespaceId };
+    let mut line = vec![];
+    line.extend(filter::filter_cell(&subscriber.addrStr, COLUMNS[0].1, query));
+    line.extend(filter::filter_cell(&subscriber.agent, COLUMNS[1].1, query));
+    line.extend(filter::filter_cell(&subscriber.app, COLUMNS[2].1, query));
+    line.extend(filter::filter_cell(namespace, 0, query));
+    ListItem::new(Line::from(line))
+}
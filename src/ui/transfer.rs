@@ -0,0 +1,131 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::transfer::{ConfigTransfer, TransferKind};
+
+pub fn render_transfer(frame: &mut Frame, area: Rect, transfer: &mut ConfigTransfer) {
+    frame.render_widget(Clear, area); //清空背景内容
+    let namespace = if transfer.ns_id.is_empty() { "public" } else { &transfer.ns_id };
+    let title = match transfer.kind {
+        TransferKind::Export => format!(" Export configs from {} ", namespace),
+        TransferKind::Import => format!(" Import configs into {} ", namespace),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [body_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .areas(inner);
+
+    let hint = if let Some(result) = &transfer.result {
+        render_result(frame, body_rect, result);
+        Line::from(Span::raw("any key: close"))
+    } else if let Some(entries) = &transfer.entries {
+        let [summary_rect, list_rect] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .areas(body_rect);
+        let summary = Line::from(vec![
+            Span::raw(format!("{} config(s) in the archive, if one already exists: ", entries.len())),
+            Span::styled(transfer.policy.name(), Style::default().fg(Color::Cyan)),
+            Span::raw(if transfer.request.is_some() { " (importing...)" } else { "" }),
+        ]);
+        frame.render_widget(Paragraph::new(summary), summary_rect);
+        let items: Vec<ListItem> = entries
+            .iter()
+            .map(|e| ListItem::new(format!("{:<40} {:<20} {}", e.data_id, e.group, e.type_)))
+            .collect();
+        frame.render_widget(List::new(items).block(Block::default().borders(Borders::TOP)), list_rect);
+        Line::from(vec![
+            Span::styled("y: import (takes effect immediately), ", Style::default().fg(Color::Yellow)),
+            Span::raw("p: change policy, "),
+            Span::raw("n/esc: back"),
+        ])
+    } else {
+        render_form(frame, body_rect, transfer);
+        Line::from(vec![
+            Span::raw("esc: cancel, "),
+            Span::raw(if transfer.kind == TransferKind::Export { "enter: export" } else { "enter: read archive, " }),
+            Span::raw(if transfer.kind == TransferKind::Import { "tab: switch focus, ←/→: choose policy" } else { "" }),
+        ])
+    };
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
+
+// 第一步：文件路径，导入时还有冲突策略
+fn render_form(frame: &mut Frame, area: Rect, transfer: &mut ConfigTransfer) {
+    let [path_rect, second_rect, _] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Min(0)])
+        .areas(area);
+
+    let focused = |yes: bool| if yes { Style::default().fg(Color::Green) } else { Style::default() };
+    let path_title = match transfer.kind {
+        TransferKind::Export => "save to",
+        TransferKind::Import => "zip file exported from Nacos",
+    };
+    transfer
+        .path
+        .set_block(Block::default().borders(Borders::ALL).title(path_title).style(focused(!transfer.policy_focused)));
+    transfer.path.set_style(Style::default().fg(Color::Reset));
+    transfer.path.set_cursor_line_style(Style::default());
+    transfer.path.set_cursor_style(if transfer.policy_focused {
+        Style::default()
+    } else {
+        Style::default().add_modifier(Modifier::REVERSED)
+    });
+    frame.render_widget(&transfer.path, path_rect);
+
+    match transfer.kind {
+        TransferKind::Export => {
+            let scope = match transfer.ids.len() {
+                0 => "all configs in the namespace".to_string(),
+                n => format!("{} marked config(s)", n),
+            };
+            let status = if transfer.request.is_some() { " (exporting...)" } else { "" };
+            frame.render_widget(Paragraph::new(format!(" exports {}{}", scope, status)), second_rect);
+        }
+        TransferKind::Import => {
+            let picker = Line::from(vec![
+                Span::raw("< "),
+                Span::styled(transfer.policy.name(), Style::default().fg(Color::Reset)),
+                Span::raw(" >"),
+            ]);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title("if a config already exists")
+                .style(focused(transfer.policy_focused));
+            frame.render_widget(Paragraph::new(picker).block(block), second_rect);
+        }
+    }
+}
+
+fn render_result(frame: &mut Frame, area: Rect, result: &crate::resp::config_import_resp::ConfigImportResult) {
+    let skipped = result.skipData.as_deref().unwrap_or_default();
+    let failed = result.failData.as_deref().unwrap_or_default();
+    let mut lines = vec![Line::from(vec![
+        Span::styled(format!("imported {}", result.succCount), Style::default().fg(Color::Green)),
+        Span::raw(format!(", skipped {}", result.skipCount)),
+        Span::styled(format!(", failed {}", failed.len()), Style::default().fg(if failed.is_empty() { Color::Reset } else { Color::Red })),
+    ])];
+    for (label, keys, color) in [("skipped", skipped, Color::DarkGray), ("failed", failed, Color::Red)] {
+        if !keys.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("{}:", label)));
+        }
+        for key in keys {
+            lines.push(Line::from(Span::styled(format!("  {} | {}", key.dataId, key.group), Style::default().fg(color))));
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), area);
+}
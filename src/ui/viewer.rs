@@ -0,0 +1,138 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::viewer::ConfigViewer;
+use crate::ui::{centered_rect, diff};
+use crate::highlight::{cut_spans, highlight_line, overlay, wrap_spans};
+
+pub fn render_config_viewer(frame: &mut Frame, area: Rect, viewer: &mut ConfigViewer) {
+    frame.render_widget(Clear, area);
+
+    let namespace = if viewer.ns_id.is_empty() { "public" } else { &viewer.ns_id };
+    let position = format!(
+        " {}/{}{} ",
+        (viewer.scroll + 1).min(viewer.lines.len()),
+        viewer.lines.len(),
+        if viewer.wrap { " wrap" } else { "" }
+    );
+    let revision = viewer.revision.as_ref().map(|r| format!(" @ {r}")).unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {}{} | {} | {} | {} ", viewer.data_id, revision, viewer.group, viewer.type_, namespace))
+        .title_bottom(Line::from(position).right_aligned())
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [banner_rect, content_rect, search_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(if viewer.remote_change.is_some() { 1 } else { 0 }),
+            Constraint::Min(1),
+            Constraint::Length(if viewer.search_input.is_some() { 1 } else { 0 }),
+        ])
+        .areas(inner);
+
+    // 别人发布或删除了配置
+    if let Some(change) = viewer.remote_change.as_ref() {
+        let at = change.at.format("%H:%M:%S");
+        let banner = if change.deleted {
+            format!(" Deleted on server at {} | x: dismiss ", at)
+        } else {
+            format!(" Changed on server by another publish at {} | d: diff with what you were viewing, x: dismiss ", at)
+        };
+        frame.render_widget(
+            Paragraph::new(banner).style(Style::default().bg(Color::Yellow).fg(Color::Black)),
+            banner_rect,
+        );
+    }
+
+    // 搜索输入框
+    if let Some(textarea) = viewer.search_input.as_mut() {
+        let [prompt_rect, input_rect] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .areas(search_rect);
+        frame.render_widget(Paragraph::new("/"), prompt_rect);
+        textarea.set_cursor_line_style(Style::default());
+        frame.render_widget(&*textarea, input_rect);
+    }
+
+    viewer.height = content_rect.height as usize;
+    if !viewer.loaded {
+        frame.render_widget(Paragraph::new("Loading..."), content_rect);
+        return;
+    }
+
+    let gutter = viewer.lines.len().max(1).to_string().len();
+    let text_width = (content_rect.width as usize).saturating_sub(gutter + 1);
+    let match_style = Style::default().bg(Color::Yellow).fg(Color::Black);
+    let current_style = Style::default().bg(Color::LightRed).fg(Color::Black);
+    let gutter_style = Style::default().fg(Color::DarkGray);
+
+    let mut rows: Vec<Line> = vec![];
+    for (line_no, line) in viewer.lines.iter().enumerate().skip(viewer.scroll) {
+        if rows.len() >= viewer.height {
+            break;
+        }
+
+        let ranges: Vec<_> = viewer
+            .matches
+            .iter()
+            .enumerate()
+            .filter(|(_, (l, _, _))| *l == line_no)
+            .map(|(i, (_, start, end))| {
+                let style = if viewer.current_match == Some(i) { current_style } else { match_style };
+                (*start, *end, style)
+            })
+            .collect();
+        let spans = overlay(highlight_line(viewer.format, line), &ranges);
+
+        let parts = if viewer.wrap {
+            wrap_spans(&spans, text_width)
+        } else {
+            vec![cut_spans(&spans, viewer.hscroll, text_width)]
+        };
+        for (i, part) in parts.into_iter().enumerate() {
+            let number = if i == 0 { format!("{:>gutter$} ", line_no + 1) } else { " ".repeat(gutter + 1) };
+            let mut row = vec![Span::styled(number, gutter_style)];
+            row.extend(part);
+            rows.push(Line::from(row));
+        }
+    }
+    rows.truncate(viewer.height);
+    frame.render_widget(Paragraph::new(rows), content_rect);
+}
+
+// 查看器里收到的服务端变化与变化前内容的对比
+pub fn render_remote_diff(frame: &mut Frame, viewer: &mut ConfigViewer) {
+    let Some(diff_view) = viewer.remote_diff.as_mut() else {
+        return;
+    };
+    let area = centered_rect(90, 85, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Changed on server | {} | {} ", viewer.data_id, viewer.group))
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [diff_rect, hint_rect] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .areas(inner);
+    diff::render_diff(frame, diff_rect, diff_view);
+    let hint = Line::from(vec![
+        Span::raw("esc: close, "),
+        Span::raw("j/k: scroll, "),
+        Span::raw("]/[: next/prev change, "),
+        Span::raw("s: side-by-side"),
+    ]);
+    frame.render_widget(Paragraph::new(hint), hint_rect);
+}
@@ -0,0 +1,145 @@
+// 发布前按配置类型检查内容能否被解析，出错时给出行列号（从 1 开始）
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::highlight::Format;
+
+#[derive(Debug, PartialEq)]
+pub struct ValidationError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+pub fn validate(format: Format, content: &str) -> Result<(), ValidationError> {
+    match format {
+        Format::Yaml => validate_yaml(content),
+        Format::Json => validate_json(content),
+        Format::Xml => validate_xml(content),
+        Format::Toml => validate_toml(content),
+        Format::Properties => validate_properties(content),
+        // html 通常不是严格的 xml，文本不需要检查
+        Format::Html | Format::Text => Ok(()),
+    }
+}
+
+fn validate_yaml(content: &str) -> Result<(), ValidationError> {
+    // 一个配置里可能有多个以 --- 分隔的文档
+    for document in serde_yaml::Deserializer::from_str(content) {
+        if let Err(err) = serde_yaml::Value::deserialize(document) {
+            let (line, column) = err.location().map_or((1, 1), |loc| (loc.line(), loc.column()));
+            // serde_yaml 的错误信息末尾自带位置，去掉避免重复
+            let message = err.to_string();
+            let message = message.split(" at line ").next().unwrap_or_default().to_string();
+            return Err(ValidationError { line, column, message });
+        }
+    }
+    Ok(())
+}
+
+fn validate_json(content: &str) -> Result<(), ValidationError> {
+    serde_json::from_str::<serde_json::Value>(content)
+        .map(|_| ())
+        .map_err(|err| ValidationError {
+            line: err.line(),
+            column: err.column(),
+            message: err.to_string(),
+        })
+}
+
+fn validate_xml(content: &str) -> Result<(), ValidationError> {
+    roxmltree::Document::parse(content)
+        .map(|_| ())
+        .map_err(|err| ValidationError {
+            line: err.pos().row as usize,
+            column: err.pos().col as usize,
+            message: err.to_string(),
+        })
+}
+
+fn validate_toml(content: &str) -> Result<(), ValidationError> {
+    toml::from_str::<toml::Table>(content).map(|_| ()).map_err(|err| {
+        let offset = err.span().map_or(0, |span| span.start);
+        let (line, column) = line_column(content, offset);
+        ValidationError {
+            line,
+            column,
+            message: err.message().to_string(),
+        }
+    })
+}
+
+// properties 几乎总能解析，只有 \uXXXX 转义写错时 java 会拒绝加载
+fn validate_properties(content: &str) -> Result<(), ValidationError> {
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') || trimmed.starts_with('!') {
+            continue;
+        }
+        let mut chars = line.char_indices();
+        while let Some((i, c)) = chars.next() {
+            if c != '\\' {
+                continue;
+            }
+            // 跳过被转义的字符，只检查 \u
+            if let Some((_, 'u')) = chars.next() {
+                let hex: String = line[i + 2..].chars().take(4).collect();
+                if hex.len() < 4 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(ValidationError {
+                        line: line_no + 1,
+                        column: line[..i].chars().count() + 1,
+                        message: "malformed \\uxxxx encoding".to_string(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_contents() {
+        assert!(validate(Format::Yaml, "a: 1\n---\nb: [1, 2]\n").is_ok());
+        assert!(validate(Format::Json, r#"{"a": [1, true, null]}"#).is_ok());
+        assert!(validate(Format::Xml, "<a><b x=\"1\"/></a>").is_ok());
+        assert!(validate(Format::Toml, "[server]\nport = 8080\n").is_ok());
+        assert!(validate(Format::Properties, "name=\\u4e2d\\u6587\n").is_ok());
+        assert!(validate(Format::Text, "{ not json").is_ok());
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let err = validate(Format::Yaml, "a: 1\nb: [1, 2\n").unwrap_err();
+        assert_eq!(err.line, 3);
+
+        let err = validate(Format::Json, "{\n  \"a\": 1,\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        let err = validate(Format::Xml, "<a>\n  <b></c>\n</a>").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = validate(Format::Toml, "a = 1\nb = \n").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        let err = validate(Format::Properties, "a=1\nb=\\u12g4\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}